@@ -0,0 +1,47 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::bail;
+use kailua_sync::provider::blobs::BlobStore;
+use kailua_sync::telemetry::TelemetryArgs;
+use std::path::PathBuf;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct BlobsArgs {
+    /// Directory of the local blob cache to operate on
+    #[clap(long, env)]
+    pub blob_store_dir: PathBuf,
+    /// Directory of blob files to verify and add to the cache
+    #[clap(long, env)]
+    pub import_dir: Option<PathBuf>,
+    /// Directory to copy all cached blob files to
+    #[clap(long, env)]
+    pub export_dir: Option<PathBuf>,
+    #[clap(flatten)]
+    pub telemetry: TelemetryArgs,
+}
+
+pub async fn blobs(args: BlobsArgs) -> anyhow::Result<()> {
+    if args.import_dir.is_none() && args.export_dir.is_none() {
+        bail!("Neither an import nor an export directory was specified.");
+    }
+    let store = BlobStore::open(args.blob_store_dir)?;
+    if let Some(import_dir) = args.import_dir {
+        store.import(&import_dir).await?;
+    }
+    if let Some(export_dir) = args.export_dir {
+        store.export(&export_dir).await?;
+    }
+    Ok(())
+}
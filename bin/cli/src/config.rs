@@ -175,13 +175,13 @@ pub async fn config(args: ConfigArgs) -> anyhow::Result<()> {
     let portal_address = system_config
         .optimismPortal()
         .stall_with_context(context.clone(), "SystemConfig::optimismPortal")
-        .await
+        .await?
         .0;
     debug!("{portal_address}");
     let dgf_address = system_config
         .disputeGameFactory()
         .stall_with_context(context.clone(), "SystemConfig::disputeGameFactory")
-        .await
+        .await?
         .0;
     debug!("{dgf_address}");
     // report factory address
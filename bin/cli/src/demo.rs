@@ -100,6 +100,7 @@ pub async fn demo(args: DemoArgs, verbosity: u8, data_dir: PathBuf) -> anyhow::R
         enable_experimental_witness_endpoint: args.enable_experimental_witness_endpoint,
         max_fault_proving_delay: 0,
         max_validity_proving_delay: 0,
+        bond_collection_interval: 600,
         #[cfg(feature = "devnet")]
         l1_head_jump_back: 0,
         validator_signer: ValidatorSignerArgs {
@@ -112,6 +113,15 @@ pub async fn demo(args: DemoArgs, verbosity: u8, data_dir: PathBuf) -> anyhow::R
             txn_timeout: 0,
             exec_gas_premium: 0,
             blob_gas_premium: 0,
+            txn_max_replacements: 3,
+            cancel_stale_txns: false,
+            max_fee_per_gas: None,
+            max_fee_per_blob_gas: None,
+            hourly_fee_budget: None,
+            daily_fee_budget: None,
+            defer_above_gas_price: None,
+            dry_run: false,
+            dry_run_report: None,
         },
         proving: args.proving,
         boundless: args.boundless,
@@ -158,8 +168,8 @@ pub async fn handle_blocks(
             context,
             tracer,
             "sync_status",
-            retry_res_ctx_timeout!(provider.op_provider.sync_status().await)
-        );
+            retry_res_ctx_timeout!(OpNode => provider.op_provider.sync_status().await)
+        )?;
         let Some(safe_l2_number) = sync_status["safe_l2"]["number"]
             .as_u64()
             .map(|v| v.saturating_sub(args.provider.op_rpc_delay))
@@ -171,13 +181,13 @@ pub async fn handle_blocks(
             context,
             tracer,
             "l1_head",
-            retry_res_ctx_timeout!(provider
+            retry_res_ctx_timeout!(EthRpc => provider
                 .l1_provider
                 .get_block_by_number(BlockNumberOrTag::Latest)
                 .await
                 .context("get_block_by_number")?
                 .ok_or_else(|| anyhow!("Failed to fetch l1 head")))
-        );
+        )?;
         // start from most recent block if unspecified
         if last_proven.is_none() {
             last_proven = Some(
@@ -192,36 +202,36 @@ pub async fn handle_blocks(
                 context,
                 tracer,
                 "agreed_l2_block",
-                retry_res_ctx_timeout!(provider
+                retry_res_ctx_timeout!(OpGeth => provider
                     .l2_provider
                     .get_block_by_number(BlockNumberOrTag::Number(agreed_l2_block_number))
                     .await
                     .context("get_block_by_number")?
                     .ok_or_else(|| anyhow!("Failed to fetch agreed l2 block")))
-            );
+            )?;
             let agreed_l2_output_root = await_tel!(
                 context,
                 tracer,
                 "agreed_l2_output_root",
                 retry_res_ctx_timeout!(
-                    provider
+                    OpNode => provider
                         .op_provider
                         .output_at_block(agreed_l2_block_number)
                         .await
                 )
-            );
+            )?;
             let claimed_l2_block_number = agreed_l2_block_number + args.num_blocks_per_proof;
             let claimed_l2_output_root = await_tel!(
                 context,
                 tracer,
                 "claimed_l2_output_root",
                 retry_res_ctx_timeout!(
-                    provider
+                    OpNode => provider
                         .op_provider
                         .output_at_block(claimed_l2_block_number)
                         .await
                 )
-            );
+            )?;
             // request proof
             if n == args.nth_proof_to_process {
                 channel
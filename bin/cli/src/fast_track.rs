@@ -32,8 +32,10 @@ use opentelemetry::global::tracer;
 use opentelemetry::trace::{FutureExt, Status, TraceContextExt, Tracer};
 use risc0_circuit_recursion::control_id::BN254_IDENTITY_CONTROL_ID;
 use risc0_zkvm::ALLOWED_CONTROL_ROOT;
+use std::path::PathBuf;
 use std::str::FromStr;
-use tracing::info;
+use std::time::Duration;
+use tracing::{info, warn};
 
 /// Fast-track migrate a rollup to use Kailua
 #[derive(clap::Args, Debug, Clone)]
@@ -84,6 +86,12 @@ pub struct FastTrackArgs {
     /// Secret key of L1 guardian wallet
     #[clap(flatten)]
     pub guardian_signer: Option<GuardianSignerArgs>,
+    /// Directory to write Safe transaction payloads to and collect owner signatures from
+    #[clap(long, env)]
+    pub safe_signatures_dir: Option<PathBuf>,
+    /// Maximum number of seconds to wait for owner signatures of each Safe transaction (unbounded if unset)
+    #[clap(long, env)]
+    pub safe_signatures_timeout: Option<u64>,
 
     /// Address of the vanguard to set
     #[clap(long, env)]
@@ -130,11 +138,11 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
     let portal_address = system_config
         .optimismPortal()
         .stall_with_context(context.clone(), "SystemConfig::optimismPortal")
-        .await;
+        .await?;
     let dgf_address = system_config
         .disputeGameFactory()
         .stall_with_context(context.clone(), "SystemConfig::disputeGameFactory")
-        .await;
+        .await?;
 
     // initialize owner wallet
     info!("Initializing owner wallet.");
@@ -156,25 +164,31 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
     let game_count = dispute_game_factory
         .gameCount()
         .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
-        .await;
+        .await?;
     info!("There have been {game_count} games created using DisputeGameFactory");
     let dispute_game_factory_ownable = OwnableUpgradeable::new(dgf_address, &owner_provider);
     let factory_owner_address = dispute_game_factory_ownable
         .owner()
         .stall_with_context(context.clone(), "DisputeGameFactory::owner")
-        .await;
+        .await?;
     let factory_owner_safe = Safe::new(factory_owner_address, &owner_provider);
     info!("Safe({:?})", factory_owner_safe.address());
     let safe_owners = factory_owner_safe
         .getOwners()
         .stall_with_context(context.clone(), "Safe::getOwners")
-        .await;
+        .await?;
     info!("Safe::owners({:?})", &safe_owners);
+    let safe_threshold = factory_owner_safe
+        .getThreshold()
+        .stall_with_context(context.clone(), "Safe::getThreshold")
+        .await?;
+    info!("Safe::threshold({safe_threshold})");
     let owner_address = owner_wallet.default_signer().address();
-    if safe_owners.first().unwrap() != &owner_address {
-        bail!("Incorrect owner key.");
-    } else if safe_owners.len() != 1 {
-        bail!("Expected exactly one owner of safe account.");
+    if !safe_owners.contains(&owner_address) {
+        warn!("Owner key {owner_address} is not an owner of the safe account.");
+    }
+    if safe_threshold > U256::from(1) && args.safe_signatures_dir.is_none() {
+        bail!("Safe signatures directory is required for a threshold of {safe_threshold}.");
     }
 
     // initialize deployment wallet
@@ -207,11 +221,11 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
         tracer,
         "root_claim",
         retry_res_ctx_timeout!(
-            op_node_provider
+            OpNode => op_node_provider
                 .output_at_block(args.starting_block_number)
                 .await
         )
-    );
+    )?;
     info!("Deploying KailuaTreasury contract to L1 rpc.");
     let receipt = KailuaTreasury::deploy_builder(
         &deployer_provider,
@@ -246,20 +260,22 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
             dispute_game_factory.setImplementation(KAILUA_GAME_TYPE, kailua_treasury_impl_addr),
             &factory_owner_safe,
             owner_address,
+            &args.safe_signatures_dir,
+            args.safe_signatures_timeout.map(Duration::from_secs),
         )
     )?;
     assert_eq!(
         dispute_game_factory
             .gameImpls(KAILUA_GAME_TYPE)
             .stall_with_context(context.clone(), "DisputeGameFactory::gameImpls")
-            .await,
+            .await?,
         kailua_treasury_impl_addr
     );
 
     if !dispute_game_factory
         .initBonds(KAILUA_GAME_TYPE)
         .stall_with_context(context.clone(), "DisputeGameFactory::initBonds")
-        .await
+        .await?
         .is_zero()
     {
         info!("Setting KailuaTreasury initialization bond value in DisputeGameFactory to zero.");
@@ -271,13 +287,15 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
                 dispute_game_factory.setInitBond(KAILUA_GAME_TYPE, U256::ZERO),
                 &factory_owner_safe,
                 owner_address,
+                &args.safe_signatures_dir,
+                args.safe_signatures_timeout.map(Duration::from_secs),
             )
         )?;
         assert_eq!(
             dispute_game_factory
                 .initBonds(KAILUA_GAME_TYPE)
                 .stall_with_context(context.clone(), "DisputeGameFactory::initBonds")
-                .await,
+                .await?,
             U256::ZERO
         );
     }
@@ -304,7 +322,7 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
         let result = dispute_game_factory
             .games(KAILUA_GAME_TYPE, root_claim, extra_data.clone())
             .stall_with_context(context.clone(), "DisputeGameFactory::games")
-            .await
+            .await?
             .proxy_;
 
         if !result.is_zero() {
@@ -317,7 +335,7 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
     let status = kailua_treasury_instance
         .status()
         .stall_with_context(context.clone(), "KailuaTreasury::status")
-        .await;
+        .await?;
     if status == 0 {
         info!("Resolving KailuaTreasury instance");
         await_tel_res!(
@@ -328,6 +346,8 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
                 kailua_treasury_instance.resolve(),
                 &factory_owner_safe,
                 owner_address,
+                &args.safe_signatures_dir,
+                args.safe_signatures_timeout.map(Duration::from_secs),
             )
         )?;
     } else {
@@ -348,13 +368,15 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
             kailua_treasury_implementation.setParticipationBond(bond_value),
             &factory_owner_safe,
             owner_address,
+            &args.safe_signatures_dir,
+            args.safe_signatures_timeout.map(Duration::from_secs),
         )
     )?;
     assert_eq!(
         kailua_treasury_implementation
             .participationBond()
             .stall_with_context(context.clone(), "KailuaTreasury::participationBond")
-            .await,
+            .await?,
         bond_value
     );
 
@@ -390,6 +412,8 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
                 .setImplementation(KAILUA_GAME_TYPE, *kailua_game_contract.address()),
             &factory_owner_safe,
             owner_address,
+            &args.safe_signatures_dir,
+            args.safe_signatures_timeout.map(Duration::from_secs),
         )
     )?;
 
@@ -407,6 +431,8 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
                 kailua_treasury_implementation.assignVanguard(vanguard_address, vanguard_advantage),
                 &factory_owner_safe,
                 owner_address,
+                &args.safe_signatures_dir,
+                args.safe_signatures_timeout.map(Duration::from_secs),
             )
         )?;
     }
@@ -433,7 +459,7 @@ pub async fn fast_track(args: FastTrackArgs) -> anyhow::Result<()> {
         let portal_guardian_address = optimism_portal
             .guardian()
             .stall_with_context(context.clone(), "OptimismPortal2::guardian")
-            .await;
+            .await?;
         if portal_guardian_address != guardian_address {
             bail!("OptimismPortal2 Guardian is {portal_guardian_address}. Provided private key has account address {guardian_address}.");
         }
@@ -503,7 +529,7 @@ pub async fn deploy_verifier<P1: Provider<N>, P2: Provider<N>, N: Network>(
     let selector = groth16_verifier_contract
         .SELECTOR()
         .stall_with_context(context.clone(), "RiscZeroGroth16Verifier::SELECTOR")
-        .await;
+        .await?;
     info!("Adding RiscZeroGroth16Verifier contract to RiscZeroVerifierRouter.");
     let receipt = verifier_contract
         .addVerifier(selector, *groth16_verifier_contract.address())
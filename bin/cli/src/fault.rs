@@ -76,7 +76,7 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
             &args.propose_args.sync.provider.op_node_url,
             &args.propose_args.sync.provider.op_geth_url,
             None,
-            args.propose_args.bypass_chain_registry
+            args.propose_args.proving.bypass_chain_registry
         )
     )
     .context("fetch_rollup_config")?;
@@ -88,7 +88,7 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
     let dgf_address = system_config
         .disputeGameFactory()
         .stall_with_context(context.clone(), "SystemConfig::disputeGameFactory")
-        .await;
+        .await?;
 
     // init l1 stuff
     let tester_wallet = await_tel_res!(
@@ -119,41 +119,41 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
         dispute_game_factory
             .gameImpls(KAILUA_GAME_TYPE)
             .stall_with_context(context.clone(), "DisputeGameFactory::gameImpls")
-            .await,
+            .await?,
         &tester_provider,
     );
     let kailua_treasury_address = kailua_game_implementation
         .KAILUA_TREASURY()
         .stall_with_context(context.clone(), "KailuaGame::KAILUA_TREASURY")
-        .await;
+        .await?;
     let kailua_treasury_instance = KailuaTreasury::new(kailua_treasury_address, &tester_provider);
 
     // load constants
     let proposal_output_count = kailua_game_implementation
         .PROPOSAL_OUTPUT_COUNT()
         .stall_with_context(context.clone(), "KailuaGame::PROPOSAL_OUTPUT_COUNT")
-        .await;
+        .await?;
     let output_block_span = kailua_game_implementation
         .OUTPUT_BLOCK_SPAN()
         .stall_with_context(context.clone(), "KailuaGame::OUTPUT_BLOCK_SPAN")
-        .await;
+        .await?;
     let proposal_block_count = proposal_output_count * output_block_span;
 
     // get proposal parent
     let games_count = dispute_game_factory
         .gameCount()
         .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
-        .await;
+        .await?;
     let parent_game_address = dispute_game_factory
         .gameAtIndex(U256::from(args.fault_parent))
         .stall_with_context(context.clone(), "DisputeGameFactory::gameAtIndex")
-        .await
+        .await?
         .proxy_;
     let parent_game_contract = KailuaGame::new(parent_game_address, &tester_provider);
     let parent_block_number: u64 = parent_game_contract
         .l2BlockNumber()
         .stall_with_context(context.clone(), "KailuaTournament::l2BlockNumber")
-        .await
+        .await?
         .to();
     // Prepare faulty proposal
     let faulty_block_number = parent_block_number + args.fault_offset * output_block_span;
@@ -168,11 +168,11 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
             tracer,
             "proposed_output_root",
             retry_res_ctx_timeout!(
-                op_node_provider
+                OpNode => op_node_provider
                     .output_at_block(proposed_block_number)
                     .await
             )
-        )
+        )?
     };
 
     // Prepare intermediate outputs
@@ -190,8 +190,10 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
                 context,
                 tracer,
                 "output_hash",
-                retry_res_ctx_timeout!(op_node_provider.output_at_block(io_block_number).await)
-            )
+                retry_res_ctx_timeout!(
+                    OpNode => op_node_provider.output_at_block(io_block_number).await
+                )
+            )?
         } else {
             B256::ZERO
         };
@@ -217,7 +219,7 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
                 Bytes::from(extra_data.clone()),
             )
             .stall_with_context(context.clone(), "DisputeGameFactory::games")
-            .await
+            .await?
             .proxy_;
         if dupe_game_address.is_zero() {
             // proposal was not made before using this dupe counter
@@ -230,11 +232,11 @@ pub async fn fault(args: FaultArgs) -> anyhow::Result<()> {
     let bond_value = kailua_treasury_instance
         .participationBond()
         .stall_with_context(context.clone(), "KailuaTreasury::participationBond")
-        .await;
+        .await?;
     let paid_in = kailua_treasury_instance
         .paidBonds(tester_address)
         .stall_with_context(context.clone(), "KailuaTreasury::paidBonds")
-        .await;
+        .await?;
     let owed_collateral = bond_value.saturating_sub(paid_in);
 
     let mut transaction =
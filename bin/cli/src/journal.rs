@@ -0,0 +1,74 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::primitives::U256;
+use kailua_sync::telemetry::TelemetryArgs;
+use kailua_sync::transact::journal::{read_journal, JournalFilter, Outcome, JOURNAL_FILE};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Query the transaction journal of a proposer or validator
+#[derive(clap::Args, Debug, Clone)]
+pub struct JournalArgs {
+    /// Data directory of the proposer or validator
+    #[clap(long, env)]
+    pub journal_dir: PathBuf,
+    /// Only list transactions whose intent contains this text (e.g. `resolve`)
+    #[clap(long, env)]
+    pub intent: Option<String>,
+    /// Only list transactions sent to this proposal
+    #[clap(long, env)]
+    pub proposal_index: Option<u64>,
+    /// Only list transactions with this outcome
+    #[clap(long, env)]
+    pub outcome: Option<Outcome>,
+    /// Only list transactions concluded at or after this unix timestamp
+    #[clap(long, env)]
+    pub since: Option<u64>,
+    /// Only list transactions concluded at or before this unix timestamp
+    #[clap(long, env)]
+    pub until: Option<u64>,
+    #[clap(flatten)]
+    pub telemetry: TelemetryArgs,
+}
+
+pub async fn journal(args: JournalArgs) -> anyhow::Result<()> {
+    let filter = JournalFilter {
+        intent: args.intent,
+        proposal_index: args.proposal_index,
+        outcome: args.outcome,
+        since: args.since,
+        until: args.until,
+    };
+    let entries = read_journal(&args.journal_dir.join(JOURNAL_FILE))?
+        .into_iter()
+        .filter(|entry| filter.matches(entry))
+        .collect::<Vec<_>>();
+
+    // Print matching entries as JSON lines followed by a summary per intent
+    let mut summary = BTreeMap::<&str, (u64, u128, U256)>::new();
+    for entry in &entries {
+        println!("{}", serde_json::to_string(entry)?);
+        let (count, fees, value) = summary.entry(&entry.intent).or_default();
+        *count += 1;
+        *fees += entry.fee();
+        if entry.outcome == Outcome::Confirmed {
+            *value += entry.value;
+        }
+    }
+    for (intent, (count, fees, value)) in &summary {
+        eprintln!("{intent}: {count} transactions, {fees} wei in fees, {value} wei in value");
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::bail;
+use kailua_sync::telemetry::TelemetryArgs;
+use kailua_sync::transact::keystore::{
+    create_keystore, decrypt_keystore, read_new_password, KeystoreInfo,
+};
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct KeystoreArgs {
+    /// Path of the encrypted keystore file to operate on
+    #[clap(long, env)]
+    pub keystore: PathBuf,
+    /// Whether to create a new keystore file instead of inspecting an existing one
+    #[clap(long, env, default_value_t = false)]
+    pub create: bool,
+    /// Private key to encrypt into the new keystore (a random key is generated if unspecified)
+    #[clap(long, env, requires = "create")]
+    pub private_key: Option<String>,
+    /// File containing the keystore password (prompts for the password if unspecified)
+    #[clap(long, env)]
+    pub password_file: Option<PathBuf>,
+    #[clap(flatten)]
+    pub telemetry: TelemetryArgs,
+}
+
+pub async fn keystore(args: KeystoreArgs) -> anyhow::Result<()> {
+    if args.create {
+        let password = read_new_password(&args.keystore, &args.password_file)?;
+        let signer = create_keystore(&args.keystore, args.private_key.as_deref(), &password)?;
+        info!(
+            "Created keystore {} for {}.",
+            args.keystore.display(),
+            signer.address()
+        );
+        return Ok(());
+    }
+
+    let info = KeystoreInfo::read(&args.keystore)?;
+    info!(
+        "Keystore {} (version {}) uses {} with {} key derivation.",
+        args.keystore.display(),
+        info.version,
+        info.crypto.cipher,
+        info.crypto.kdf
+    );
+    match info.address() {
+        Some(address) => info!("Keystore address: {address}"),
+        None => info!("Keystore address is not stored in plain text."),
+    }
+    // Decrypting is only attempted when the password is supplied non-interactively
+    if args.password_file.is_some() {
+        let signer = decrypt_keystore(&args.keystore, &args.password_file)?;
+        if info
+            .address()
+            .is_some_and(|address| address != signer.address())
+        {
+            bail!(
+                "Keystore decrypts to {} instead of its stated address.",
+                signer.address()
+            );
+        }
+        info!("Keystore decrypts to {}.", signer.address());
+    }
+    Ok(())
+}
@@ -13,16 +13,21 @@
 // limitations under the License.
 
 use kailua_sync::telemetry::TelemetryArgs;
+use kailua_sync::transact::TransactArgs;
 use kailua_validator::args;
 use std::path::PathBuf;
 
 pub mod bench;
+pub mod blobs;
 pub mod bonsai;
 pub mod config;
 pub mod demo;
 pub mod export;
 pub mod fast_track;
 pub mod fault;
+pub mod journal;
+pub mod keystore;
+pub mod safe_sign;
 
 /// The Kailua all-in-one CLI utility suite for securing rollups
 #[derive(clap::Parser, Debug, Clone)]
@@ -99,6 +104,30 @@ pub enum KailuaCli {
         #[clap(flatten)]
         cli: CliArgs,
     },
+    Blobs {
+        #[clap(flatten)]
+        args: blobs::BlobsArgs,
+        #[clap(flatten)]
+        cli: CliArgs,
+    },
+    Keystore {
+        #[clap(flatten)]
+        args: keystore::KeystoreArgs,
+        #[clap(flatten)]
+        cli: CliArgs,
+    },
+    SafeSign {
+        #[clap(flatten)]
+        args: safe_sign::SafeSignArgs,
+        #[clap(flatten)]
+        cli: CliArgs,
+    },
+    Journal {
+        #[clap(flatten)]
+        args: journal::JournalArgs,
+        #[clap(flatten)]
+        cli: CliArgs,
+    },
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -121,6 +150,10 @@ impl KailuaCli {
             KailuaCli::Rpc { cli, .. } => cli.v,
             KailuaCli::Bonsai { cli, .. } => cli.v,
             KailuaCli::Export { cli, .. } => cli.v,
+            KailuaCli::Blobs { cli, .. } => cli.v,
+            KailuaCli::Keystore { cli, .. } => cli.v,
+            KailuaCli::SafeSign { cli, .. } => cli.v,
+            KailuaCli::Journal { cli, .. } => cli.v,
         }
     }
 
@@ -136,6 +169,16 @@ impl KailuaCli {
         }
     }
 
+    pub fn txn_args(&self) -> Option<&TransactArgs> {
+        match self {
+            KailuaCli::FastTrack { args, .. } => Some(&args.txn_args),
+            KailuaCli::Propose { args, .. } => Some(&args.txn_args),
+            KailuaCli::Validate { args, .. } => Some(&args.txn_args),
+            KailuaCli::TestFault { args, .. } => Some(&args.propose_args.txn_args),
+            _ => None,
+        }
+    }
+
     pub fn telemetry_args(&self) -> &TelemetryArgs {
         match self {
             KailuaCli::Config { args, .. } => &args.telemetry,
@@ -149,6 +192,10 @@ impl KailuaCli {
             KailuaCli::Rpc { args, .. } => &args.sync.telemetry,
             KailuaCli::Bonsai { args, .. } => &args.telemetry,
             KailuaCli::Export { telemetry, .. } => telemetry,
+            KailuaCli::Blobs { args, .. } => &args.telemetry,
+            KailuaCli::Keystore { args, .. } => &args.telemetry,
+            KailuaCli::SafeSign { args, .. } => &args.telemetry,
+            KailuaCli::Journal { args, .. } => &args.telemetry,
         }
     }
 }
@@ -27,6 +27,9 @@ async fn main() -> anyhow::Result<()> {
     let cli = KailuaCli::parse();
     kona_cli::init_tracing_subscriber(cli.verbosity(), None::<EnvFilter>)?;
     init_tracer_provider(cli.telemetry_args())?;
+    if let Some(txn_args) = cli.txn_args() {
+        txn_args.install();
+    }
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("cli"));
 
@@ -40,8 +43,11 @@ async fn main() -> anyhow::Result<()> {
         KailuaCli::FastTrack { args, .. } => {
             await_tel!(context, kailua_cli::fast_track::fast_track(args))
         }
-        KailuaCli::Propose { args, .. } => {
-            await_tel!(context, kailua_proposer::propose::propose(args, data_dir))
+        KailuaCli::Propose { args, cli } => {
+            await_tel!(
+                context,
+                kailua_proposer::propose::propose(args, cli.v, data_dir)
+            )
         }
         KailuaCli::Validate { args, cli } => {
             await_tel!(
@@ -77,6 +83,18 @@ async fn main() -> anyhow::Result<()> {
         KailuaCli::Export { .. } => {
             await_tel!(context, kailua_cli::export::export(data_dir))
         }
+        KailuaCli::Blobs { args, .. } => {
+            await_tel!(context, kailua_cli::blobs::blobs(args))
+        }
+        KailuaCli::Keystore { args, .. } => {
+            await_tel!(context, kailua_cli::keystore::keystore(args))
+        }
+        KailuaCli::SafeSign { args, .. } => {
+            await_tel!(context, kailua_cli::safe_sign::safe_sign(args))
+        }
+        KailuaCli::Journal { args, .. } => {
+            await_tel!(context, kailua_cli::journal::journal(args))
+        }
     };
 
     let span = context.span();
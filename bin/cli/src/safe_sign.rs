@@ -0,0 +1,74 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Context;
+use kailua_sync::telemetry::TelemetryArgs;
+use kailua_sync::transact::safe::SafeTransaction;
+use kailua_sync::transact::signer::OwnerSignerArgs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Sign a Safe transaction payload as one of the Safe owners
+#[derive(clap::Args, Debug, Clone)]
+pub struct SafeSignArgs {
+    /// Path of the Safe transaction payload to sign
+    #[clap(long, env)]
+    pub safe_txn: PathBuf,
+    /// Directory to write the signature to (defaults to the directory of the payload)
+    #[clap(long, env)]
+    pub safe_signatures_dir: Option<PathBuf>,
+
+    /// Secret key of the signing Safe owner
+    #[clap(flatten)]
+    pub owner_signer: OwnerSignerArgs,
+
+    #[clap(flatten)]
+    pub telemetry: TelemetryArgs,
+}
+
+pub async fn safe_sign(args: SafeSignArgs) -> anyhow::Result<()> {
+    let safe_txn = SafeTransaction::read(&args.safe_txn)?;
+    info!(
+        "Signing Safe({}) transaction {} with nonce {} calling {} with {} bytes of data.",
+        safe_txn.safe,
+        safe_txn.hash,
+        safe_txn.nonce,
+        safe_txn.to,
+        safe_txn.data.len()
+    );
+    let signer = args
+        .owner_signer
+        .signer(Some(safe_txn.chain_id))
+        .await
+        .context("OwnerSignerArgs::signer")?;
+    let signature = safe_txn.sign(&signer).await?;
+
+    let dir = match args.safe_signatures_dir {
+        Some(dir) => dir,
+        None => args
+            .safe_txn
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf(),
+    };
+    std::fs::create_dir_all(&dir).context("create_dir_all")?;
+    let path = safe_txn.signature_path(&dir, signature.owner);
+    signature.write(&path)?;
+    info!(
+        "Signature by {} written to {}.",
+        signature.owner,
+        path.display()
+    );
+    Ok(())
+}
@@ -25,7 +25,7 @@ use kailua_prover::args::{ProveArgs, ProvingArgs};
 use kailua_prover::prove::prove;
 use kailua_sync::agent::SyncAgent;
 use kailua_sync::args::SyncArgs;
-use kailua_sync::provider::ProviderArgs;
+use kailua_sync::provider::{OutputSource, ProviderArgs};
 use kailua_sync::transact::signer::{
     DeployerSignerArgs, GuardianSignerArgs, OwnerSignerArgs, ProposerSignerArgs,
     ValidatorSignerArgs,
@@ -70,6 +70,15 @@ async fn deploy_kailua_contracts(challenge_timeout: u64) -> anyhow::Result<()> {
             txn_timeout: 12,
             exec_gas_premium: 0,
             blob_gas_premium: 0,
+            txn_max_replacements: 3,
+            cancel_stale_txns: false,
+            max_fee_per_gas: None,
+            max_fee_per_blob_gas: None,
+            hourly_fee_budget: None,
+            daily_fee_budget: None,
+            defer_above_gas_price: None,
+            dry_run: false,
+            dry_run_report: None,
         },
         starting_block_number: 0,
         proposal_output_count: 5,
@@ -86,6 +95,8 @@ async fn deploy_kailua_contracts(challenge_timeout: u64) -> anyhow::Result<()> {
         guardian_signer: Some(GuardianSignerArgs::from(
             "0x2a871d0798f97d79848a013d4936a73bf4cc922c825d33c1cf7073dff6d409c6".to_string(),
         )),
+        safe_signatures_dir: None,
+        safe_signatures_timeout: None,
         vanguard_address: Some("0x9965507D1a55bcC2695C58ba16FB37d819B0A4dc".to_string()),
         vanguard_advantage: Some(60),
         respect_kailua_proposals: true,
@@ -155,6 +166,16 @@ async fn proposer_validator() {
             op_node_url: "http://127.0.0.1:7545".to_string(),
             op_rpc_delay: 0,
             beacon_rpc_url: "http://127.0.0.1:5052".to_string(),
+            log_sync_range: None,
+            op_node_fallback_urls: vec![],
+            op_geth_fallback_urls: vec![],
+            eth_rpc_fallback_urls: vec![],
+            beacon_rpc_fallback_urls: vec![],
+            rpc_quorum: None,
+            blob_archive_urls: vec![],
+            blob_store_dir: None,
+            output_source: OutputSource::OpNode,
+            retry: Default::default(),
         },
         kailua_game_implementation: None,
         kailua_anchor_address: None,
@@ -168,6 +189,29 @@ async fn proposer_validator() {
         txn_timeout: 30,
         exec_gas_premium: 25,
         blob_gas_premium: 25,
+        txn_max_replacements: 3,
+        cancel_stale_txns: false,
+        max_fee_per_gas: None,
+        max_fee_per_blob_gas: None,
+        hourly_fee_budget: None,
+        daily_fee_budget: None,
+        defer_above_gas_price: None,
+        dry_run: false,
+        dry_run_report: None,
+    };
+
+    // Instantiate proposer proving arguments
+    let proving_args = ProvingArgs {
+        payout_recipient_address: None,
+        segment_limit: 21,
+        max_witness_size: 2_684_354_560,
+        num_concurrent_preflights: 1,
+        num_concurrent_proofs: 1,
+        bypass_chain_registry: false,
+        skip_derivation_proof: false,
+        skip_await_proof: false,
+        hokulea: Default::default(),
+        hana: Default::default(),
     };
 
     // Instantiate proposer wallet
@@ -179,10 +223,20 @@ async fn proposer_validator() {
     propose(
         ProposeArgs {
             sync: sync.clone(),
-            bypass_chain_registry: false,
+            exit: false,
+            catch_up_depth: 1,
+            resolve_batch_size: 16,
+            resolve_gas_budget: None,
             proposer_signer: proposer_signer.clone(),
             txn_args: txn_args.clone(),
+            lease: Default::default(),
+            finality: Default::default(),
+            blob_fee: Default::default(),
+            self_prove: Default::default(),
+            proving: proving_args.clone(),
+            boundless: Default::default(),
         },
+        3,
         proposer_data_dir.clone(),
     )
     .await
@@ -208,11 +262,20 @@ async fn proposer_validator() {
     fault(FaultArgs {
         propose_args: ProposeArgs {
             sync: sync.clone(),
-            bypass_chain_registry: false,
+            exit: false,
+            catch_up_depth: 1,
+            resolve_batch_size: 16,
+            resolve_gas_budget: None,
             proposer_signer: ProposerSignerArgs::from(
                 "0x4bbbf85ce3377467afe5d46f804f221813b2bb87f24d81f60f1fcdbf7cbf4356".to_string(),
             ),
             txn_args: txn_args.clone(),
+            lease: Default::default(),
+            finality: Default::default(),
+            blob_fee: Default::default(),
+            self_prove: Default::default(),
+            proving: proving_args.clone(),
+            boundless: Default::default(),
         },
         fault_offset: 1,
         fault_parent,
@@ -224,11 +287,20 @@ async fn proposer_validator() {
     fault(FaultArgs {
         propose_args: ProposeArgs {
             sync: sync.clone(),
-            bypass_chain_registry: false,
+            exit: false,
+            catch_up_depth: 1,
+            resolve_batch_size: 16,
+            resolve_gas_budget: None,
             proposer_signer: ProposerSignerArgs::from(
                 "0xdbda1821b80551c9d65939329250298aa3472ba22feea921c0cf5d620ea67b97".to_string(),
             ),
             txn_args: txn_args.clone(),
+            lease: Default::default(),
+            finality: Default::default(),
+            blob_fee: Default::default(),
+            self_prove: Default::default(),
+            proving: proving_args.clone(),
+            boundless: Default::default(),
         },
         fault_offset: 250,
         fault_parent,
@@ -257,6 +329,7 @@ async fn proposer_validator() {
             enable_experimental_witness_endpoint: true,
             max_fault_proving_delay: 0,
             max_validity_proving_delay: 0,
+            bond_collection_interval: 600,
             l1_head_jump_back: 0,
             validator_signer: ValidatorSignerArgs::from(
                 "0x92db14e403b83dfe3df233f83dfa3a0d7096f21ca9b0d6d6b8d88b2b4ec1564e".to_string(),
@@ -282,10 +355,20 @@ async fn proposer_validator() {
     let proposer_handle = tokio::task::spawn(propose(
         ProposeArgs {
             sync: sync.clone(),
-            bypass_chain_registry: false,
+            exit: false,
+            catch_up_depth: 1,
+            resolve_batch_size: 16,
+            resolve_gas_budget: None,
             proposer_signer: proposer_signer.clone(),
             txn_args: txn_args.clone(),
+            lease: Default::default(),
+            finality: Default::default(),
+            blob_fee: Default::default(),
+            self_prove: Default::default(),
+            proving: proving_args.clone(),
+            boundless: Default::default(),
         },
+        3,
         proposer_data_dir.clone(),
     ));
     println!("Waiting for proposer and validator to terminate.");
@@ -311,6 +394,7 @@ async fn proposer_validator() {
             enable_experimental_witness_endpoint: true,
             max_fault_proving_delay: 0,
             max_validity_proving_delay: 0,
+            bond_collection_interval: 600,
             l1_head_jump_back: 0,
             validator_signer: ValidatorSignerArgs::from(
                 "0x92db14e403b83dfe3df233f83dfa3a0d7096f21ca9b0d6d6b8d88b2b4ec1564e".to_string(),
@@ -336,10 +420,20 @@ async fn proposer_validator() {
     let proposer_handle = tokio::task::spawn(propose(
         ProposeArgs {
             sync: sync.clone(),
-            bypass_chain_registry: false,
+            exit: false,
+            catch_up_depth: 1,
+            resolve_batch_size: 16,
+            resolve_gas_budget: None,
             proposer_signer: proposer_signer.clone(),
             txn_args: txn_args.clone(),
+            lease: Default::default(),
+            finality: Default::default(),
+            blob_fee: Default::default(),
+            self_prove: Default::default(),
+            proving: proving_args.clone(),
+            boundless: Default::default(),
         },
+        3,
         proposer_data_dir.clone(),
     ));
     println!("Waiting for proposer and validator to terminate.");
@@ -376,6 +470,16 @@ async fn prover() {
             op_node_url: "http://127.0.0.1:7545".to_string(),
             op_rpc_delay: 0,
             beacon_rpc_url: "http://127.0.0.1:5052".to_string(),
+            log_sync_range: None,
+            op_node_fallback_urls: vec![],
+            op_geth_fallback_urls: vec![],
+            eth_rpc_fallback_urls: vec![],
+            beacon_rpc_fallback_urls: vec![],
+            rpc_quorum: None,
+            blob_archive_urls: vec![],
+            blob_store_dir: None,
+            output_source: OutputSource::OpNode,
+            retry: Default::default(),
         },
         kailua_game_implementation: None,
         kailua_anchor_address: None,
@@ -12,6 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::finality::FinalityArgs;
+use crate::lease::LeaseArgs;
+use crate::schedule::BlobFeeArgs;
+use crate::self_prove::SelfProveArgs;
+use kailua_prover::args::ProvingArgs;
+use kailua_prover::risczero::boundless::BoundlessArgs;
 use kailua_sync::args::SyncArgs;
 use kailua_sync::transact::signer::ProposerSignerArgs;
 use kailua_sync::transact::TransactArgs;
@@ -22,9 +28,18 @@ pub struct ProposeArgs {
     #[clap(flatten)]
     pub sync: SyncArgs,
 
-    /// Whether to bypass loading rollup chain configurations from the kona registry
+    /// Whether to stop proposing and claim back the proposer bond once it is unlocked
     #[clap(long, env, default_value_t = false)]
-    pub bypass_chain_registry: bool,
+    pub exit: bool,
+    /// The maximum number of successive proposals to submit at once while catching up. (Default 1)
+    #[clap(long, env, default_value_t = 1)]
+    pub catch_up_depth: u64,
+    /// The maximum number of proposals to resolve per iteration. (Default 16)
+    #[clap(long, env, default_value_t = 16)]
+    pub resolve_batch_size: u64,
+    /// The maximum amount of gas to spend on pruning and resolving proposals per iteration
+    #[clap(long, env)]
+    pub resolve_gas_budget: Option<u64>,
 
     /// L1 wallet to use for proposing outputs
     #[clap(flatten)]
@@ -32,4 +47,21 @@ pub struct ProposeArgs {
     /// Transaction publication configuration
     #[clap(flatten)]
     pub txn_args: TransactArgs,
+    /// Leader election among redundant proposer instances
+    #[clap(flatten)]
+    pub lease: LeaseArgs,
+    /// Finality requirements for the L1 origin of proposed outputs
+    #[clap(flatten)]
+    pub finality: FinalityArgs,
+    /// Scheduling of proposals around blob fee spikes
+    #[clap(flatten)]
+    pub blob_fee: BlobFeeArgs,
+    /// Validity proving of own proposals
+    #[clap(flatten)]
+    pub self_prove: SelfProveArgs,
+
+    #[clap(flatten)]
+    pub proving: ProvingArgs,
+    #[clap(flatten)]
+    pub boundless: BoundlessArgs,
 }
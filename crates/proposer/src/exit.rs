@@ -0,0 +1,335 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::network::{Network, ReceiptResponse};
+use alloy::primitives::{Address, U256};
+use alloy::providers::Provider;
+use anyhow::{bail, Context};
+use kailua_contracts::*;
+use kailua_sync::agent::SyncAgent;
+use kailua_sync::await_tel;
+use kailua_sync::proposal::Proposal;
+use kailua_sync::stall::Stall;
+use kailua_sync::transact::policy::Urgency;
+use kailua_sync::transact::{Transact, TransactArgs};
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Whether the bond of a proposer can be claimed back from the treasury
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BondStatus {
+    /// No bond is held by the treasury
+    Empty,
+    /// The proposer was eliminated in the tournament of the given proposal index
+    Forfeited { elimination_round: u64 },
+    /// The tournament of the proposer's last proposal has not been resolved yet
+    Locked {
+        last_proposal: Address,
+        last_proposal_index: u64,
+    },
+    /// The bond can be claimed back
+    Claimable,
+}
+
+/// The bond paid in by a proposer and whether it can be withdrawn
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BondReport {
+    pub proposer: Address,
+    pub treasury: Address,
+    pub paid: U256,
+    pub status: BondStatus,
+}
+
+impl Display for BondReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.status {
+            BondStatus::Empty => write!(
+                f,
+                "Proposer {} has no bond locked in treasury {}.",
+                self.proposer, self.treasury
+            ),
+            BondStatus::Forfeited { elimination_round } => write!(
+                f,
+                "Bond of {} wei locked by proposer {} in treasury {} was forfeited after its elimination at proposal {elimination_round}.",
+                self.paid, self.proposer, self.treasury
+            ),
+            BondStatus::Locked {
+                last_proposal,
+                last_proposal_index,
+            } => write!(
+                f,
+                "Bond of {} wei remains locked by proposer {} in treasury {} until the tournament of its last proposal {last_proposal_index} ({last_proposal}) is resolved.",
+                self.paid, self.proposer, self.treasury
+            ),
+            BondStatus::Claimable => write!(
+                f,
+                "Bond of {} wei locked by proposer {} in treasury {} can be claimed.",
+                self.paid, self.proposer, self.treasury
+            ),
+        }
+    }
+}
+
+/// Reports the bond of the given proposer in the current treasury and in every other known
+/// treasury where it still has a bond.
+pub async fn fetch_bond_reports(
+    agent: &SyncAgent,
+    proposer: Address,
+) -> anyhow::Result<Vec<BondReport>> {
+    let tracer = tracer("kailua");
+    let context = opentelemetry::Context::current_with_span(tracer.start("fetch_bond_reports"));
+
+    let mut reports = vec![];
+    for treasury in agent.known_treasuries() {
+        let report = await_tel!(context, fetch_bond_report(agent, treasury, proposer))
+            .context("fetch_bond_report")?;
+        if treasury == agent.deployment.treasury || report.status != BondStatus::Empty {
+            reports.push(report);
+        }
+    }
+    Ok(reports)
+}
+
+/// Reproduces the checks of `KailuaTreasury::claimProposerBond` for the given proposer.
+pub async fn fetch_bond_report(
+    agent: &SyncAgent,
+    treasury_address: Address,
+    proposer: Address,
+) -> anyhow::Result<BondReport> {
+    let tracer = tracer("kailua");
+    let context = opentelemetry::Context::current_with_span(tracer.start("fetch_bond_report"));
+
+    let treasury = KailuaTreasury::new(treasury_address, &agent.provider.l1_provider);
+    let mut report = BondReport {
+        proposer,
+        treasury: treasury_address,
+        paid: treasury
+            .paidBonds(proposer)
+            .stall_with_context(context.clone(), "KailuaTreasury::paidBonds")
+            .await?,
+        status: BondStatus::Claimable,
+    };
+    // Eliminated proposers forfeit their bond
+    let elimination_round: u64 = treasury
+        .eliminationRound(proposer)
+        .stall_with_context(context.clone(), "KailuaTreasury::eliminationRound")
+        .await?
+        .to();
+    if elimination_round > 0 {
+        report.status = BondStatus::Forfeited { elimination_round };
+        return Ok(report);
+    }
+    if report.paid.is_zero() {
+        report.status = BondStatus::Empty;
+        return Ok(report);
+    }
+    // The bond stays locked until the tournament of the last proposal has a resolved winner
+    let last_proposal = treasury
+        .lastProposal(proposer)
+        .stall_with_context(context.clone(), "KailuaTreasury::lastProposal")
+        .await?;
+    if last_proposal.is_zero() {
+        return Ok(report);
+    }
+    let last_proposal_instance = KailuaTournament::new(last_proposal, &agent.provider.l1_provider);
+    let parent_tournament = last_proposal_instance
+        .parentGame()
+        .stall_with_context(context.clone(), "KailuaTournament::parentGame")
+        .await?;
+    let parent_tournament_instance =
+        KailuaTournament::new(parent_tournament, &agent.provider.l1_provider);
+    let contender_index = parent_tournament_instance
+        .contenderIndex()
+        .stall_with_context(context.clone(), "KailuaTournament::contenderIndex")
+        .await?;
+    let contender = parent_tournament_instance
+        .children(U256::from(contender_index))
+        .stall_with_context(context.clone(), "KailuaTournament::children")
+        .await?;
+    let contender_status = KailuaTournament::new(contender, &agent.provider.l1_provider)
+        .status()
+        .stall_with_context(context.clone(), "KailuaTournament::status")
+        .await?;
+    if Proposal::parse_finality(contender_status)
+        .ok()
+        .flatten()
+        .is_none_or(|defender_wins| !defender_wins)
+    {
+        report.status = BondStatus::Locked {
+            last_proposal,
+            last_proposal_index: last_proposal_instance
+                .gameIndex()
+                .stall_with_context(context.clone(), "KailuaTournament::gameIndex")
+                .await?
+                .to(),
+        };
+    }
+    Ok(report)
+}
+
+/// Withdraws the bond of the proposer signing transactions through the given provider from the
+/// given treasury.
+pub async fn claim_proposer_bond<P: Provider<N>, N: Network>(
+    treasury: Address,
+    proposer_provider: P,
+    txn_args: &TransactArgs,
+) -> anyhow::Result<N::ReceiptResponse> {
+    let tracer = tracer("kailua");
+    let context = opentelemetry::Context::current_with_span(tracer.start("claim_proposer_bond"));
+
+    let receipt = KailuaTreasury::new(treasury, &proposer_provider)
+        .claimProposerBond()
+        .timed_transact_with_urgency(
+            context.clone(),
+            "KailuaTreasury::claimProposerBond",
+            Some(Duration::from_secs(txn_args.txn_timeout)),
+            Urgency::Normal,
+        )
+        .await
+        .context("KailuaTreasury::claimProposerBond")?;
+    if !receipt.status() {
+        bail!("KailuaTreasury::claimProposerBond reverted.");
+    }
+    Ok(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, B256};
+    use kailua_sync::provider::memory::fixtures::*;
+    use kailua_sync::provider::memory::InMemoryChain;
+    use std::collections::BTreeSet;
+
+    const OLD_TREASURY: Address = address!("0x0000000000000000000000000000000000000005");
+
+    fn script_bond(
+        chain: &InMemoryChain,
+        treasury: Address,
+        proposer: Address,
+        paid: u64,
+        elimination_round: u64,
+        last_proposal: Address,
+    ) {
+        chain.mock_call(
+            treasury,
+            KailuaTreasury::paidBondsCall::new((proposer,)),
+            U256::from(paid),
+        );
+        chain.mock_call(
+            treasury,
+            KailuaTreasury::eliminationRoundCall::new((proposer,)),
+            U256::from(elimination_round),
+        );
+        chain.mock_call(
+            treasury,
+            KailuaTreasury::lastProposalCall::new((proposer,)),
+            last_proposal,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_bond_report() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        chain
+            .script_proposal(
+                &deployment,
+                &game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER),
+            )
+            .unwrap();
+        chain.mock_call(ANCHOR, KailuaTournament::contenderIndexCall {}, 0u64);
+        chain.mock_call(
+            ANCHOR,
+            KailuaTournament::childrenCall::new((U256::ZERO,)),
+            HONEST_GAME,
+        );
+        let data_dir = tempfile::tempdir().unwrap();
+        let agent = sync_agent(&chain, data_dir.path(), None).await;
+        let status = |proposer| {
+            let agent = &agent;
+            async move {
+                fetch_bond_report(agent, TREASURY, proposer)
+                    .await
+                    .unwrap()
+                    .status
+            }
+        };
+
+        // proposers without a bond have nothing to claim
+        script_bond(&chain, TREASURY, HONEST_PROPOSER, 0, 0, Address::ZERO);
+        assert_eq!(status(HONEST_PROPOSER).await, BondStatus::Empty);
+        // bonds of proposers that never proposed can be claimed right away
+        script_bond(&chain, TREASURY, HONEST_PROPOSER, 10, 0, Address::ZERO);
+        assert_eq!(status(HONEST_PROPOSER).await, BondStatus::Claimable);
+        // bonds stay locked until the tournament of the last proposal is resolved
+        script_bond(&chain, TREASURY, HONEST_PROPOSER, 10, 0, HONEST_GAME);
+        assert_eq!(
+            status(HONEST_PROPOSER).await,
+            BondStatus::Locked {
+                last_proposal: HONEST_GAME,
+                last_proposal_index: 1
+            }
+        );
+        chain.resolve_proposal(&deployment, HONEST_GAME, true);
+        assert_eq!(status(HONEST_PROPOSER).await, BondStatus::Claimable);
+        // eliminated proposers forfeit their bond
+        script_bond(&chain, TREASURY, FAULTY_PROPOSER, 10, 2, FAULTY_GAME);
+        assert_eq!(
+            status(FAULTY_PROPOSER).await,
+            BondStatus::Forfeited {
+                elimination_round: 2
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_bond_reports() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let data_dir = tempfile::tempdir().unwrap();
+        let agent = sync_agent(&chain, data_dir.path(), None).await;
+        script_bond(&chain, TREASURY, HONEST_PROPOSER, 0, 0, Address::ZERO);
+        script_bond(&chain, OLD_TREASURY, HONEST_PROPOSER, 10, 0, Address::ZERO);
+        let reports = fetch_bond_reports(&agent, HONEST_PROPOSER).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, BondStatus::Empty);
+
+        // bonds left in the treasuries of earlier deployments are reported as well
+        std::fs::create_dir_all(
+            data_dir
+                .path()
+                .join(B256::ZERO.to_string())
+                .join(OLD_TREASURY.to_string()),
+        )
+        .unwrap();
+        assert_eq!(
+            agent.known_treasuries(),
+            BTreeSet::from([TREASURY, OLD_TREASURY])
+        );
+        let reports = fetch_bond_reports(&agent, HONEST_PROPOSER).await.unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].treasury, OLD_TREASURY);
+        assert_eq!(reports[1].paid, U256::from(10));
+        assert_eq!(reports[1].status, BondStatus::Claimable);
+
+        // empty treasuries of earlier deployments are omitted
+        script_bond(&chain, OLD_TREASURY, HONEST_PROPOSER, 0, 0, Address::ZERO);
+        let reports = fetch_bond_reports(&agent, HONEST_PROPOSER).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].treasury, TREASURY);
+    }
+}
@@ -15,17 +15,19 @@
 use alloy::consensus::BlockHeader;
 use alloy::eips::BlockNumberOrTag;
 use alloy::network::BlockResponse;
-use alloy::primitives::{Address, U256};
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::sol_types::SolValue;
 use kailua_contracts::*;
 use kailua_sync::agent::SyncAgent;
-use kailua_sync::await_tel;
 use kailua_sync::proposal::Proposal;
 use kailua_sync::stall::Stall;
 use kailua_sync::transact::rpc::get_block;
+use kailua_sync::{await_tel, KAILUA_GAME_TYPE};
 use opentelemetry::global::tracer;
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use tracing::{info, warn};
 
-pub async fn fetch_vanguard(agent: &SyncAgent) -> Address {
+pub async fn fetch_vanguard(agent: &SyncAgent) -> anyhow::Result<Address> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("fetch_vanguard"));
     KailuaTreasury::new(agent.deployment.treasury, &agent.provider.l1_provider)
@@ -34,7 +36,7 @@ pub async fn fetch_vanguard(agent: &SyncAgent) -> Address {
         .await
 }
 
-pub async fn fetch_vanguard_advantage(agent: &SyncAgent) -> u64 {
+pub async fn fetch_vanguard_advantage(agent: &SyncAgent) -> anyhow::Result<u64> {
     let tracer = tracer("kailua");
     let context =
         opentelemetry::Context::current_with_span(tracer.start("fetch_vanguard_advantage"));
@@ -44,7 +46,7 @@ pub async fn fetch_vanguard_advantage(agent: &SyncAgent) -> u64 {
         .await
 }
 
-pub async fn fetch_participation_bond(agent: &SyncAgent) -> U256 {
+pub async fn fetch_participation_bond(agent: &SyncAgent) -> anyhow::Result<U256> {
     let tracer = tracer("kailua");
     let context =
         opentelemetry::Context::current_with_span(tracer.start("fetch_participation_bond"));
@@ -54,7 +56,7 @@ pub async fn fetch_participation_bond(agent: &SyncAgent) -> U256 {
         .await
 }
 
-pub async fn fetch_paid_bond(agent: &SyncAgent, address: Address) -> U256 {
+pub async fn fetch_paid_bond(agent: &SyncAgent, address: Address) -> anyhow::Result<U256> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("fetch_paid_bond"));
     KailuaTreasury::new(agent.deployment.treasury, &agent.provider.l1_provider)
@@ -63,7 +65,10 @@ pub async fn fetch_paid_bond(agent: &SyncAgent, address: Address) -> U256 {
         .await
 }
 
-pub async fn fetch_current_challenger_duration(agent: &SyncAgent, proposal: &Proposal) -> u64 {
+pub async fn fetch_current_challenger_duration(
+    agent: &SyncAgent,
+    proposal: &Proposal,
+) -> anyhow::Result<u64> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(
         tracer.start("Proposal::fetch_current_challenger_duration"),
@@ -72,7 +77,7 @@ pub async fn fetch_current_challenger_duration(agent: &SyncAgent, proposal: &Pro
     let chain_time = await_tel!(
         context,
         get_block(&agent.provider.l1_provider, BlockNumberOrTag::Latest)
-    )
+    )?
     .header()
     .timestamp();
 
@@ -82,3 +87,167 @@ pub async fn fetch_current_challenger_duration(agent: &SyncAgent, proposal: &Pro
         .stall_with_context(context.clone(), "KailuaTournament::getChallengerDuration")
         .await
 }
+
+/// Returns the first duplication counter under which the output at the given block has not been
+/// proposed on top of the given parent yet, along with the resulting proposal extra data.
+///
+/// Returns `None` if the output was already proposed honestly, or if a duplicate proposal has not
+/// been synchronized yet.
+pub async fn fetch_unique_extra_data(
+    agent: &SyncAgent,
+    parent_index: u64,
+    block_number: u64,
+    output_root: B256,
+) -> anyhow::Result<Option<(u64, Vec<u8>)>> {
+    let tracer = tracer("kailua");
+    let context =
+        opentelemetry::Context::current_with_span(tracer.start("fetch_unique_extra_data"));
+
+    let dispute_game_factory =
+        IDisputeGameFactory::new(agent.deployment.factory, &agent.provider.l1_provider);
+    let mut dupe_counter = 0u64;
+    loop {
+        // compute extra data with block number, parent factory index, and blob hash
+        let extra_data = [
+            block_number.abi_encode_packed(),
+            parent_index.abi_encode_packed(),
+            dupe_counter.abi_encode_packed(),
+        ]
+        .concat();
+        // check if proposal exists
+        let dupe_game_address = dispute_game_factory
+            .games(
+                KAILUA_GAME_TYPE,
+                output_root,
+                Bytes::from(extra_data.clone()),
+            )
+            .stall_with_context(context.clone(), "DisputeGameFactory::games")
+            .await?
+            .proxy_;
+        if dupe_game_address.is_zero() {
+            // proposal was not made before using this dupe counter
+            info!("Dupe counter {dupe_counter} available.");
+            return Ok(Some((dupe_counter, extra_data)));
+        }
+        // fetch proposal from local data
+        let dupe_game_index: u64 =
+            KailuaTournament::new(dupe_game_address, &agent.provider.l1_provider)
+                .gameIndex()
+                .stall_with_context(context.clone(), "KailuaTournament::gameIndex")
+                .await?
+                .to();
+        if dupe_game_index >= agent.cursor.next_factory_index {
+            // we need to fetch this proposal's data
+            warn!("Duplicate proposal data not yet available.");
+            return Ok(None);
+        }
+        if let Some(dupe_proposal) = agent.proposals.get(&dupe_game_index) {
+            // check if proposal was made incorrectly or by an already eliminated player
+            if dupe_proposal.is_correct().unwrap_or_default()
+                && !agent.was_proposer_eliminated_before(dupe_proposal)
+            {
+                info!("Correct proposal was already made honestly.");
+                return Ok(None);
+            }
+        };
+        // this invalid proposal will not participate in the tournament
+        warn!("Incrementing duplication counter");
+        // increment counter
+        dupe_counter += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kailua_sync::provider::memory::fixtures::*;
+    use kailua_sync::provider::memory::ScriptedProposal;
+
+    fn extra_data(block_number: u64, parent_index: u64, dupe_counter: u64) -> Bytes {
+        Bytes::from(
+            [
+                block_number.abi_encode_packed(),
+                parent_index.abi_encode_packed(),
+                dupe_counter.abi_encode_packed(),
+            ]
+            .concat(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fetch_unique_extra_data() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        // a faulty proposal of the correct output root with incorrect intermediate outputs
+        let faulty_proposal = ScriptedProposal {
+            io_field_elements: vec![U256::from(1); 3],
+            ..game_proposal(&chain, &deployment, FAULTY_GAME, 1, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+        let (block_number, output_root) = (
+            faulty_proposal.output_block_number,
+            faulty_proposal.output_root,
+        );
+        chain.mock_call(
+            FACTORY,
+            IDisputeGameFactory::gamesCall::new((
+                KAILUA_GAME_TYPE,
+                output_root,
+                extra_data(block_number, 0, 0),
+            )),
+            (FAULTY_GAME, 0u64),
+        );
+        chain.mock_call(
+            FACTORY,
+            IDisputeGameFactory::gamesCall::new((
+                KAILUA_GAME_TYPE,
+                output_root,
+                extra_data(block_number, 0, 1),
+            )),
+            (Address::ZERO, 0u64),
+        );
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        agent.sync(0, None).await.unwrap();
+        // faulty duplicates are skipped
+        assert_eq!(
+            fetch_unique_extra_data(&agent, 0, block_number, output_root)
+                .await
+                .unwrap(),
+            Some((1, extra_data(block_number, 0, 1).to_vec()))
+        );
+
+        // honest duplicates must be synchronized before deciding
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 2, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        chain.mock_call(
+            FACTORY,
+            IDisputeGameFactory::gamesCall::new((
+                KAILUA_GAME_TYPE,
+                output_root,
+                extra_data(block_number, 0, 1),
+            )),
+            (HONEST_GAME, 0u64),
+        );
+        assert_eq!(
+            fetch_unique_extra_data(&agent, 0, block_number, output_root)
+                .await
+                .unwrap(),
+            None
+        );
+        // honest duplicates are not repeated
+        agent.sync(0, None).await.unwrap();
+        assert_eq!(agent.proposals[&2].is_correct(), Some(true));
+        assert_eq!(
+            fetch_unique_extra_data(&agent, 0, block_number, output_root)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
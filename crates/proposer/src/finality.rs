@@ -0,0 +1,182 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::consensus::BlockHeader;
+use alloy::eips::BlockNumberOrTag;
+use alloy::network::BlockResponse;
+use anyhow::Context;
+use kailua_sync::agent::SyncAgent;
+use kailua_sync::transact::rpc::get_block;
+use kailua_sync::{await_tel, retry_res_ctx_timeout};
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+
+/// Policy restricting proposals to outputs derived from L1 blocks that are unlikely to be reorged
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct FinalityArgs {
+    /// Whether to only propose outputs derived entirely from finalized L1 blocks
+    #[clap(long, env, default_value_t = false)]
+    pub require_finalized_l1: bool,
+    /// The minimum number of L1 blocks that must be built on top of the L1 blocks an output was derived from before proposing it
+    #[clap(long, env)]
+    pub min_l1_confirmations: Option<u64>,
+}
+
+impl FinalityArgs {
+    /// Returns whether any restriction on the L1 blocks proposed outputs are derived from is
+    /// configured.
+    pub fn is_enabled(&self) -> bool {
+        self.require_finalized_l1 || self.min_l1_confirmations.is_some()
+    }
+
+    /// Returns a label describing the policy for telemetry.
+    pub fn policy(&self) -> String {
+        match (self.require_finalized_l1, self.min_l1_confirmations) {
+            (false, None) => "safe".to_string(),
+            (true, None) => "finalized".to_string(),
+            (false, Some(confirmations)) => format!("confirmations:{confirmations}"),
+            (true, Some(confirmations)) => format!("finalized+confirmations:{confirmations}"),
+        }
+    }
+
+    /// Returns the most recent l2 block derived entirely from L1 blocks that satisfy the policy.
+    ///
+    /// The finalized l2 block is reported by the op-node, while the safe head as of a confirmed
+    /// L1 block is read from its safe head database. When both apply, the lower bound is used.
+    pub async fn final_l2_block(&self, agent: &SyncAgent) -> anyhow::Result<Option<u64>> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("FinalityArgs::final_l2_block"));
+
+        let mut final_l2_block = None;
+        if self.require_finalized_l1 {
+            let sync_status = await_tel!(
+                context,
+                retry_res_ctx_timeout!(OpNode => agent.provider.chain.sync_status().await)
+            )?;
+            let finalized = sync_status["finalized_l2"]["number"]
+                .as_u64()
+                .context("finalized_l2")?;
+            final_l2_block = Some(finalized);
+        }
+        if let Some(confirmations) = self.min_l1_confirmations {
+            let confirmed_l1_block = await_tel!(
+                context,
+                get_block(&agent.provider.l1_provider, BlockNumberOrTag::Latest)
+            )?
+            .header()
+            .number()
+            .saturating_sub(confirmations);
+            let confirmed = await_tel!(
+                context,
+                agent
+                    .provider
+                    .chain
+                    .safe_head_at_l1_block(confirmed_l1_block)
+            )
+            .context("ChainReader::safe_head_at_l1_block")?;
+            final_l2_block = Some(final_l2_block.map_or(confirmed, |b: u64| b.min(confirmed)));
+        }
+        Ok(final_l2_block)
+    }
+}
+
+/// The l2 block of an output compared against the most recent l2 block deemed final
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L1Finality {
+    pub block_number: u64,
+    pub final_l2_block: u64,
+}
+
+impl L1Finality {
+    /// Returns whether the output was derived from final L1 blocks only.
+    pub fn is_final(&self) -> bool {
+        self.block_number <= self.final_l2_block
+    }
+
+    /// Returns the number of l2 blocks by which the output is ahead of the final l2 block.
+    pub fn lag(&self) -> u64 {
+        self.block_number.saturating_sub(self.final_l2_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kailua_sync::provider::memory::fixtures::*;
+
+    #[test]
+    fn test_finality_policy() {
+        let mut args = FinalityArgs::default();
+        assert!(!args.is_enabled());
+        assert_eq!(args.policy(), "safe");
+        args.require_finalized_l1 = true;
+        assert!(args.is_enabled());
+        assert_eq!(args.policy(), "finalized");
+        args.min_l1_confirmations = Some(12);
+        assert_eq!(args.policy(), "finalized+confirmations:12");
+        args.require_finalized_l1 = false;
+        assert!(args.is_enabled());
+        assert_eq!(args.policy(), "confirmations:12");
+    }
+
+    #[test]
+    fn test_l1_finality() {
+        let finality = L1Finality {
+            block_number: 100,
+            final_l2_block: 100,
+        };
+        assert!(finality.is_final());
+        assert_eq!(finality.lag(), 0);
+        let finality = L1Finality {
+            block_number: 132,
+            final_l2_block: 100,
+        };
+        assert!(!finality.is_final());
+        assert_eq!(finality.lag(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_final_l2_block() {
+        let chain = anchored_chain(&deployment());
+        let data_dir = tempfile::tempdir().unwrap();
+        let agent = sync_agent(&chain, data_dir.path(), None).await;
+        for _ in 0..20 {
+            chain.mine_l1_block();
+        }
+        let (l1_head, _, _) = chain.l1_head();
+        chain.set_finalized_l2(4);
+        chain.set_safe_head_at_l1(l1_head - 8, 8);
+        chain.set_safe_head_at_l1(l1_head - 2, 12);
+
+        let mut args = FinalityArgs::default();
+        assert_eq!(args.final_l2_block(&agent).await.unwrap(), None);
+        // the finalized l2 block is reported by the op-node
+        args.require_finalized_l1 = true;
+        assert_eq!(args.final_l2_block(&agent).await.unwrap(), Some(4));
+        // the safe head is bounded by the l1 block it was derived from
+        args.require_finalized_l1 = false;
+        args.min_l1_confirmations = Some(2);
+        assert_eq!(args.final_l2_block(&agent).await.unwrap(), Some(12));
+        args.min_l1_confirmations = Some(5);
+        assert_eq!(args.final_l2_block(&agent).await.unwrap(), Some(8));
+        // the lower of both bounds applies
+        args.require_finalized_l1 = true;
+        assert_eq!(args.final_l2_block(&agent).await.unwrap(), Some(4));
+        // no output is final before the safe head database covers the confirmed l1 block
+        args.require_finalized_l1 = false;
+        args.min_l1_confirmations = Some(l1_head);
+        args.final_l2_block(&agent).await.unwrap_err();
+    }
+}
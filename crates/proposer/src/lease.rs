@@ -0,0 +1,444 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+/// Age after which the lock guarding a lease file is considered abandoned
+pub const LEASE_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Leader election arguments for running several proposer instances
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct LeaseArgs {
+    /// Shared lease file through which proposer instances elect a leader (disabled if unset)
+    #[clap(long, env)]
+    pub lease_file: Option<PathBuf>,
+    /// Duration of the leader lease in seconds, which bounds the time for a standby to take over
+    #[clap(long, env, required = false, default_value_t = 30)]
+    pub lease_duration: u64,
+    /// Identifier of this instance in the lease (defaults to the host name and process id)
+    #[clap(long, env)]
+    pub lease_holder: Option<String>,
+}
+
+impl LeaseArgs {
+    pub fn leader_lease(&self) -> Option<LeaderLease> {
+        let path = self.lease_file.as_ref()?;
+        let holder = self.lease_holder.clone().unwrap_or_else(|| {
+            let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+            format!("{host}-{}", std::process::id())
+        });
+        Some(LeaderLease::new(
+            Box::new(FileLease::new(path.clone())),
+            holder,
+            Duration::from_secs(self.lease_duration),
+        ))
+    }
+}
+
+/// The current holder of the lease and its expiry as a unix timestamp in milliseconds
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lease {
+    pub holder: String,
+    pub expires_at: u128,
+}
+
+/// Storage shared by the proposer instances competing for the lease
+pub trait LeaseBackend: Send + Sync {
+    /// Grants the lease to the holder until the given expiry unless another holder's lease is
+    /// still valid at the given time, and returns the resulting lease.
+    fn acquire(&self, holder: &str, now: u128, expires_at: u128) -> anyhow::Result<Lease>;
+
+    /// Gives up the lease if held by the given holder.
+    fn release(&self, holder: &str) -> anyhow::Result<()>;
+}
+
+/// A lease stored in a file on storage shared by all instances
+#[derive(Clone, Debug)]
+pub struct FileLease {
+    path: PathBuf,
+}
+
+impl FileLease {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn lock_path(&self) -> PathBuf {
+        self.path.with_extension("lock")
+    }
+
+    /// Runs the given function while exclusively holding the lock file.
+    fn locked<T>(&self, f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let lock_path = self.lock_path();
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).context("create_dir_all")?;
+        }
+        let create_lock = || {
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+        };
+        if let Err(err) = create_lock() {
+            // Remove locks abandoned by crashed instances and try again once
+            let abandoned = std::fs::metadata(&lock_path)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified.elapsed().unwrap_or_default() > LEASE_LOCK_TIMEOUT);
+            if !abandoned {
+                bail!("Lease file {} is locked: {err}", self.path.display());
+            }
+            warn!("Removing abandoned lease lock {}.", lock_path.display());
+            std::fs::remove_file(&lock_path).ok();
+            if let Err(err) = create_lock() {
+                bail!("Lease file {} is locked: {err}", self.path.display());
+            }
+        }
+        let result = f();
+        std::fs::remove_file(&lock_path).context("remove_file")?;
+        result
+    }
+
+    fn read(&self) -> anyhow::Result<Option<Lease>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let data = std::fs::read(&self.path).context("read")?;
+        let lease = serde_json::from_slice(&data)
+            .with_context(|| format!("Corrupt lease file {}", self.path.display()))?;
+        Ok(Some(lease))
+    }
+
+    fn write(&self, lease: &Lease) -> anyhow::Result<()> {
+        // Replace the lease atomically so that readers never observe a partial write
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(lease)?).context("write")?;
+        std::fs::rename(&tmp_path, &self.path).context("rename")
+    }
+}
+
+impl LeaseBackend for FileLease {
+    fn acquire(&self, holder: &str, now: u128, expires_at: u128) -> anyhow::Result<Lease> {
+        self.locked(|| {
+            if let Some(lease) = self.read()? {
+                if lease.holder != holder && lease.expires_at > now {
+                    return Ok(lease);
+                }
+            }
+            let lease = Lease {
+                holder: holder.to_string(),
+                expires_at,
+            };
+            self.write(&lease)?;
+            Ok(lease)
+        })
+    }
+
+    fn release(&self, holder: &str) -> anyhow::Result<()> {
+        self.locked(|| {
+            if self.read()?.is_some_and(|lease| lease.holder == holder) {
+                std::fs::remove_file(&self.path).context("remove_file")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Tracks whether this instance is the leader allowed to resolve and propose
+pub struct LeaderLease {
+    backend: Box<dyn LeaseBackend>,
+    holder: String,
+    duration: Duration,
+    /// Expiry of the lease held by this instance
+    fence: LeaseFence,
+}
+
+impl LeaderLease {
+    pub fn new(backend: Box<dyn LeaseBackend>, holder: String, duration: Duration) -> Self {
+        Self {
+            backend,
+            holder,
+            duration,
+            fence: LeaseFence {
+                held_until: Default::default(),
+                margin: duration.as_millis() / 3,
+            },
+        }
+    }
+
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// Acquires or renews the lease, returning whether this instance is the leader.
+    pub fn refresh(&mut self) -> bool {
+        let now = unix_millis();
+        let was_leader = self.fence.is_held_at(now);
+        match self
+            .backend
+            .acquire(&self.holder, now, now + self.duration.as_millis())
+        {
+            Ok(lease) if lease.holder == self.holder => {
+                if !was_leader {
+                    info!("Acquired proposer lease as {}.", self.holder);
+                }
+                self.fence.set(Some(lease.expires_at));
+            }
+            Ok(lease) => {
+                if was_leader {
+                    warn!("Lost proposer lease to {}.", lease.holder);
+                } else {
+                    info!(
+                        "Standing by while {} holds the proposer lease.",
+                        lease.holder
+                    );
+                }
+                self.fence.set(None);
+            }
+            Err(err) => warn!("Failed to renew proposer lease: {err:?}"),
+        }
+        self.is_leader()
+    }
+
+    /// Returns whether the lease is held for at least another third of its duration.
+    ///
+    /// The margin leaves the leader time to stop before a standby may take over.
+    pub fn is_leader(&self) -> bool {
+        self.fence.is_held()
+    }
+
+    /// Returns a handle for checking that the lease is still held before each transaction.
+    pub fn fence(&self) -> LeaseFence {
+        self.fence.clone()
+    }
+
+    /// Drives the future to completion while renewing the lease every third of its duration.
+    ///
+    /// The future is dropped as soon as the lease is lost, so that a leader waiting for its
+    /// transactions to be confirmed stops publishing before a standby takes over.
+    pub async fn renew_while<F: Future>(&mut self, future: F) -> anyhow::Result<F::Output> {
+        let mut renewal = interval(self.duration / 3);
+        renewal.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(future);
+        loop {
+            tokio::select! {
+                output = &mut future => return Ok(output),
+                _ = renewal.tick() => {
+                    if !self.refresh() {
+                        bail!("Lost proposer lease before completion.");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gives up the lease so that a standby can take over immediately.
+    pub fn release(&mut self) {
+        self.fence.set(None);
+        if let Err(err) = self.backend.release(&self.holder) {
+            warn!("Failed to release proposer lease: {err:?}");
+        }
+    }
+}
+
+/// Drives the future to completion, renewing the lease while it runs if one is configured.
+pub async fn renew_while<F: Future>(
+    lease: Option<&mut LeaderLease>,
+    future: F,
+) -> anyhow::Result<F::Output> {
+    match lease {
+        Some(lease) => lease.renew_while(future).await,
+        None => Ok(future.await),
+    }
+}
+
+/// A shared view of the expiry of the lease held by this instance
+#[derive(Clone, Debug, Default)]
+pub struct LeaseFence {
+    /// Expiry of the lease as a unix timestamp in milliseconds, or zero if not held
+    held_until: Arc<AtomicU64>,
+    /// Time in milliseconds before the expiry at which the lease is no longer considered held
+    margin: u128,
+}
+
+impl LeaseFence {
+    /// Returns whether the lease is still held with enough margin to publish a transaction.
+    pub fn is_held(&self) -> bool {
+        self.is_held_at(unix_millis())
+    }
+
+    /// Fails unless the lease is still held, to be checked before each transaction is sent.
+    pub fn check(&self) -> anyhow::Result<()> {
+        if !self.is_held() {
+            bail!("Not sending transaction without holding the proposer lease.");
+        }
+        Ok(())
+    }
+
+    fn is_held_at(&self, now: u128) -> bool {
+        self.held_until.load(Ordering::Acquire) as u128 > now + self.margin
+    }
+
+    fn set(&self, held_until: Option<u128>) {
+        let held_until = held_until.map_or(0, |held_until| held_until as u64);
+        self.held_until.store(held_until, Ordering::Release);
+    }
+}
+
+pub fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// Reads the lease currently stored in the given file.
+pub fn read_lease(path: &Path) -> anyhow::Result<Option<Lease>> {
+    FileLease::new(path.to_path_buf()).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_lease() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proposer.lease");
+        let backend = FileLease::new(path.clone());
+
+        // The first holder acquires and renews the lease
+        assert_eq!(backend.acquire("a", 0, 100).unwrap().holder, "a");
+        assert_eq!(backend.acquire("a", 50, 150).unwrap().expires_at, 150);
+        // Others stand by until it expires
+        assert_eq!(backend.acquire("b", 100, 200).unwrap().holder, "a");
+        assert_eq!(backend.acquire("b", 151, 251).unwrap().holder, "b");
+        assert_eq!(read_lease(&path).unwrap().unwrap().holder, "b");
+        // Only the holder can release the lease
+        backend.release("a").unwrap();
+        assert!(read_lease(&path).unwrap().is_some());
+        backend.release("b").unwrap();
+        assert!(read_lease(&path).unwrap().is_none());
+
+        // Concurrent updates are refused
+        std::fs::write(backend.lock_path(), []).unwrap();
+        backend.acquire("a", 0, 100).unwrap_err();
+        // Abandoned locks are cleared
+        let abandoned = SystemTime::now() - LEASE_LOCK_TIMEOUT * 2;
+        std::fs::File::options()
+            .write(true)
+            .open(backend.lock_path())
+            .unwrap()
+            .set_modified(abandoned)
+            .unwrap();
+        assert_eq!(backend.acquire("a", 0, 100).unwrap().holder, "a");
+        assert!(!backend.lock_path().exists());
+
+        // Corrupt lease files are reported
+        std::fs::write(&path, b"{\"holder\":").unwrap();
+        assert!(read_lease(&path).is_err());
+        backend.acquire("a", 0, 100).unwrap_err();
+    }
+
+    #[test]
+    fn test_leader_lease() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proposer.lease");
+        let duration = Duration::from_secs(60);
+        let mut leader = LeaderLease::new(
+            Box::new(FileLease::new(path.clone())),
+            "a".to_string(),
+            duration,
+        );
+        let mut standby = LeaderLease::new(
+            Box::new(FileLease::new(path.clone())),
+            "b".to_string(),
+            duration,
+        );
+        assert!(leader.refresh());
+        assert!(!standby.refresh());
+        assert!(leader.refresh());
+        // The standby takes over once the leader releases the lease
+        leader.release();
+        assert!(!leader.is_leader());
+        assert!(standby.refresh());
+        assert!(!leader.refresh());
+    }
+
+    /// A lease backend counting acquisitions
+    struct CountingLease {
+        inner: FileLease,
+        acquisitions: Arc<AtomicU64>,
+    }
+
+    impl LeaseBackend for CountingLease {
+        fn acquire(&self, holder: &str, now: u128, expires_at: u128) -> anyhow::Result<Lease> {
+            self.acquisitions.fetch_add(1, Ordering::Relaxed);
+            self.inner.acquire(holder, now, expires_at)
+        }
+
+        fn release(&self, holder: &str) -> anyhow::Result<()> {
+            self.inner.release(holder)
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_renew_while() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proposer.lease");
+        let duration = Duration::from_millis(1500);
+        let acquisitions = Arc::new(AtomicU64::new(0));
+        let mut leader = LeaderLease::new(
+            Box::new(CountingLease {
+                inner: FileLease::new(path.clone()),
+                acquisitions: acquisitions.clone(),
+            }),
+            "a".to_string(),
+            duration,
+        );
+        assert!(leader.refresh());
+        let fence = leader.fence();
+        fence.check().unwrap();
+        // The lease is renewed every third of its duration while the future runs
+        let output = leader
+            .renew_while(async {
+                tokio::time::sleep(duration + duration / 3).await;
+                fence.is_held()
+            })
+            .await
+            .unwrap();
+        assert!(output);
+        assert!(acquisitions.load(Ordering::Relaxed) >= 4);
+        // The wait is aborted once another instance takes over
+        let backend = FileLease::new(path.clone());
+        let result = leader
+            .renew_while(async {
+                backend.release("a").unwrap();
+                backend.acquire("b", 0, u128::MAX).unwrap();
+                tokio::time::sleep(duration * 2).await;
+            })
+            .await;
+        assert!(result.is_err());
+        assert!(!leader.is_leader());
+        fence.check().unwrap_err();
+    }
+}
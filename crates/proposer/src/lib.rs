@@ -13,6 +13,12 @@
 // limitations under the License.
 
 pub mod args;
+pub mod exit;
 pub mod fetch;
+pub mod finality;
+pub mod lease;
+pub mod pipeline;
 pub mod propose;
 pub mod resolve;
+pub mod schedule;
+pub mod self_prove;
@@ -0,0 +1,577 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lease::LeaseFence;
+use alloy::consensus::BlobTransactionSidecar;
+use alloy::contract::{CallBuilder, CallDecoder};
+use alloy::network::{Network, TransactionBuilder4844};
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::Provider;
+use alloy::sol_types::SolValue;
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use futures::future::join_all;
+use kailua_contracts::*;
+use kailua_kona::blobs::hash_to_fe;
+use kailua_sync::agent::SyncAgent;
+use kailua_sync::proposal::Proposal;
+use kailua_sync::stall::Stall;
+use kailua_sync::transact::nonce::nonce_tracker;
+use kailua_sync::transact::Transact;
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{TraceContextExt, Tracer};
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// A proposal extending a preceding proposal by this proposer that is not yet on chain
+#[derive(Clone, Debug)]
+pub struct PipelinedProposal {
+    pub block_number: u64,
+    pub output_root: B256,
+    pub extra_data: Vec<u8>,
+    pub sidecar: BlobTransactionSidecar,
+}
+
+/// Prepares up to `depth - 1` successors of the proposal about to be made for the given block.
+///
+/// Each successor names its predecessor as parent by assuming that the predecessor is assigned
+/// the next factory index, which holds as long as no other proposals are made meanwhile. This is
+/// checked using [verify_pipelined_parent] before each successor is published.
+/// Successors are only prepared for outputs already synced from the op-node's safe head whose
+/// proposal gap and vanguard advantage have elapsed.
+pub fn prepare_pipelined_proposals(
+    agent: &SyncAgent,
+    first_block_number: u64,
+    first_factory_index: u64,
+    depth: u64,
+    chain_time: u64,
+    vanguard_advantage: u64,
+    final_l2_block: Option<u64>,
+) -> Vec<PipelinedProposal> {
+    let mut proposals = vec![];
+    for k in 1..depth {
+        let parent_block_number =
+            first_block_number + (k - 1) * agent.deployment.blocks_per_proposal();
+        let block_number = parent_block_number + agent.deployment.blocks_per_proposal();
+        if final_l2_block.is_some_and(|final_l2_block| parent_block_number >= final_l2_block) {
+            break;
+        }
+        if agent.cursor.last_output_index < block_number {
+            break;
+        }
+        if chain_time < agent.deployment.min_proposal_time(block_number) + vanguard_advantage {
+            break;
+        }
+        let Some(output_root) = agent.outputs.get(&block_number).copied() else {
+            break;
+        };
+        let mut io_field_elements = vec![];
+        for i in 1..agent.deployment.proposal_output_count {
+            let io_block_number = parent_block_number + i * agent.deployment.output_block_span;
+            let Some(output_hash) = agent.outputs.get(&io_block_number).copied() else {
+                break;
+            };
+            io_field_elements.push(hash_to_fe(output_hash));
+        }
+        if io_field_elements.len() as u64 != agent.deployment.proposal_output_count - 1 {
+            break;
+        }
+        let sidecar = match Proposal::create_sidecar(&io_field_elements) {
+            Ok(sidecar) => sidecar,
+            Err(err) => {
+                warn!("Failed to create blob sidecar for pipelined proposal: {err:?}");
+                break;
+            }
+        };
+        let extra_data = [
+            block_number.abi_encode_packed(),
+            (first_factory_index + k - 1).abi_encode_packed(),
+            0u64.abi_encode_packed(),
+        ]
+        .concat();
+        proposals.push(PipelinedProposal {
+            block_number,
+            output_root,
+            extra_data,
+            sidecar,
+        });
+    }
+    if !proposals.is_empty() {
+        info!(
+            "Prepared {} pipelined proposals up to l2 block number {}.",
+            proposals.len(),
+            proposals.last().unwrap().block_number
+        );
+    }
+    proposals
+}
+
+/// Checks that the factory indices assigned since the first pipelined proposal hold the
+/// pipelined proposals in order, up to the parent of the `k`-th successor.
+///
+/// Fails if any of these indices was assigned to a game of another proposal, in which case the
+/// `k`-th successor would name the wrong parent. Otherwise, returns whether the parent of the
+/// `k`-th successor is already on chain.
+pub async fn verify_pipelined_parent<P: Provider>(
+    factory: &IDisputeGameFactory::IDisputeGameFactoryInstance<P>,
+    proposer: Address,
+    first_factory_index: u64,
+    block_numbers: &[u64],
+    k: u64,
+) -> anyhow::Result<bool> {
+    let tracer = tracer("kailua");
+    let context =
+        opentelemetry::Context::current_with_span(tracer.start("verify_pipelined_parent"));
+
+    let parent_index = first_factory_index + k - 1;
+    let game_count: u64 = factory
+        .gameCount()
+        .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
+        .await?
+        .to();
+    for index in first_factory_index..game_count.min(parent_index + 1) {
+        let game_address = factory
+            .gameAtIndex(U256::from(index))
+            .stall_with_context(context.clone(), "DisputeGameFactory::gameAtIndex")
+            .await?
+            .proxy_;
+        let game = KailuaTournament::new(game_address, factory.provider());
+        let game_proposer = game
+            .proposer()
+            .stall_with_context(context.clone(), "KailuaTournament::proposer")
+            .await?;
+        let game_block_number: u64 = game
+            .l2BlockNumber()
+            .stall_with_context(context.clone(), "KailuaTournament::l2BlockNumber")
+            .await?
+            .to();
+        let expected_block_number = block_numbers
+            .get((index - first_factory_index) as usize)
+            .copied();
+        if game_proposer != proposer || Some(game_block_number) != expected_block_number {
+            bail!("Factory index {index} was assigned to game {game_address} instead of the pipelined proposal.");
+        }
+    }
+    Ok(game_count > parent_index)
+}
+
+/// A call published with an explicit nonce as part of a pipeline
+#[async_trait]
+pub trait PipelinedCall: Send + Sync {
+    type Receipt: Send;
+
+    /// Returns the lowest nonce of the sender not held by any pending transaction.
+    async fn next_nonce(&self, sender: Address) -> anyhow::Result<u64>;
+
+    /// Publishes the call with the given nonce and gas limit, and waits for its receipt.
+    async fn transact_at(
+        self,
+        nonce: u64,
+        gas: Option<u64>,
+        context: opentelemetry::Context,
+        span: &'static str,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<Self::Receipt>;
+}
+
+#[async_trait]
+impl<P: Provider<N>, D: CallDecoder + Send + Sync + 'static, N: Network> PipelinedCall
+    for CallBuilder<P, D, N>
+where
+    CallBuilder<P, D, N>: Transact<N> + Clone,
+    N::TransactionRequest: TransactionBuilder4844,
+{
+    type Receipt = N::ReceiptResponse;
+
+    async fn next_nonce(&self, sender: Address) -> anyhow::Result<u64> {
+        Ok(nonce_tracker()
+            .next_nonce::<_, N>(&self.provider, sender)
+            .await?)
+    }
+
+    async fn transact_at(
+        self,
+        nonce: u64,
+        gas: Option<u64>,
+        context: opentelemetry::Context,
+        span: &'static str,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<Self::Receipt> {
+        let call = match gas {
+            Some(gas) => self.gas(gas),
+            None => self,
+        };
+        call.nonce(nonce)
+            .timed_transact_with_context(context, span, timeout)
+            .await
+    }
+}
+
+/// Publishes the calls with consecutive nonces in order, without waiting for each to be
+/// confirmed before publishing the next.
+///
+/// A call is only published once its predecessor has been published, so that a failure to
+/// publish never leaves a gap in the sender's nonces, and only while the lease is still held.
+/// Before each call but the first is published, `verify` is awaited with its position, and may
+/// fail to stop the pipeline or return a new gas limit for the call.
+#[allow(clippy::too_many_arguments)]
+pub async fn transact_pipelined<C: PipelinedCall, V, F>(
+    calls: Vec<C>,
+    sender: Address,
+    fence: Option<&LeaseFence>,
+    verify: V,
+    context: opentelemetry::Context,
+    span: &'static str,
+    timeout: Option<Duration>,
+) -> Vec<anyhow::Result<C::Receipt>>
+where
+    V: Fn(usize) -> F,
+    F: Future<Output = anyhow::Result<Option<u64>>>,
+{
+    let Some(first) = calls.first() else {
+        return vec![];
+    };
+    let base_nonce = match first.next_nonce(sender).await {
+        Ok(nonce) => nonce,
+        Err(err) => {
+            let msg = format!("Failed to fetch next nonce: {err:?}");
+            return calls.iter().map(|_| Err(anyhow!(msg.clone()))).collect();
+        }
+    };
+    // Whether each call succeeded, once finished
+    let outcomes = calls
+        .iter()
+        .map(|_| watch::channel(None::<bool>).0)
+        .collect::<Vec<_>>();
+    let (outcomes, verify) = (&outcomes, &verify);
+    let transactions = calls.into_iter().enumerate().map(|(i, call)| {
+        let context = context.clone();
+        async move {
+            let nonce = base_nonce + i as u64;
+            let finish = |success: bool| outcomes[i].send_replace(Some(success));
+            // Nodes reject blob transactions that leave a gap in the sender's nonces
+            if i > 0 {
+                let mut preceding = outcomes[i - 1].subscribe();
+                loop {
+                    let tracked = nonce_tracker().tracked();
+                    tokio::pin!(tracked);
+                    tracked.as_mut().enable();
+                    if nonce_tracker().is_tracked(sender, nonce - 1) {
+                        break;
+                    }
+                    match *preceding.borrow_and_update() {
+                        Some(true) => break,
+                        Some(false) => {
+                            finish(false);
+                            return Err(anyhow!(
+                                "Skipped publication after the preceding transaction failed."
+                            ));
+                        }
+                        None => {}
+                    }
+                    tokio::select! {
+                        _ = tracked => {}
+                        _ = preceding.changed() => {}
+                    }
+                }
+            }
+            if let Err(err) = fence.map_or(Ok(()), LeaseFence::check) {
+                finish(false);
+                return Err(err);
+            }
+            let gas = match i {
+                0 => None,
+                _ => match verify(i).await {
+                    Ok(gas) => gas,
+                    Err(err) => {
+                        finish(false);
+                        return Err(err);
+                    }
+                },
+            };
+            let result = call.transact_at(nonce, gas, context, span, timeout).await;
+            finish(result.is_ok());
+            result
+        }
+    });
+    join_all(transactions).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+    use kailua_sync::provider::memory::fixtures::*;
+    use kailua_sync::transact::nonce::TrackedTransaction;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+    use tokio::sync::Barrier;
+
+    const PIPELINED_GAME: Address = address!("0x0000000000000000000000000000000000000013");
+
+    #[tokio::test]
+    async fn test_prepare_pipelined_proposals() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        for block_number in 0..=16 {
+            agent
+                .outputs
+                .insert(block_number, chain.output_at(block_number));
+        }
+        agent.cursor.last_output_index = 16;
+
+        // successors extend each other up to the safe head and name the next factory indices
+        let proposals = prepare_pipelined_proposals(&agent, 4, 7, 8, 1000, 0, None);
+        assert_eq!(
+            proposals.iter().map(|p| p.block_number).collect::<Vec<_>>(),
+            vec![8, 12, 16]
+        );
+        for (k, proposal) in proposals.iter().enumerate() {
+            assert_eq!(proposal.output_root, chain.output_at(proposal.block_number));
+            assert_eq!(
+                proposal.extra_data,
+                [
+                    proposal.block_number.abi_encode_packed(),
+                    (7 + k as u64).abi_encode_packed(),
+                    0u64.abi_encode_packed(),
+                ]
+                .concat()
+            );
+        }
+        // the pipeline depth, proposal gap and final l2 block bound the successors
+        assert_eq!(
+            prepare_pipelined_proposals(&agent, 4, 7, 2, 1000, 0, None).len(),
+            1
+        );
+        let gap = deployment.min_proposal_time(12);
+        assert_eq!(
+            prepare_pipelined_proposals(&agent, 4, 7, 8, gap - 1, 0, None).len(),
+            1
+        );
+        assert_eq!(
+            prepare_pipelined_proposals(&agent, 4, 7, 8, gap - 1, 8, None).len(),
+            0
+        );
+        assert_eq!(
+            prepare_pipelined_proposals(&agent, 4, 7, 8, 1000, 0, Some(8)).len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_pipelined_parent() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        install_retry_policy();
+        let provider = kailua_sync::provider::SyncProvider::in_memory(&chain);
+        let factory = IDisputeGameFactory::new(FACTORY, &provider.l1_provider);
+        let block_numbers = [4, 8, 12];
+
+        // parents are not on chain before their proposals are included
+        assert!(
+            !verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &block_numbers, 1)
+                .await
+                .unwrap()
+        );
+        // the first proposal is assigned the expected index
+        let first = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain.script_proposal(&deployment, &first).unwrap();
+        assert!(
+            verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &block_numbers, 1)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &block_numbers, 2)
+                .await
+                .unwrap()
+        );
+        // the pipeline stops once another proposal takes the index assumed for a parent
+        chain
+            .script_proposal(
+                &deployment,
+                &game_proposal(&chain, &deployment, FAULTY_GAME, 2, FAULTY_PROPOSER),
+            )
+            .unwrap();
+        verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &block_numbers, 2)
+            .await
+            .unwrap_err();
+        verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &block_numbers, 3)
+            .await
+            .unwrap_err();
+        // proposals made out of order are detected as well
+        let second = child_proposal(
+            &chain,
+            &deployment,
+            &first,
+            PIPELINED_GAME,
+            2,
+            HONEST_PROPOSER,
+        );
+        chain.script_proposal(&deployment, &second).unwrap();
+        assert!(
+            verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &block_numbers, 2)
+                .await
+                .unwrap()
+        );
+        verify_pipelined_parent(&factory, HONEST_PROPOSER, 1, &[4, 12], 2)
+            .await
+            .unwrap_err();
+    }
+
+    /// A call that is tracked as published once all of the given number of calls are, and
+    /// returns its nonce and gas limit as receipt.
+    struct FakeCall {
+        sender: Address,
+        published: Arc<Barrier>,
+        rejected: bool,
+    }
+
+    #[async_trait]
+    impl PipelinedCall for FakeCall {
+        type Receipt = (u64, Option<u64>);
+
+        async fn next_nonce(&self, _sender: Address) -> anyhow::Result<u64> {
+            Ok(5)
+        }
+
+        async fn transact_at(
+            self,
+            nonce: u64,
+            gas: Option<u64>,
+            _context: opentelemetry::Context,
+            _span: &'static str,
+            _timeout: Option<Duration>,
+        ) -> anyhow::Result<Self::Receipt> {
+            if self.rejected {
+                bail!("Transaction rejected.");
+            }
+            nonce_tracker().track(TrackedTransaction {
+                from: self.sender,
+                nonce,
+                gas_limit: gas.unwrap_or(21_000),
+                max_fee_per_gas: 1,
+                max_priority_fee_per_gas: 1,
+                max_fee_per_blob_gas: None,
+                hashes: vec![],
+                published_at: Instant::now(),
+            });
+            // confirmation waits for the successors to be published
+            self.published.wait().await;
+            nonce_tracker().forget(self.sender, nonce);
+            Ok((nonce, gas))
+        }
+    }
+
+    /// Runs a pipeline of fake calls of which the given one is rejected, returning the receipts
+    /// and the positions passed to the verifier.
+    async fn run_pipeline(
+        sender: Address,
+        count: usize,
+        published: usize,
+        rejected: Option<usize>,
+        fence: Option<&LeaseFence>,
+        verify: impl Fn(usize) -> anyhow::Result<Option<u64>>,
+    ) -> (Vec<anyhow::Result<(u64, Option<u64>)>>, Vec<usize>) {
+        let published = Arc::new(Barrier::new(published));
+        let calls = (0..count)
+            .map(|i| FakeCall {
+                sender,
+                published: published.clone(),
+                rejected: rejected == Some(i),
+            })
+            .collect();
+        let verified = Mutex::new(vec![]);
+        let results = tokio::time::timeout(
+            Duration::from_secs(10),
+            transact_pipelined(
+                calls,
+                sender,
+                fence,
+                |i| {
+                    verified.lock().unwrap().push(i);
+                    let result = verify(i);
+                    async move { result }
+                },
+                opentelemetry::Context::current(),
+                "FakeCall",
+                None,
+            ),
+        )
+        .await
+        .expect("Pipeline stalled");
+        (results, verified.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_transact_pipelined() {
+        // successors are published before their predecessors are confirmed
+        let sender = address!("0x00000000000000000000000000000000000000a1");
+        let (results, verified) =
+            run_pipeline(
+                sender,
+                3,
+                3,
+                None,
+                None,
+                |i| Ok((i == 2).then_some(100_000)),
+            )
+            .await;
+        let receipts = results
+            .into_iter()
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(receipts, vec![(5, None), (6, None), (7, Some(100_000))]);
+        assert_eq!(verified, vec![1, 2]);
+        assert!(!nonce_tracker().is_tracked(sender, 5));
+
+        // a failed verification stops the pipeline
+        let sender = address!("0x00000000000000000000000000000000000000a2");
+        let (results, verified) = run_pipeline(sender, 3, 1, None, None, |_| {
+            Err(anyhow!("Parent index taken."))
+        })
+        .await;
+        assert_eq!(results[0].as_ref().unwrap(), &(5, None));
+        assert!(results[1].is_err());
+        assert!(results[2].is_err());
+        assert_eq!(verified, vec![1]);
+
+        // successors of a rejected transaction are skipped without leaving a nonce gap
+        let sender = address!("0x00000000000000000000000000000000000000a3");
+        let (results, verified) = run_pipeline(sender, 3, 1, Some(1), None, |_| Ok(None)).await;
+        assert!(results[0].is_ok());
+        assert_eq!(
+            results[1].as_ref().unwrap_err().to_string(),
+            "Transaction rejected."
+        );
+        assert!(results[2]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .starts_with("Skipped publication"));
+        assert_eq!(verified, vec![1]);
+
+        // nothing is published without holding the lease
+        let sender = address!("0x00000000000000000000000000000000000000a4");
+        let fence = LeaseFence::default();
+        let (results, verified) =
+            run_pipeline(sender, 2, 1, None, Some(&fence), |_| Ok(None)).await;
+        assert!(results.iter().all(|result| result.is_err()));
+        assert!(verified.is_empty());
+    }
+}
@@ -13,26 +13,35 @@
 // limitations under the License.
 
 use crate::args::ProposeArgs;
+use crate::exit::{claim_proposer_bond, fetch_bond_reports, BondStatus};
 use crate::fetch::{
-    fetch_paid_bond, fetch_participation_bond, fetch_vanguard, fetch_vanguard_advantage,
+    fetch_paid_bond, fetch_participation_bond, fetch_unique_extra_data, fetch_vanguard,
+    fetch_vanguard_advantage,
 };
-use crate::resolve::resolve_next_pending_proposal;
+use crate::finality::L1Finality;
+use crate::lease::{renew_while, LeaderLease, LeaseFence};
+use crate::pipeline::{prepare_pipelined_proposals, transact_pipelined, verify_pipelined_parent};
+use crate::resolve::resolve_pending_proposals;
+use crate::schedule::BlobFeeScheduler;
+use crate::self_prove::SelfProver;
 use alloy::consensus::BlockHeader;
 use alloy::eips::BlockNumberOrTag;
 use alloy::network::{BlockResponse, Ethereum, TxSigner};
 use alloy::primitives::Bytes;
 use alloy::providers::Provider;
-use alloy::sol_types::SolValue;
 use anyhow::{bail, Context};
 use kailua_contracts::*;
 use kailua_kona::blobs::hash_to_fe;
 use kailua_sync::agent::{SyncAgent, FINAL_L2_BLOCK_RESOLVED};
 use kailua_sync::proposal::Proposal;
 use kailua_sync::stall::Stall;
+use kailua_sync::transact::dry_run::{dry_run, is_dry_run};
+use kailua_sync::transact::journal::{journal, JOURNAL_FILE};
+use kailua_sync::transact::nonce::nonce_tracker;
 use kailua_sync::transact::provider::SafeProvider;
 use kailua_sync::transact::rpc::get_block;
 use kailua_sync::transact::Transact;
-use kailua_sync::{await_tel, await_tel_res, retry_res_ctx_timeout, KAILUA_GAME_TYPE};
+use kailua_sync::{await_tel, await_tel_res, retry_res_ctx_timeout};
 use opentelemetry::global::{meter, tracer};
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use opentelemetry::KeyValue;
@@ -41,7 +50,7 @@ use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{error, info, warn};
 
-pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()> {
+pub async fn propose(args: ProposeArgs, verbosity: u8, data_dir: PathBuf) -> anyhow::Result<()> {
     // Telemetry
     let meter = meter("kailua");
     let meter_prune_num = meter.u64_counter("proposer.prune.count").build();
@@ -49,20 +58,30 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
     let meter_resolve_num = meter.u64_counter("proposer.resolve.count").build();
     let meter_resolve_last = meter.u64_gauge("proposer.resolve.last").build();
     let meter_resolve_fail = meter.u64_counter("proposer.resolve.errs").build();
+    let meter_resolve_backlog = meter.u64_gauge("proposer.resolve.backlog").build();
     let meter_propose_num = meter.u64_counter("proposer.propose.count").build();
     let meter_propose_last = meter.u64_gauge("proposer.propose.last").build();
     let meter_propose_fail = meter.u64_counter("proposer.propose.errs").build();
     let meter_propose_fault = meter.u64_gauge("proposer.propose.fault").build();
+    let meter_finality_lag = meter.u64_gauge("proposer.finality.lag").build();
+    let meter_blob_fee_base = meter.u64_gauge("proposer.blob_fee.base").build();
+    let meter_blob_fee_saved = meter.u64_counter("proposer.blob_fee.saved").build();
+    let meter_blob_fee_overpaid = meter.u64_counter("proposer.blob_fee.overpaid").build();
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("propose"));
 
+    // Journal all submitted transactions
+    journal()
+        .open(&data_dir.join(JOURNAL_FILE))
+        .context("Journal::open")?;
+
     // initialize sync agent
     let mut agent = SyncAgent::new(
         &args.sync.provider,
-        data_dir,
+        data_dir.clone(),
         args.sync.kailua_game_implementation,
         args.sync.kailua_anchor_address,
-        args.bypass_chain_registry,
+        args.proving.bypass_chain_registry,
     )
     .await?;
     info!("KailuaTreasury({:?})", agent.deployment.treasury);
@@ -80,9 +99,39 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
         args.txn_args
             .premium_provider::<Ethereum>()
             .wallet(&proposer_wallet)
-            .connect_http(args.sync.provider.eth_rpc_url.as_str().try_into()?),
+            .connect_client(args.sync.provider.eth_rpc_client()?),
     );
     info!("Proposer address: {proposer_address}");
+    if args.finality.is_enabled() {
+        info!(
+            "Only proposing outputs derived from final L1 blocks (policy: {}).",
+            args.finality.policy()
+        );
+    }
+    // Prove the validity of own proposals in the background
+    let mut self_prover = args
+        .self_prove
+        .self_prove
+        .then(|| SelfProver::start(&args, proposer_address, verbosity, data_dir));
+    if args.exit {
+        warn!("Exiting: no new proposals will be made until the proposer bond is claimed.");
+    }
+
+    // Standby instances must not touch the transactions of the leader
+    let mut leader_lease = args.lease.leader_lease();
+    let lease_fence = leader_lease.as_ref().map(LeaderLease::fence);
+    if leader_lease.is_none() {
+        await_tel_res!(
+            context,
+            tracer,
+            "NonceTracker::reconcile",
+            nonce_tracker().reconcile(
+                &proposer_provider,
+                proposer_address,
+                args.txn_args.cancel_stale_txns
+            )
+        )?;
+    }
 
     // Run the proposer loop to sync and post
     info!(
@@ -92,6 +141,8 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
 
     // on startup, prioritize submitting a proposal
     let mut prioritize_proposing = true;
+    let mut last_bond_reports = vec![];
+    let mut blob_fee_scheduler = BlobFeeScheduler::new(&args.blob_fee);
     loop {
         // Wait for new data on every iteration
         sleep(Duration::from_secs(1)).await;
@@ -107,6 +158,9 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
                 .to_string()
                 .contains(FINAL_L2_BLOCK_RESOLVED)
             {
+                if let Some(lease) = leader_lease.as_mut() {
+                    lease.release();
+                }
                 return Ok(());
             }
             error!("Synchronization error: {err:?}");
@@ -126,24 +180,58 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
             );
         }
 
+        // Only the lease holder resolves and proposes
+        if let Some(lease) = leader_lease.as_mut() {
+            let was_leader = lease.is_leader();
+            if !lease.refresh() {
+                continue;
+            }
+            if !was_leader {
+                // Pick up where the previous leader left off
+                if let Err(err) = await_tel_res!(
+                    context,
+                    tracer,
+                    "NonceTracker::reconcile",
+                    nonce_tracker().reconcile(
+                        &proposer_provider,
+                        proposer_address,
+                        args.txn_args.cancel_stale_txns
+                    )
+                ) {
+                    error!("Failed to reconcile nonce after acquiring lease: {err:?}");
+                    lease.release();
+                    continue;
+                }
+                prioritize_proposing = true;
+            }
+        }
+
         // Resolve one proposal per iteration
         if !prioritize_proposing {
-            if let Err(err) = await_tel_res!(
-                context,
-                tracer,
-                "resolve_next_pending_proposal",
-                resolve_next_pending_proposal(
-                    &agent,
-                    &args.txn_args,
-                    &proposer_provider,
-                    &meter_prune_num,
-                    &meter_prune_fail,
-                    &meter_resolve_num,
-                    &meter_resolve_fail,
-                    &meter_resolve_last
+            let resolution = async {
+                await_tel_res!(
+                    context,
+                    tracer,
+                    "resolve_pending_proposals",
+                    resolve_pending_proposals(
+                        &agent,
+                        &args.txn_args,
+                        &proposer_provider,
+                        lease_fence.as_ref(),
+                        args.resolve_batch_size,
+                        args.resolve_gas_budget,
+                        &meter_prune_num,
+                        &meter_prune_fail,
+                        &meter_resolve_num,
+                        &meter_resolve_fail,
+                        &meter_resolve_last,
+                        &meter_resolve_backlog
+                    )
                 )
-            ) {
-                error!("Failed to resolve proposal: {err:?}");
+            };
+            match renew_while(leader_lease.as_mut(), resolution).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) | Err(err) => error!("Failed to resolve proposal: {err:?}"),
             }
         } else {
             warn!("Skipping resolving to prioritize proposing.");
@@ -152,15 +240,82 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
         // Reset priority
         prioritize_proposing = false;
 
+        // Defend own proposals with validity proofs
+        if let Some(self_prover) = self_prover.as_mut() {
+            if let Err(err) = await_tel!(
+                context,
+                self_prover.advance(&agent, &proposer_provider, args.txn_args.txn_timeout)
+            ) {
+                error!("Failed to advance self-proving: {err:?}");
+            }
+        }
+
+        // Claim back the bonds instead of proposing when exiting
+        if args.exit {
+            let reports = match await_tel!(context, fetch_bond_reports(&agent, proposer_address)) {
+                Ok(reports) => reports,
+                Err(err) => {
+                    error!("Failed to fetch bond reports: {err:?}");
+                    continue;
+                }
+            };
+            if last_bond_reports != reports {
+                for report in &reports {
+                    info!("{report}");
+                }
+            }
+            // Exit once no bond remains to be claimed from any treasury
+            let mut exited = true;
+            for report in &reports {
+                match &report.status {
+                    BondStatus::Claimable => {
+                        match await_tel_res!(
+                            context,
+                            tracer,
+                            "claim_proposer_bond",
+                            claim_proposer_bond(
+                                report.treasury,
+                                &proposer_provider,
+                                &args.txn_args
+                            )
+                        ) {
+                            Ok(receipt) => {
+                                info!("Proposer bond claimed: {:?}", receipt.transaction_hash);
+                            }
+                            Err(e) if is_dry_run(&e) => exited = false,
+                            Err(e) => {
+                                error!("Failed to claim proposer bond: {e:?}");
+                                exited = false;
+                            }
+                        }
+                    }
+                    BondStatus::Locked { .. } => exited = false,
+                    BondStatus::Empty | BondStatus::Forfeited { .. } => {}
+                }
+            }
+            if exited {
+                if let Some(lease) = leader_lease.as_mut() {
+                    lease.release();
+                }
+                return Ok(());
+            }
+            last_bond_reports = reports;
+            continue;
+        }
+
         // Check if deployment is still valid
-        let dispute_game_factory =
-            IDisputeGameFactory::new(agent.deployment.factory, &agent.provider.l1_provider);
-        let latest_game_impl_addr = dispute_game_factory
-            .gameImpls(KAILUA_GAME_TYPE)
-            .stall_with_context(context.clone(), "DisputeGameFactory::gameImpls")
-            .await;
-        if latest_game_impl_addr != agent.deployment.game {
-            warn!("Not proposing. Deployment {} outdated. Found new deployment {latest_game_impl_addr}.", agent.deployment.game);
+        if agent.latest_game_impl != agent.deployment.game {
+            if agent.follow_upgrades {
+                info!(
+                    "Not proposing. Awaiting handover from deployment {} to {}.",
+                    agent.deployment.game, agent.latest_game_impl
+                );
+            } else {
+                warn!(
+                    "Not proposing. Deployment {} outdated. Found new deployment {}.",
+                    agent.deployment.game, agent.latest_game_impl
+                );
+            }
             continue;
         }
 
@@ -204,12 +359,47 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
         let proposed_block_number =
             canonical_tip.output_block_number + agent.deployment.blocks_per_proposal();
 
-        let chain_time = await_tel!(
+        // Wait for the output to be derived from final L1 blocks under the configured policy
+        let final_l2_block = match await_tel!(context, args.finality.final_l2_block(&agent)) {
+            Ok(final_l2_block) => final_l2_block,
+            Err(err) => {
+                error!("Failed to fetch final l2 block: {err:?}");
+                continue;
+            }
+        };
+        if let Some(final_l2_block) = final_l2_block {
+            let finality = L1Finality {
+                block_number: proposed_block_number,
+                final_l2_block,
+            };
+            meter_finality_lag.record(
+                finality.lag(),
+                &[
+                    KeyValue::new("policy", args.finality.policy()),
+                    KeyValue::new("l2_block", proposed_block_number.to_string()),
+                ],
+            );
+            if !finality.is_final() {
+                info!(
+                    "Waiting for l2 block {} to be derived from final l1 blocks before proposing ({} more blocks needed).",
+                    finality.block_number,
+                    finality.lag()
+                );
+                continue;
+            }
+        }
+
+        let l1_head = match await_tel!(
             context,
             get_block(&agent.provider.l1_provider, BlockNumberOrTag::Latest)
-        )
-        .header()
-        .timestamp();
+        ) {
+            Ok(l1_head) => l1_head,
+            Err(err) => {
+                error!("Failed to fetch l1 head: {err:?}");
+                continue;
+            }
+        };
+        let chain_time = l1_head.header().timestamp();
 
         let min_proposal_time = agent.deployment.min_proposal_time(proposed_block_number);
         if chain_time < min_proposal_time {
@@ -219,11 +409,22 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
         }
 
         // Wait for vanguard to make submission
-        let vanguard = await_tel!(context, fetch_vanguard(&agent));
+        let vanguard = match await_tel!(context, fetch_vanguard(&agent)) {
+            Ok(vanguard) => vanguard,
+            Err(err) => {
+                error!("Failed to fetch vanguard: {err:?}");
+                continue;
+            }
+        };
         let vanguard_advantage_timeout =
             if canonical_tip.requires_vanguard_advantage(proposer_address, vanguard) {
-                let vanguard_advantage = await_tel!(context, fetch_vanguard_advantage(&agent));
-                min_proposal_time + vanguard_advantage
+                match await_tel!(context, fetch_vanguard_advantage(&agent)) {
+                    Ok(vanguard_advantage) => min_proposal_time + vanguard_advantage,
+                    Err(err) => {
+                        error!("Failed to fetch vanguard advantage: {err:?}");
+                        continue;
+                    }
+                }
             } else {
                 min_proposal_time
             };
@@ -233,6 +434,46 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
             continue;
         }
 
+        // Wait for cheaper blob fees while the proposal can still be made before the next is due
+        if blob_fee_scheduler.is_enabled() && agent.deployment.proposal_blobs > 0 {
+            let blob_fee = match await_tel!(
+                context,
+                blob_fee_scheduler.sample(&agent.provider.l1_provider, l1_head.header().number())
+            ) {
+                Ok(blob_fee) => blob_fee,
+                Err(err) => {
+                    error!("Failed to sample blob base fees: {err:?}");
+                    continue;
+                }
+            };
+            meter_blob_fee_base.record(
+                u64::try_from(blob_fee).unwrap_or(u64::MAX),
+                &[KeyValue::new(
+                    "target",
+                    blob_fee_scheduler
+                        .target_fee()
+                        .unwrap_or_default()
+                        .to_string(),
+                )],
+            );
+            let next_proposal_time = min_proposal_time
+                + agent.deployment.blocks_per_proposal() * agent.deployment.block_time;
+            let delay = blob_fee_scheduler.delay(
+                proposed_block_number,
+                chain_time,
+                vanguard_advantage_timeout,
+                next_proposal_time,
+                blob_fee,
+            );
+            if delay > 0 {
+                info!(
+                    "Waiting for at most {delay} more seconds of chain time for blob base fee {blob_fee} to drop below {} wei.",
+                    blob_fee_scheduler.target_fee().unwrap_or_default()
+                );
+                continue;
+            }
+        }
+
         // Prepare proposal
         let Some(proposed_output_root) = agent.outputs.get(&proposed_block_number).copied() else {
             error!("Could not fetch output claim.");
@@ -262,84 +503,123 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
         info!("Candidate proposal prepared");
 
         // Calculate required duplication counter
-        let mut dupe_counter = 0u64;
-        let unique_extra_data = loop {
-            // compute extra data with block number, parent factory index, and blob hash
-            let extra_data = [
-                proposed_block_number.abi_encode_packed(),
-                canonical_tip.index.abi_encode_packed(),
-                dupe_counter.abi_encode_packed(),
-            ]
-            .concat();
-            // check if proposal exists
-            let dupe_game_address = dispute_game_factory
-                .games(
-                    KAILUA_GAME_TYPE,
-                    proposed_output_root,
-                    Bytes::from(extra_data.clone()),
-                )
-                .stall_with_context(context.clone(), "DisputeGameFactory::games")
-                .await
-                .proxy_;
-            if dupe_game_address.is_zero() {
-                // proposal was not made before using this dupe counter
-                info!("Dupe counter {dupe_counter} available.");
-                break Some(extra_data);
-            }
-            // fetch proposal from local data
-            let dupe_game_index: u64 =
-                KailuaTournament::new(dupe_game_address, &agent.provider.l1_provider)
-                    .gameIndex()
-                    .stall_with_context(context.clone(), "KailuaTournament::gameIndex")
-                    .await
-                    .to();
-            if dupe_game_index >= agent.cursor.next_factory_index {
-                // we need to fetch this proposal's data
-                warn!("Duplicate proposal data not yet available.");
-                break None;
+        let unique_extra_data = match await_tel!(
+            context,
+            fetch_unique_extra_data(
+                &agent,
+                canonical_tip.index,
+                proposed_block_number,
+                proposed_output_root
+            )
+        ) {
+            Ok(unique_extra_data) => unique_extra_data,
+            Err(err) => {
+                error!("Failed to fetch unique extra data: {err:?}");
+                continue;
             }
-            if let Some(dupe_proposal) = agent.proposals.get(&dupe_game_index) {
-                // check if proposal was made incorrectly or by an already eliminated player
-                if dupe_proposal.is_correct().unwrap_or_default()
-                    && !agent.was_proposer_eliminated_before(dupe_proposal)
-                {
-                    info!("Correct proposal was already made honestly.");
-                    break None;
-                }
-            };
-            // this invalid proposal will not participate in the tournament
-            warn!("Incrementing duplication counter");
-            // increment counter
-            dupe_counter += 1;
         };
-
-        let Some(extra_data) = unique_extra_data else {
+        let Some((dupe_counter, extra_data)) = unique_extra_data else {
             // this proposal was already correctly made or we need more data
             warn!("Skipping proposal attempt.");
             continue;
         };
 
         // Check collateral requirements
-        let bond_value = await_tel!(context, fetch_participation_bond(&agent));
-        let paid_in = await_tel!(context, fetch_paid_bond(&agent, proposer_address));
-        let balance = await_tel!(
+        let bond_value = match await_tel!(context, fetch_participation_bond(&agent)) {
+            Ok(bond_value) => bond_value,
+            Err(err) => {
+                error!("Failed to fetch participation bond: {err:?}");
+                continue;
+            }
+        };
+        let paid_in = match await_tel!(context, fetch_paid_bond(&agent, proposer_address)) {
+            Ok(paid_in) => paid_in,
+            Err(err) => {
+                error!("Failed to fetch paid bond: {err:?}");
+                continue;
+            }
+        };
+        let balance = match await_tel!(
             context,
             tracer,
             "get_balance",
             retry_res_ctx_timeout!(
-                agent
+                EthRpc => agent
                     .provider
                     .l1_provider
                     .get_balance(proposer_address)
                     .await
             )
-        );
+        ) {
+            Ok(balance) => balance,
+            Err(err) => {
+                error!("Failed to fetch proposer balance: {err:?}");
+                continue;
+            }
+        };
         let owed_collateral = bond_value.saturating_sub(paid_in);
         if balance < owed_collateral {
             error!("INSUFFICIENT BALANCE! Need to lock in at least {owed_collateral} more.");
             continue;
         }
 
+        // Renew the lease in case resolution or proof fetching took long
+        if let Some(lease) = leader_lease.as_mut() {
+            if !lease.refresh() {
+                warn!("Not proposing without holding the proposer lease.");
+                continue;
+            }
+        }
+
+        // Prepare successive proposals to catch up with the safe head
+        let mut pipelined_proposals = vec![];
+        let mut first_factory_index = 0;
+        if args.catch_up_depth > 1 && !dry_run().is_enabled() {
+            let dispute_game_factory =
+                IDisputeGameFactory::new(agent.deployment.factory, &agent.provider.l1_provider);
+            first_factory_index = match dispute_game_factory
+                .gameCount()
+                .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
+                .await
+            {
+                Ok(game_count) => game_count.to(),
+                Err(err) => {
+                    error!("Failed to fetch game count: {err:?}");
+                    continue;
+                }
+            };
+            let vanguard_advantage = if vanguard.is_zero() || vanguard == proposer_address {
+                0
+            } else {
+                match await_tel!(context, fetch_vanguard_advantage(&agent)) {
+                    Ok(vanguard_advantage) => vanguard_advantage,
+                    Err(err) => {
+                        error!("Failed to fetch vanguard advantage: {err:?}");
+                        continue;
+                    }
+                }
+            };
+            pipelined_proposals = prepare_pipelined_proposals(
+                &agent,
+                proposed_block_number,
+                first_factory_index,
+                args.catch_up_depth,
+                chain_time,
+                vanguard_advantage,
+                args.sync.final_l2_block,
+            );
+            // Only pipeline successors that satisfy the finality policy as well
+            if let Some(final_l2_block) = final_l2_block {
+                pipelined_proposals.retain(|proposal| proposal.block_number <= final_l2_block);
+            }
+        }
+
+        // Never publish proposals without holding the lease
+        if let Err(err) = lease_fence.as_ref().map_or(Ok(()), LeaseFence::check) {
+            warn!("{err}");
+            continue;
+        }
+
         // Submit proposal
         info!("Proposing output {proposed_output_root} at l2 block number {proposed_block_number} with {owed_collateral} additional collateral and duplication counter {dupe_counter}.");
 
@@ -353,67 +633,194 @@ pub async fn propose(args: ProposeArgs, data_dir: PathBuf) -> anyhow::Result<()>
         if !sidecar.blobs.is_empty() {
             transaction = transaction.sidecar(sidecar);
         }
-        match transaction
-            .timed_transact_with_context(
-                context.clone(),
-                "KailuaTreasury::propose",
-                Some(Duration::from_secs(args.txn_args.txn_timeout)),
-            )
-            .await
-            .context("KailuaTreasury::propose")
-        {
-            Ok(receipt) => {
-                info!("Proposal submitted: {:?}", receipt.transaction_hash);
-                info!("KailuaTreasury::propose: {} gas", receipt.gas_used);
-                meter_propose_num.add(
-                    1,
-                    &[
-                        KeyValue::new("l2_height", proposed_block_number.to_string()),
-                        KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
-                        KeyValue::new("txn_from", receipt.from.to_string()),
-                        KeyValue::new("txn_to", receipt.to.unwrap_or_default().to_string()),
-                        KeyValue::new("txn_gas_used", receipt.gas_used.to_string()),
-                        KeyValue::new("txn_gas_price", receipt.effective_gas_price.to_string()),
-                        KeyValue::new(
-                            "txn_blob_gas_used",
-                            receipt.blob_gas_used.unwrap_or_default().to_string(),
-                        ),
-                        KeyValue::new(
-                            "txn_blob_gas_price",
-                            receipt.blob_gas_price.unwrap_or_default().to_string(),
-                        ),
-                    ],
-                );
-                meter_propose_last.record(
-                    proposed_block_number,
-                    &[
-                        KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
-                        KeyValue::new("txn_from", receipt.from.to_string()),
-                        KeyValue::new("txn_to", receipt.to.unwrap_or_default().to_string()),
-                        KeyValue::new("txn_gas_used", receipt.gas_used.to_string()),
-                        KeyValue::new("txn_gas_price", receipt.effective_gas_price.to_string()),
-                        KeyValue::new(
-                            "txn_blob_gas_used",
-                            receipt.blob_gas_used.unwrap_or_default().to_string(),
-                        ),
-                        KeyValue::new(
-                            "txn_blob_gas_price",
-                            receipt.blob_gas_price.unwrap_or_default().to_string(),
-                        ),
-                    ],
-                );
+        // Successors cannot be simulated before their parents exist, so reuse the first estimate
+        // until they do
+        let gas_limit = if pipelined_proposals.is_empty() {
+            None
+        } else {
+            match transaction.estimate_gas().await {
+                Ok(gas) => Some(gas + gas / 2),
+                Err(err) => {
+                    warn!("Not pipelining proposals after failing to estimate gas: {err:?}");
+                    None
+                }
             }
-            Err(e) => {
-                // Skip resolve transactions on next iteration
-                prioritize_proposing = true;
-                error!("Failed to confirm proposal txn: {e:?}");
-                meter_propose_fail.add(
-                    1,
-                    &[
-                        KeyValue::new("l2_height", proposed_block_number.to_string()),
-                        KeyValue::new("msg", e.to_string()),
-                    ],
-                );
+        };
+        if gas_limit.is_none() {
+            pipelined_proposals.clear();
+        }
+        // Keep the lease while waiting for confirmations so that no standby takes over meanwhile
+        let transactions = async {
+            match gas_limit {
+                None => {
+                    vec![
+                        transaction
+                            .timed_transact_with_context(
+                                context.clone(),
+                                "KailuaTreasury::propose",
+                                Some(Duration::from_secs(args.txn_args.txn_timeout)),
+                            )
+                            .await,
+                    ]
+                }
+                Some(gas_limit) => {
+                    let mut transactions = vec![transaction.gas(gas_limit)];
+                    for proposal in &pipelined_proposals {
+                        info!(
+                            "Pipelining proposal of output {} at l2 block number {}.",
+                            proposal.output_root, proposal.block_number
+                        );
+                        let mut transaction = treasury_contract_instance
+                            .propose(
+                                proposal.output_root,
+                                Bytes::from(proposal.extra_data.clone()),
+                            )
+                            .gas(gas_limit);
+                        if !proposal.sidecar.blobs.is_empty() {
+                            transaction = transaction.sidecar(proposal.sidecar.clone());
+                        }
+                        transactions.push(transaction);
+                    }
+                    let dispute_game_factory = IDisputeGameFactory::new(
+                        agent.deployment.factory,
+                        &agent.provider.l1_provider,
+                    );
+                    let block_numbers = std::iter::once(proposed_block_number)
+                        .chain(pipelined_proposals.iter().map(|p| p.block_number))
+                        .collect::<Vec<_>>();
+                    // Stop pipelining once another proposal takes an index assumed for a parent
+                    let verify = |i: usize| {
+                        let (dispute_game_factory, block_numbers) =
+                            (&dispute_game_factory, &block_numbers);
+                        let proposal = &pipelined_proposals[i - 1];
+                        let treasury_contract_instance = &treasury_contract_instance;
+                        async move {
+                            let parent_on_chain = verify_pipelined_parent(
+                                dispute_game_factory,
+                                proposer_address,
+                                first_factory_index,
+                                block_numbers,
+                                i as u64,
+                            )
+                            .await?;
+                            if !parent_on_chain {
+                                return Ok(None);
+                            }
+                            let mut transaction = treasury_contract_instance.propose(
+                                proposal.output_root,
+                                Bytes::from(proposal.extra_data.clone()),
+                            );
+                            if !proposal.sidecar.blobs.is_empty() {
+                                transaction = transaction.sidecar(proposal.sidecar.clone());
+                            }
+                            let gas = transaction
+                                .estimate_gas()
+                                .await
+                                .context("KailuaTreasury::propose estimate_gas")?;
+                            Ok(Some(gas + gas / 2))
+                        }
+                    };
+                    transact_pipelined(
+                        transactions,
+                        proposer_address,
+                        lease_fence.as_ref(),
+                        verify,
+                        context.clone(),
+                        "KailuaTreasury::propose",
+                        Some(Duration::from_secs(args.txn_args.txn_timeout)),
+                    )
+                    .await
+                }
+            }
+        };
+        let results = match renew_while(leader_lease.as_mut(), transactions).await {
+            Ok(results) => results,
+            Err(err) => {
+                error!("Abandoned proposal txn: {err:?}");
+                continue;
+            }
+        };
+        let proposed_block_numbers = std::iter::once(proposed_block_number)
+            .chain(pipelined_proposals.iter().map(|p| p.block_number));
+        for (proposed_block_number, result) in proposed_block_numbers.zip(results) {
+            match result.context("KailuaTreasury::propose") {
+                Ok(receipt) => {
+                    info!("Proposal submitted: {:?}", receipt.transaction_hash);
+                    info!("KailuaTreasury::propose: {} gas", receipt.gas_used);
+                    if let Some(saved) = blob_fee_scheduler.settle(
+                        proposed_block_number,
+                        receipt.blob_gas_used.unwrap_or_default(),
+                        receipt.blob_gas_price.unwrap_or_default(),
+                    ) {
+                        info!(
+                            "Blob fee scheduling saved {saved} wei on this proposal ({} wei in total).",
+                            blob_fee_scheduler.total_saved
+                        );
+                        let attributes = [
+                            KeyValue::new("l2_height", proposed_block_number.to_string()),
+                            KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
+                        ];
+                        let amount = u64::try_from(saved.unsigned_abs()).unwrap_or(u64::MAX);
+                        if saved >= 0 {
+                            meter_blob_fee_saved.add(amount, &attributes);
+                        } else {
+                            meter_blob_fee_overpaid.add(amount, &attributes);
+                        }
+                    }
+                    meter_propose_num.add(
+                        1,
+                        &[
+                            KeyValue::new("l2_height", proposed_block_number.to_string()),
+                            KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
+                            KeyValue::new("txn_from", receipt.from.to_string()),
+                            KeyValue::new("txn_to", receipt.to.unwrap_or_default().to_string()),
+                            KeyValue::new("txn_gas_used", receipt.gas_used.to_string()),
+                            KeyValue::new("txn_gas_price", receipt.effective_gas_price.to_string()),
+                            KeyValue::new(
+                                "txn_blob_gas_used",
+                                receipt.blob_gas_used.unwrap_or_default().to_string(),
+                            ),
+                            KeyValue::new(
+                                "txn_blob_gas_price",
+                                receipt.blob_gas_price.unwrap_or_default().to_string(),
+                            ),
+                        ],
+                    );
+                    meter_propose_last.record(
+                        proposed_block_number,
+                        &[
+                            KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
+                            KeyValue::new("txn_from", receipt.from.to_string()),
+                            KeyValue::new("txn_to", receipt.to.unwrap_or_default().to_string()),
+                            KeyValue::new("txn_gas_used", receipt.gas_used.to_string()),
+                            KeyValue::new("txn_gas_price", receipt.effective_gas_price.to_string()),
+                            KeyValue::new(
+                                "txn_blob_gas_used",
+                                receipt.blob_gas_used.unwrap_or_default().to_string(),
+                            ),
+                            KeyValue::new(
+                                "txn_blob_gas_price",
+                                receipt.blob_gas_price.unwrap_or_default().to_string(),
+                            ),
+                        ],
+                    );
+                }
+                Err(e) if is_dry_run(&e) => {
+                    // Skip resolve transactions on next iteration
+                    prioritize_proposing = true;
+                }
+                Err(e) => {
+                    // Skip resolve transactions on next iteration
+                    prioritize_proposing = true;
+                    error!("Failed to confirm proposal txn: {e:?}");
+                    meter_propose_fail.add(
+                        1,
+                        &[
+                            KeyValue::new("l2_height", proposed_block_number.to_string()),
+                            KeyValue::new("msg", e.to_string()),
+                        ],
+                    );
+                }
             }
         }
     }
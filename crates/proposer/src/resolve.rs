@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::fetch::fetch_current_challenger_duration;
+use crate::lease::LeaseFence;
 use alloy::network::{Network, ReceiptResponse};
 use alloy::primitives::{Address, U256};
 use alloy::providers::Provider;
@@ -21,6 +22,7 @@ use kailua_contracts::*;
 use kailua_sync::agent::SyncAgent;
 use kailua_sync::proposal::{Proposal, ELIMINATIONS_LIMIT};
 use kailua_sync::stall::Stall;
+use kailua_sync::transact::policy::Urgency;
 use kailua_sync::transact::{Transact, TransactArgs};
 use kailua_sync::{await_tel, await_tel_res};
 use opentelemetry::global::tracer;
@@ -31,30 +33,238 @@ use std::future::IntoFuture;
 use std::time::Duration;
 use tracing::{debug, error, info};
 
+/// Conservative estimate of the gas used by a prune transaction eliminating up to
+/// [ELIMINATIONS_LIMIT] contenders
+pub const PRUNE_GAS_ESTIMATE: u64 = 5_000_000;
+
+/// A proposal that can be resolved once the proposals before it in the plan are
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResolutionStep {
+    /// Index of the resolved (or earlier planned) parent proposal
+    pub parent: u64,
+    /// Index of the proposal to resolve
+    pub proposal: u64,
+    /// Estimated number of prune transactions to send before resolution
+    pub prunes: u64,
+}
+
+/// Returns the number of unresolved proposals on the canonical chain.
+pub fn resolution_backlog(agent: &SyncAgent) -> u64 {
+    let mut backlog = 0;
+    let mut next = agent
+        .proposals
+        .get(&agent.cursor.last_resolved_game)
+        .and_then(|p| p.successor);
+    while let Some(proposal) = next.and_then(|index| agent.proposals.get(&index)) {
+        backlog += 1;
+        next = proposal.successor;
+    }
+    backlog
+}
+
+/// Estimates the number of prune transactions needed before the successor of the given
+/// proposal can be resolved.
+pub async fn fetch_prune_estimate(agent: &SyncAgent, parent: &Proposal) -> anyhow::Result<u64> {
+    let tracer = tracer("kailua");
+    let context = opentelemetry::Context::current_with_span(tracer.start("fetch_prune_estimate"));
+
+    let contract_instance = parent.tournament_contract_instance(&agent.provider.l1_provider);
+    let child_count: u64 = contract_instance
+        .childCount()
+        .stall_with_context(context.clone(), "KailuaTournament::childCount")
+        .await?
+        .to();
+    let opponent_index = contract_instance
+        .opponentIndex()
+        .stall_with_context(context.clone(), "KailuaTournament::opponentIndex")
+        .await?;
+    // The final pruning step is part of the resolution
+    Ok(child_count
+        .saturating_sub(opponent_index)
+        .div_ceil(ELIMINATIONS_LIMIT)
+        .saturating_sub(1))
+}
+
+/// Plans the resolution of up to `limit` successive canonical proposals whose challenge period
+/// has elapsed or whose validity was proven.
+///
+/// Proposals can only be resolved after their parents, so the plan follows the canonical chain
+/// and ends at the first proposal that cannot be resolved yet.
+pub async fn plan_resolutions(
+    agent: &SyncAgent,
+    limit: u64,
+) -> anyhow::Result<Vec<ResolutionStep>> {
+    let tracer = tracer("kailua");
+    let context = opentelemetry::Context::current_with_span(tracer.start("plan_resolutions"));
+
+    let mut plan = vec![];
+    let mut parent_index = agent.cursor.last_resolved_game;
+    while (plan.len() as u64) < limit {
+        let Some(parent) = agent.proposals.get(&parent_index) else {
+            bail!("Resolved proposal {parent_index} missing from database.");
+        };
+        let Some(successor_index) = parent.successor else {
+            break;
+        };
+        let Some(successor) = agent.proposals.get(&successor_index) else {
+            bail!("Unresolved successor {successor_index} missing from database.");
+        };
+        // Skip over proposals resolved by others
+        if await_tel!(
+            context,
+            successor.fetch_finality(&agent.provider.l1_provider)
+        )
+        .context("Proposal::fetch_finality")?
+        .unwrap_or_default()
+        {
+            parent_index = successor_index;
+            continue;
+        }
+        // Check for timeout and fast-forward status
+        let challenger_duration =
+            await_tel!(context, fetch_current_challenger_duration(agent, successor))
+                .context("fetch_current_challenger_duration")?;
+        let is_validity_proven = await_tel!(
+            context,
+            parent.fetch_is_successor_validity_proven(&agent.provider.l1_provider)
+        )
+        .context("Proposal::fetch_is_successor_validity_proven")?;
+        if !is_validity_proven && challenger_duration > 0 {
+            info!("Waiting for {challenger_duration} more seconds of chain time before resolution of proposal {successor_index}.");
+            break;
+        }
+        let prunes = if successor.has_parent() {
+            await_tel!(context, fetch_prune_estimate(agent, parent))
+                .context("fetch_prune_estimate")?
+        } else {
+            0
+        };
+        plan.push(ResolutionStep {
+            parent: parent_index,
+            proposal: successor_index,
+            prunes,
+        });
+        parent_index = successor_index;
+    }
+    Ok(plan)
+}
+
+/// Truncates the plan before the first step whose estimated prune gas, together with that of the
+/// steps before it, would exceed the budget.
+///
+/// The first step is always kept, so that resolution makes progress under any budget.
+pub fn budget_plan(plan: &mut Vec<ResolutionStep>, gas_budget: Option<u64>) {
+    let Some(gas_budget) = gas_budget else {
+        return;
+    };
+    let mut prune_gas = 0u64;
+    let affordable = plan
+        .iter()
+        .take_while(|step| {
+            prune_gas = prune_gas.saturating_add(step.prunes.saturating_mul(PRUNE_GAS_ESTIMATE));
+            prune_gas <= gas_budget
+        })
+        .count();
+    plan.truncate(affordable.max(1));
+}
+
+/// Resolves the proposals that are ready for resolution, stopping once `limit` proposals were
+/// resolved, the gas spent exceeds the budget or the lease is lost.
 #[allow(clippy::too_many_arguments)]
-pub async fn resolve_next_pending_proposal<P: Provider>(
+pub async fn resolve_pending_proposals<P: Provider>(
     agent: &SyncAgent,
     txn_args: &TransactArgs,
     proposer_provider: P,
+    fence: Option<&LeaseFence>,
+    limit: u64,
+    gas_budget: Option<u64>,
     meter_prune_num: &Counter<u64>,
     meter_prune_fail: &Counter<u64>,
     meter_resolve_num: &Counter<u64>,
     meter_resolve_fail: &Counter<u64>,
     meter_resolve_last: &Gauge<u64>,
-) -> anyhow::Result<bool> {
+    meter_resolve_backlog: &Gauge<u64>,
+) -> anyhow::Result<u64> {
     let tracer = tracer("kailua");
     let context =
-        opentelemetry::Context::current_with_span(tracer.start("resolve_next_pending_proposal"));
+        opentelemetry::Context::current_with_span(tracer.start("resolve_pending_proposals"));
 
-    let Some(resolved_parent) = agent.proposals.get(&agent.cursor.last_resolved_game) else {
-        bail!(
-            "Last resolved proposal {} missing from database.",
-            agent.cursor.last_resolved_game
-        );
+    let backlog = resolution_backlog(agent);
+    let mut plan = await_tel_res!(
+        context,
+        tracer,
+        "plan_resolutions",
+        plan_resolutions(agent, limit)
+    )?;
+    meter_resolve_backlog.record(backlog, &[KeyValue::new("state", "unresolved")]);
+    meter_resolve_backlog.record(plan.len() as u64, &[KeyValue::new("state", "ready")]);
+    if plan.is_empty() {
+        return Ok(0);
+    }
+    // Leave proposals that need more pruning than the budget allows to later iterations
+    let ready = plan.len();
+    budget_plan(&mut plan, gas_budget);
+    info!(
+        "Planned resolution of {} out of {backlog} unresolved proposals ({ready} ready) with {} prune transactions.",
+        plan.len(),
+        plan.iter().map(|step| step.prunes).sum::<u64>()
+    );
+
+    let mut resolved = 0;
+    let mut gas_spent = 0;
+    for step in plan {
+        // Stop once the budget is spent, as later resolutions depend on earlier ones
+        if gas_budget.is_some_and(|budget| gas_spent >= budget) {
+            info!("Resolution gas budget exhausted after spending {gas_spent} gas.");
+            break;
+        }
+        fence.map_or(Ok(()), LeaseFence::check)?;
+        let Some(gas_used) = resolve_pending_proposal(
+            agent,
+            step.parent,
+            txn_args,
+            &proposer_provider,
+            meter_prune_num,
+            meter_prune_fail,
+            meter_resolve_num,
+            meter_resolve_fail,
+            meter_resolve_last,
+        )
+        .await?
+        else {
+            // Later proposals cannot be resolved before this one
+            break;
+        };
+        resolved += 1;
+        gas_spent += gas_used;
+        meter_resolve_backlog.record(backlog - resolved, &[KeyValue::new("state", "unresolved")]);
+    }
+    Ok(resolved)
+}
+
+/// Resolves the successor of the given resolved proposal, returning the gas spent if successful.
+#[allow(clippy::too_many_arguments)]
+pub async fn resolve_pending_proposal<P: Provider>(
+    agent: &SyncAgent,
+    resolved_parent_index: u64,
+    txn_args: &TransactArgs,
+    proposer_provider: P,
+    meter_prune_num: &Counter<u64>,
+    meter_prune_fail: &Counter<u64>,
+    meter_resolve_num: &Counter<u64>,
+    meter_resolve_fail: &Counter<u64>,
+    meter_resolve_last: &Gauge<u64>,
+) -> anyhow::Result<Option<u64>> {
+    let tracer = tracer("kailua");
+    let context =
+        opentelemetry::Context::current_with_span(tracer.start("resolve_pending_proposal"));
+
+    let Some(resolved_parent) = agent.proposals.get(&resolved_parent_index) else {
+        bail!("Resolved proposal {resolved_parent_index} missing from database.");
     };
 
     let Some(unresolved_successor_index) = resolved_parent.successor else {
-        return Ok(false);
+        return Ok(None);
     };
 
     let Some(unresolved_successor) = agent.proposals.get(&unresolved_successor_index) else {
@@ -64,32 +274,8 @@ pub async fn resolve_next_pending_proposal<P: Provider>(
     let resolved_parent_contract =
         resolved_parent.tournament_contract_instance(&agent.provider.l1_provider);
 
-    // Skip resolved games
-    if await_tel!(
-        context,
-        unresolved_successor.fetch_finality(&agent.provider.l1_provider)
-    )
-    .context("Proposal::fetch_finality")?
-    .unwrap_or_default()
-    {
-        return Ok(false);
-    }
-
-    // Check for timeout and fast-forward status
-    let challenger_duration = await_tel!(
-        context,
-        fetch_current_challenger_duration(agent, unresolved_successor)
-    );
-    let is_validity_proven = await_tel!(
-        context,
-        resolved_parent.fetch_is_successor_validity_proven(&agent.provider.l1_provider)
-    );
-    if !is_validity_proven && challenger_duration > 0 {
-        info!("Waiting for {challenger_duration} more seconds of chain time before resolution of proposal {unresolved_successor_index}.");
-        return Ok(false);
-    }
-
     // Check if can prune next set of children in parent tournament
+    let mut gas_used = 0;
     if unresolved_successor.has_parent() {
         let can_resolve = loop {
             let result = await_tel_res!(
@@ -118,16 +304,18 @@ pub async fn resolve_next_pending_proposal<P: Provider>(
             info!("Eliminating {ELIMINATIONS_LIMIT} opponents before resolution.");
             match resolved_parent_contract
                 .pruneChildren(U256::from(ELIMINATIONS_LIMIT))
-                .timed_transact_with_context(
+                .timed_transact_with_urgency(
                     context.clone(),
                     "KailuaTournament::pruneChildren",
                     Some(Duration::from_secs(txn_args.txn_timeout)),
+                    Urgency::Deferrable,
                 )
                 .await
                 .context("KailuaTournament::pruneChildren transact")
             {
                 Ok(receipt) => {
                     info!("KailuaTournament::pruneChildren: {} gas", receipt.gas_used);
+                    gas_used += receipt.gas_used;
                     meter_prune_num.add(
                         1,
                         &[
@@ -170,7 +358,7 @@ pub async fn resolve_next_pending_proposal<P: Provider>(
         // Some disputes are still unresolved
         if !can_resolve {
             info!("Waiting for more proofs to resolve proposal.");
-            return Ok(false);
+            return Ok(None);
         }
     }
 
@@ -186,7 +374,7 @@ pub async fn resolve_next_pending_proposal<P: Provider>(
             "Failed to determine proposal at {} as successor of proposal at {}.",
             unresolved_successor.contract, resolved_parent.contract
         );
-        return Ok(false);
+        return Ok(None);
     }
 
     // resolve
@@ -234,7 +422,7 @@ pub async fn resolve_next_pending_proposal<P: Provider>(
                     ),
                 ],
             );
-            Ok(true)
+            Ok(Some(gas_used + receipt.gas_used))
         }
         Err(err) => {
             error!("KailuaTournament::resolve: {err:?}");
@@ -249,7 +437,7 @@ pub async fn resolve_next_pending_proposal<P: Provider>(
                     KeyValue::new("msg", err.to_string()),
                 ],
             );
-            Ok(false)
+            Ok(None)
         }
     }
 }
@@ -266,7 +454,7 @@ pub async fn resolve_proposal<P: Provider<N>, N: Network>(
     let parent_tournament: Address = contract_instance
         .parentGame()
         .stall_with_context(context.clone(), "KailuaTournament::parentGame")
-        .await;
+        .await?;
     let parent_tournament_instance = KailuaTournament::new(parent_tournament, &provider);
 
     // Issue any necessary pre-emptive pruning calls
@@ -291,10 +479,11 @@ pub async fn resolve_proposal<P: Provider<N>, N: Network>(
         info!("Eliminating {ELIMINATIONS_LIMIT} opponents before resolution.");
         let receipt = parent_tournament_instance
             .pruneChildren(U256::from(ELIMINATIONS_LIMIT))
-            .timed_transact_with_context(
+            .timed_transact_with_urgency(
                 context.clone(),
                 "KailuaTournament::pruneChildren",
                 Some(Duration::from_secs(txn_args.txn_timeout)),
+                Urgency::Deferrable,
             )
             .await
             .context("KailuaTournament::pruneChildren")?;
@@ -307,10 +496,11 @@ pub async fn resolve_proposal<P: Provider<N>, N: Network>(
     // Issue resolution call
     let receipt = contract_instance
         .resolve()
-        .timed_transact_with_context(
+        .timed_transact_with_urgency(
             context.clone(),
             "KailuaTournament::resolve",
             Some(Duration::from_secs(txn_args.txn_timeout)),
+            Urgency::Deferrable,
         )
         .await
         .context("KailuaTournament::resolve")?;
@@ -318,3 +508,103 @@ pub async fn resolve_proposal<P: Provider<N>, N: Network>(
 
     Ok(receipt)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+    use kailua_sync::provider::memory::fixtures::*;
+
+    const SUCCESSOR_GAME: Address = address!("0x0000000000000000000000000000000000000013");
+
+    fn step(proposal: u64, prunes: u64) -> ResolutionStep {
+        ResolutionStep {
+            parent: proposal - 1,
+            proposal,
+            prunes,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_resolutions() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let first = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain.script_proposal(&deployment, &first).unwrap();
+        let second = child_proposal(
+            &chain,
+            &deployment,
+            &first,
+            SUCCESSOR_GAME,
+            2,
+            HONEST_PROPOSER,
+        );
+        chain.script_proposal(&deployment, &second).unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        for _ in 0..4 {
+            agent.sync(0, None).await.unwrap();
+        }
+        assert_eq!(resolution_backlog(&agent), 2);
+
+        // the anchor has no competing children, while the first proposal has 300
+        for (contract, child_count) in [(ANCHOR, 1u64), (HONEST_GAME, 300)] {
+            chain.mock_call(
+                contract,
+                KailuaTournament::childCountCall {},
+                U256::from(child_count),
+            );
+            chain.mock_call(contract, KailuaTournament::opponentIndexCall {}, 0u64);
+        }
+        let script_challenger_duration = |contract, duration: u64| {
+            let (_, _, chain_time) = chain.l1_head();
+            chain.mock_call(
+                contract,
+                KailuaTournament::getChallengerDurationCall::new((U256::from(chain_time),)),
+                duration,
+            );
+        };
+
+        // the plan ends at the first proposal that cannot be resolved yet
+        script_challenger_duration(HONEST_GAME, 0);
+        script_challenger_duration(SUCCESSOR_GAME, 100);
+        assert_eq!(
+            plan_resolutions(&agent, 16).await.unwrap(),
+            vec![step(1, 0)]
+        );
+        // proposals are planned in order and their pruning is estimated
+        script_challenger_duration(SUCCESSOR_GAME, 0);
+        assert_eq!(
+            plan_resolutions(&agent, 16).await.unwrap(),
+            vec![step(1, 0), step(2, 2)]
+        );
+        assert_eq!(plan_resolutions(&agent, 1).await.unwrap(), vec![step(1, 0)]);
+        // proposals resolved by others are skipped
+        chain.resolve_proposal(&deployment, HONEST_GAME, true);
+        script_challenger_duration(SUCCESSOR_GAME, 0);
+        assert_eq!(
+            plan_resolutions(&agent, 16).await.unwrap(),
+            vec![step(2, 2)]
+        );
+    }
+
+    #[test]
+    fn test_budget_plan() {
+        let plan = vec![step(1, 0), step(2, 1), step(3, 0), step(4, 2)];
+        let budgeted = |gas_budget| {
+            let mut plan = plan.clone();
+            budget_plan(&mut plan, gas_budget);
+            plan.len()
+        };
+        assert_eq!(budgeted(None), 4);
+        // steps are cut once their estimated prune gas exceeds the budget
+        assert_eq!(budgeted(Some(3 * PRUNE_GAS_ESTIMATE)), 4);
+        assert_eq!(budgeted(Some(2 * PRUNE_GAS_ESTIMATE)), 3);
+        assert_eq!(budgeted(Some(PRUNE_GAS_ESTIMATE)), 3);
+        assert_eq!(budgeted(Some(PRUNE_GAS_ESTIMATE - 1)), 1);
+        // the first step is always kept
+        let mut plan = vec![step(1, 4), step(2, 0)];
+        budget_plan(&mut plan, Some(0));
+        assert_eq!(plan, vec![step(1, 4)]);
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::network::Network;
+use alloy::providers::Provider;
+use anyhow::Context;
+use kailua_sync::{await_tel, retry_res_ctx_timeout};
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use std::collections::BTreeMap;
+
+/// Number of recent L1 blocks whose blob base fee is compared against (about an hour)
+pub const BLOB_FEE_WINDOW: u64 = 300;
+
+/// Scheduling of proposals around blob fee spikes
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct BlobFeeArgs {
+    /// The maximum number of seconds of chain time to delay a proposal for cheaper blob fees. (Default 0, disabled)
+    #[clap(long, env, default_value_t = 0)]
+    pub blob_fee_slack: u64,
+    /// The percentile of recent blob base fees below which delayed proposals are submitted. (Default 50)
+    #[clap(long, env, default_value_t = 50)]
+    pub blob_fee_percentile: u8,
+}
+
+/// A proposal delayed for cheaper blob fees
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DelayedProposal {
+    /// The l2 block number of the proposed output
+    pub block_number: u64,
+    /// Chain time at which the proposal could first have been submitted
+    pub eligible_at: u64,
+    /// Blob base fee at which the proposal could first have been submitted
+    pub reference_fee: u128,
+}
+
+/// Tracks recent blob base fees to decide when to submit proposals and how much was saved
+#[derive(Clone, Debug, Default)]
+pub struct BlobFeeScheduler {
+    pub slack: u64,
+    pub percentile: u8,
+    /// Blob base fee observed at each recent L1 block
+    pub samples: BTreeMap<u64, u128>,
+    /// The proposal whose submission is being scheduled
+    pub pending: Option<DelayedProposal>,
+    /// Total wei saved (or lost, if negative) on blob fees by delaying proposals
+    pub total_saved: i128,
+}
+
+impl BlobFeeScheduler {
+    pub fn new(args: &BlobFeeArgs) -> Self {
+        Self {
+            slack: args.blob_fee_slack,
+            percentile: args.blob_fee_percentile.min(100),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.slack > 0
+    }
+
+    /// Records the blob base fee of the given L1 block.
+    pub fn observe(&mut self, l1_block_number: u64, blob_fee: u128) {
+        self.samples.insert(l1_block_number, blob_fee);
+        let oldest = l1_block_number.saturating_sub(BLOB_FEE_WINDOW - 1);
+        self.samples = self.samples.split_off(&oldest);
+    }
+
+    /// Records the blob base fees of the recent L1 blocks up to the one following `l1_head` and
+    /// returns the blob base fee of that next block.
+    ///
+    /// Fees are read from `eth_feeHistory`, so the window is seeded on the first call and any
+    /// blocks missed since the previous call are backfilled.
+    pub async fn sample<P: Provider<N>, N: Network>(
+        &mut self,
+        provider: &P,
+        l1_head: u64,
+    ) -> anyhow::Result<u128> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("BlobFeeScheduler::sample"));
+
+        // The fee last sampled for the next block is re-read once that block exists
+        let first_block = self
+            .samples
+            .last_key_value()
+            .map_or(0, |(block, _)| *block)
+            .max(l1_head.saturating_sub(BLOB_FEE_WINDOW - 2))
+            .min(l1_head);
+        let fee_history = await_tel!(
+            context,
+            tracer,
+            "get_fee_history",
+            retry_res_ctx_timeout!(
+                EthRpc => provider
+                    .get_fee_history(
+                        l1_head - first_block + 1,
+                        BlockNumberOrTag::Number(l1_head),
+                        &[],
+                    )
+                    .await
+            )
+        )?;
+        for (i, blob_fee) in fee_history.base_fee_per_blob_gas.iter().enumerate() {
+            self.observe(fee_history.oldest_block + i as u64, *blob_fee);
+        }
+        fee_history
+            .base_fee_per_blob_gas
+            .last()
+            .copied()
+            .context("baseFeePerBlobGas")
+    }
+
+    /// Returns the configured percentile of the observed blob base fees.
+    pub fn target_fee(&self) -> Option<u128> {
+        let mut fees = self.samples.values().copied().collect::<Vec<_>>();
+        if fees.is_empty() {
+            return None;
+        }
+        fees.sort_unstable();
+        let index = (fees.len() - 1) * self.percentile as usize / 100;
+        Some(fees[index])
+    }
+
+    /// Returns the number of seconds the proposal for the given block should still be delayed.
+    ///
+    /// A proposal is only delayed while the blob base fee exceeds the target, for at most the
+    /// configured slack, and never past `deadline`, when the next proposal becomes due.
+    pub fn delay(
+        &mut self,
+        block_number: u64,
+        chain_time: u64,
+        eligible_at: u64,
+        deadline: u64,
+        blob_fee: u128,
+    ) -> u64 {
+        if self
+            .pending
+            .is_none_or(|pending| pending.block_number != block_number)
+        {
+            self.pending = Some(DelayedProposal {
+                block_number,
+                eligible_at,
+                reference_fee: blob_fee,
+            });
+        }
+        if !self.is_enabled() {
+            return 0;
+        }
+        let submit_by = deadline.min(eligible_at + self.slack);
+        if chain_time >= submit_by {
+            return 0;
+        }
+        if self.target_fee().is_none_or(|target| blob_fee <= target) {
+            return 0;
+        }
+        submit_by - chain_time
+    }
+
+    /// Records the blob fees paid for the proposal of the given block and returns the wei saved
+    /// compared to submitting it as soon as it was eligible.
+    pub fn settle(
+        &mut self,
+        block_number: u64,
+        blob_gas_used: u64,
+        blob_gas_price: u128,
+    ) -> Option<i128> {
+        let pending = self
+            .pending
+            .take_if(|pending| pending.block_number == block_number)?;
+        let saved =
+            blob_gas_used as i128 * (pending.reference_fee as i128 - blob_gas_price as i128);
+        self.total_saved += saved;
+        Some(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kailua_sync::provider::memory::fixtures::*;
+
+    #[test]
+    fn test_target_fee() {
+        let mut scheduler = BlobFeeScheduler::new(&BlobFeeArgs {
+            blob_fee_slack: 60,
+            blob_fee_percentile: 50,
+        });
+        assert_eq!(scheduler.target_fee(), None);
+        for (block, fee) in [(1, 30), (2, 10), (3, 20)] {
+            scheduler.observe(block, fee);
+        }
+        assert_eq!(scheduler.target_fee(), Some(20));
+        // Old samples leave the window
+        scheduler.observe(BLOB_FEE_WINDOW + 2, 40);
+        assert_eq!(scheduler.samples.len(), 2);
+        assert_eq!(scheduler.target_fee(), Some(20));
+        scheduler.percentile = 100;
+        assert_eq!(scheduler.target_fee(), Some(40));
+    }
+
+    #[test]
+    fn test_delay() {
+        let mut scheduler = BlobFeeScheduler::new(&BlobFeeArgs {
+            blob_fee_slack: 60,
+            blob_fee_percentile: 50,
+        });
+        scheduler.observe(1, 10);
+        scheduler.observe(2, 100);
+        scheduler.observe(3, 100);
+        // Expensive blobs delay the proposal by at most the slack
+        assert_eq!(scheduler.delay(10, 1000, 1000, 2000, 100), 0);
+        scheduler.observe(4, 1);
+        scheduler.observe(5, 1);
+        assert_eq!(scheduler.delay(10, 1000, 1000, 2000, 100), 60);
+        assert_eq!(scheduler.delay(10, 1030, 1000, 2000, 100), 30);
+        assert_eq!(scheduler.delay(10, 1060, 1000, 2000, 100), 0);
+        // Never past the deadline
+        assert_eq!(scheduler.delay(10, 1000, 1000, 1010, 100), 10);
+        // Cheap blobs are used right away
+        assert_eq!(scheduler.delay(10, 1030, 1000, 2000, 1), 0);
+        // Savings are measured against the fee when the proposal became eligible
+        assert_eq!(scheduler.settle(11, 131072, 1), None);
+        assert_eq!(scheduler.settle(10, 131072, 1), Some(131072 * 99));
+        assert_eq!(scheduler.settle(10, 131072, 1), None);
+        assert_eq!(scheduler.total_saved, 131072 * 99);
+        // Disabled scheduling never delays
+        let mut scheduler = BlobFeeScheduler::new(&BlobFeeArgs::default());
+        scheduler.observe(1, 1);
+        assert_eq!(scheduler.delay(10, 1000, 1000, 2000, 100), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sample() {
+        let chain = anchored_chain(&deployment());
+        let data_dir = tempfile::tempdir().unwrap();
+        let agent = sync_agent(&chain, data_dir.path(), None).await;
+        let l1_provider = &agent.provider.l1_provider;
+        chain.set_blob_base_fee(0, 10);
+        for _ in 0..BLOB_FEE_WINDOW {
+            chain.mine_l1_block();
+        }
+        // A spike right before the proposal becomes eligible is compared against the seeded window
+        let (l1_head, _, _) = chain.l1_head();
+        chain.set_blob_base_fee(l1_head, 100);
+        let mut scheduler = BlobFeeScheduler::new(&BlobFeeArgs {
+            blob_fee_slack: 60,
+            blob_fee_percentile: 50,
+        });
+        let blob_fee = scheduler.sample(l1_provider, l1_head).await.unwrap();
+        assert_eq!(blob_fee, 100);
+        assert_eq!(scheduler.samples.len() as u64, BLOB_FEE_WINDOW);
+        assert_eq!(
+            scheduler.samples.last_key_value(),
+            Some((&(l1_head + 1), &100))
+        );
+        assert_eq!(scheduler.target_fee(), Some(10));
+        assert_eq!(scheduler.delay(10, 1000, 1000, 2000, blob_fee), 60);
+        // Blocks mined between samples are backfilled
+        chain.mine_l1_block();
+        chain.mine_l1_block();
+        let (l1_head, _, _) = chain.l1_head();
+        chain.set_blob_base_fee(l1_head + 1, 5);
+        let blob_fee = scheduler.sample(l1_provider, l1_head).await.unwrap();
+        assert_eq!(blob_fee, 5);
+        assert_eq!(scheduler.samples.len() as u64, BLOB_FEE_WINDOW);
+        assert_eq!(scheduler.samples[&l1_head], 100);
+        assert_eq!(scheduler.delay(10, 1012, 1000, 2000, blob_fee), 0);
+        // Savings are measured against the spike at which the proposal became eligible
+        assert_eq!(scheduler.settle(10, 131072, 5), Some(131072 * 95));
+    }
+}
@@ -0,0 +1,646 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::args::ProposeArgs;
+use alloy::network::ReceiptResponse;
+use alloy::primitives::{Address, Bytes};
+use alloy::providers::Provider;
+use anyhow::Context;
+use kailua_contracts::*;
+use kailua_kona::journal::ProofJournal;
+use kailua_prover::args::ProvingArgs;
+use kailua_sync::agent::SyncAgent;
+use kailua_sync::await_tel;
+use kailua_sync::proposal::Proposal;
+use kailua_sync::transact::dry_run::is_dry_run;
+use kailua_sync::transact::policy::Urgency;
+use kailua_sync::transact::Transact;
+use kailua_validator::args::ValidateArgs;
+use kailua_validator::channel::{DuplexChannel, Message};
+use kailua_validator::proposals::{encode_seal, get_next_l1_head};
+use kailua_validator::requests::{handle_proof_requests, request_validity_proof};
+use opentelemetry::global::{meter, tracer};
+use opentelemetry::metrics::Counter;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use opentelemetry::KeyValue;
+use risc0_zkvm::Receipt;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::spawn;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Validity proving of the proposer's own proposals
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct SelfProveArgs {
+    /// Whether to compute a validity proof for each of the proposer's own proposals
+    #[clap(long, env, default_value_t = false)]
+    pub self_prove: bool,
+    /// Whether to submit the validity proofs of own proposals even if no competing proposal appears
+    #[clap(long, env, default_value_t = false)]
+    pub self_prove_always: bool,
+    /// Path to the prover binary to use for proving
+    #[clap(long, env)]
+    pub kailua_cli: Option<PathBuf>,
+    /// How many proofs to compute simultaneously
+    #[clap(long, env, default_value_t = 1)]
+    pub num_concurrent_provers: u64,
+    /// Optionally enables the use of `debug_executePayload` to collect the execution witness from
+    /// the execution layer.
+    #[arg(long, env, default_value_t = false)]
+    pub enable_experimental_witness_endpoint: bool,
+}
+
+impl SelfProveArgs {
+    /// Returns the configuration of the validator's proving pipeline for proving own proposals.
+    pub fn validate_args(&self, args: &ProposeArgs, payout_recipient: Address) -> ValidateArgs {
+        let mut proving = ProvingArgs {
+            payout_recipient_address: Some(payout_recipient),
+            ..args.proving.clone()
+        };
+        if proving.skip_await_proof {
+            warn!("Self-proving proposer ignores the skip-await-proof flag.");
+            proving.skip_await_proof = false;
+        }
+        if proving.skip_derivation_proof {
+            warn!("Self-proving proposer ignores the skip-derivation-proof flag.");
+            proving.skip_derivation_proof = false;
+        }
+        ValidateArgs {
+            sync: args.sync.clone(),
+            kailua_cli: self.kailua_cli.clone(),
+            fast_forward_start: 0,
+            fast_forward_target: 0,
+            num_concurrent_provers: self.num_concurrent_provers,
+            enable_experimental_witness_endpoint: self.enable_experimental_witness_endpoint,
+            max_fault_proving_delay: 0,
+            max_validity_proving_delay: 0,
+            bond_collection_interval: 0,
+            #[cfg(feature = "devnet")]
+            l1_head_jump_back: 0,
+            validator_signer: Default::default(),
+            txn_args: args.txn_args.clone(),
+            proving,
+            boundless: args.boundless.clone(),
+        }
+    }
+}
+
+/// Spawns a proving pipeline and returns the channel to it along with its task
+pub type PipelineSpawner =
+    Box<dyn Fn() -> (DuplexChannel<Message>, JoinHandle<anyhow::Result<()>>) + Send + Sync>;
+
+/// Computes validity proofs for the proposer's own proposals and submits them when needed
+pub struct SelfProver {
+    /// The proposer whose proposals are proven
+    pub proposer: Address,
+    /// Whether to submit proofs for uncontested proposals
+    pub always_submit: bool,
+    /// Spawns the proving pipeline, again if it terminates
+    pub spawn_pipeline: PipelineSpawner,
+    /// Channel to the proving pipeline
+    pub channel: DuplexChannel<Message>,
+    /// The proving pipeline task
+    pub handle: JoinHandle<anyhow::Result<()>>,
+    /// Proposals for which a validity proof is being computed
+    pub requested: BTreeSet<u64>,
+    /// Block number of the last l1 head used to prove each proposal
+    pub last_proof_l1_head: BTreeMap<u64, u64>,
+    /// Computed validity proofs that are not yet submitted
+    pub receipts: BTreeMap<u64, Receipt>,
+    /// Unresolved proposals whose validity proofs were submitted or simulated
+    pub submitted: BTreeSet<u64>,
+    meter_proofs_requested: Counter<u64>,
+    meter_proofs_completed: Counter<u64>,
+    meter_proofs_published: Counter<u64>,
+    meter_proofs_fail: Counter<u64>,
+    meter_proofs_discarded: Counter<u64>,
+}
+
+impl SelfProver {
+    /// Starts the proving pipeline in the background.
+    pub fn start(args: &ProposeArgs, proposer: Address, verbosity: u8, data_dir: PathBuf) -> Self {
+        let tracer = tracer("kailua");
+        let context = opentelemetry::Context::current_with_span(tracer.start("SelfProver::start"));
+
+        let payout_recipient = args.proving.payout_recipient_address.unwrap_or(proposer);
+        info!("Self-proving proposals with payout recipient {payout_recipient}.");
+        let validate_args = args.self_prove.validate_args(args, payout_recipient);
+        let spawn_pipeline = move || {
+            let channel_pair = DuplexChannel::new_pair(4096);
+            let handle = spawn(
+                handle_proof_requests(
+                    channel_pair.1,
+                    validate_args.clone(),
+                    verbosity,
+                    data_dir.clone(),
+                )
+                .with_context(context.clone()),
+            );
+            (channel_pair.0, handle)
+        };
+        Self::new(
+            proposer,
+            args.self_prove.self_prove_always,
+            Box::new(spawn_pipeline),
+        )
+    }
+
+    /// Creates a self-prover around the proving pipeline created by `spawn_pipeline`.
+    pub fn new(proposer: Address, always_submit: bool, spawn_pipeline: PipelineSpawner) -> Self {
+        let (channel, handle) = spawn_pipeline();
+        let meter = meter("kailua");
+        Self {
+            proposer,
+            always_submit,
+            spawn_pipeline,
+            channel,
+            handle,
+            requested: Default::default(),
+            last_proof_l1_head: Default::default(),
+            receipts: Default::default(),
+            submitted: Default::default(),
+            meter_proofs_requested: meter.u64_counter("proposer.proofs.requested").build(),
+            meter_proofs_completed: meter.u64_counter("proposer.proofs.complete").build(),
+            meter_proofs_published: meter.u64_counter("proposer.proofs.published").build(),
+            meter_proofs_fail: meter.u64_counter("proposer.proofs.errs").build(),
+            meter_proofs_discarded: meter.u64_counter("proposer.proofs.discarded").build(),
+        }
+    }
+
+    /// Requests validity proofs for new proposals, and submits computed proofs when needed.
+    pub async fn advance<P: Provider>(
+        &mut self,
+        agent: &SyncAgent,
+        proposer_provider: &P,
+        txn_timeout: u64,
+    ) -> anyhow::Result<()> {
+        self.receive_proofs().await;
+        self.restart_pipeline().await;
+        self.request_proofs(agent)
+            .await
+            .context("SelfProver::request_proofs")?;
+        self.publish_proofs(agent, proposer_provider, txn_timeout)
+            .await
+            .context("SelfProver::publish_proofs")
+    }
+
+    /// Restarts the proving pipeline if it terminated.
+    async fn restart_pipeline(&mut self) {
+        if !self.handle.is_finished() {
+            return;
+        }
+        match (&mut self.handle).await {
+            Ok(Ok(())) => error!("Self-proving pipeline terminated."),
+            Ok(Err(err)) => error!("Self-proving pipeline failed: {err:?}"),
+            Err(err) => error!("Self-proving pipeline panicked: {err:?}"),
+        }
+        info!("Restarting self-proving pipeline.");
+        (self.channel, self.handle) = (self.spawn_pipeline)();
+        // Proofs that were being computed are requested again
+        self.requested.clear();
+    }
+
+    /// Requests a validity proof for each unresolved correct proposal made by the proposer.
+    async fn request_proofs(&mut self, agent: &SyncAgent) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SelfProver::request_proofs"));
+
+        self.submitted
+            .retain(|index| *index > agent.cursor.last_resolved_game);
+        for proposal in agent
+            .proposals
+            .range(agent.cursor.last_resolved_game + 1..)
+            .map(|(_, proposal)| proposal)
+        {
+            if proposal.proposer != self.proposer
+                || proposal.resolved_at != 0
+                || self.requested.contains(&proposal.index)
+                || self.receipts.contains_key(&proposal.index)
+                || self.submitted.contains(&proposal.index)
+            {
+                continue;
+            }
+            // Only correct proposals can be proven valid
+            if !proposal.is_correct().unwrap_or_default() {
+                continue;
+            }
+            let Some(parent) = agent.proposals.get(&proposal.parent) else {
+                continue;
+            };
+            if await_tel!(
+                context,
+                parent.fetch_is_successor_validity_proven(&agent.provider.l1_provider)
+            )? {
+                continue;
+            }
+            let Some(l1_head) = get_next_l1_head(
+                agent,
+                &mut self.last_proof_l1_head,
+                proposal,
+                #[cfg(feature = "devnet")]
+                0,
+            ) else {
+                warn!(
+                    "Could not choose an L1 head to prove proposal {}.",
+                    proposal.index
+                );
+                continue;
+            };
+            info!(
+                "Requesting validity proof for own proposal {}.",
+                proposal.index
+            );
+            match await_tel!(
+                context,
+                request_validity_proof(agent, &mut self.channel, parent, proposal, l1_head)
+            ) {
+                Ok(()) => {
+                    self.requested.insert(proposal.index);
+                    self.meter_proofs_requested.add(
+                        1,
+                        &[KeyValue::new("proposal", proposal.contract.to_string())],
+                    );
+                }
+                Err(err) => error!(
+                    "Could not request validity proof for {}: {err:?}",
+                    proposal.index
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Collects the proofs computed by the proving pipeline.
+    async fn receive_proofs(&mut self) {
+        while !self.channel.receiver.is_empty() {
+            let Some(message) = self.channel.receiver.recv().await else {
+                error!("Proofs receiver channel closed");
+                break;
+            };
+            let Message::Proof(proposal_index, receipt) = message else {
+                error!("Self-prover received an unexpected message.");
+                continue;
+            };
+            self.requested.remove(&proposal_index);
+            match receipt {
+                Some(receipt) => {
+                    info!("Validity proof for own proposal {proposal_index} computed.");
+                    self.meter_proofs_completed.add(1, &[]);
+                    self.receipts.insert(proposal_index, receipt);
+                }
+                // The proposal is requested again with the next l1 head
+                None => warn!("Insufficient l1 data to prove proposal {proposal_index}."),
+            }
+        }
+    }
+
+    /// Submits the computed proofs of contested proposals, or of all proposals if configured.
+    async fn publish_proofs<P: Provider>(
+        &mut self,
+        agent: &SyncAgent,
+        proposer_provider: &P,
+        txn_timeout: u64,
+    ) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SelfProver::publish_proofs"));
+
+        let proposal_indices = self.receipts.keys().copied().collect::<Vec<_>>();
+        for proposal_index in proposal_indices {
+            let (Some(proposal), Some(parent)) = (
+                agent.proposals.get(&proposal_index),
+                agent
+                    .proposals
+                    .get(&proposal_index)
+                    .and_then(|proposal| agent.proposals.get(&proposal.parent)),
+            ) else {
+                if agent.cursor.last_resolved_game >= proposal_index {
+                    info!("Discarding validity proof for resolved proposal {proposal_index}.");
+                    self.discard(proposal_index, "resolved");
+                }
+                continue;
+            };
+            if proposal.resolved_at != 0 {
+                info!("Discarding validity proof for resolved proposal {proposal_index}.");
+                self.discard(proposal_index, "resolved");
+                continue;
+            }
+            if await_tel!(
+                context,
+                parent.fetch_is_successor_validity_proven(&agent.provider.l1_provider)
+            )? {
+                info!(
+                    "Discarding validity proof for proposal {proposal_index} in tournament {} with validity proof.",
+                    parent.index
+                );
+                self.discard(proposal_index, "redundant");
+                continue;
+            }
+            // Only spend gas on proving validity once another proposal competes with ours
+            if !self.always_submit && !is_contested(agent, parent, proposal) {
+                continue;
+            }
+
+            let receipt = self.receipts.get(&proposal_index).unwrap().clone();
+            let parent_contract = KailuaTournament::new(parent.contract, proposer_provider);
+            // patch the proof if in dev mode
+            #[cfg(feature = "devnet")]
+            let receipt = {
+                use kailua_sync::stall::Stall;
+                let expected_fpvm_image_id = parent_contract
+                    .FPVM_IMAGE_ID()
+                    .stall_with_context(context.clone(), "KailuaTournament::FPVM_IMAGE_ID")
+                    .await?
+                    .0;
+                match kailua_validator::proposals::devnet::maybe_patch_proof(
+                    receipt.clone(),
+                    expected_fpvm_image_id,
+                ) {
+                    Ok(receipt) => receipt,
+                    Err(err) => {
+                        error!("(DEVNET) Failed to patch proof: {err:?}");
+                        receipt
+                    }
+                }
+            };
+            let proof_journal = ProofJournal::decode_packed(receipt.journal.as_ref());
+            let Some((l1_head_contract, _)) = agent.l1_heads_inv.get(&proof_journal.l1_head) else {
+                error!(
+                    "Failed to look up proposal contract with l1 head {}",
+                    proof_journal.l1_head
+                );
+                continue;
+            };
+            let Ok(encoded_seal) = encode_seal(&receipt).map(Bytes::from) else {
+                error!("Discarding validity proof. Failed to encode receipt seal.");
+                self.discard(proposal_index, "seal");
+                continue;
+            };
+            let Some(child_index) = parent.child_index(proposal.index) else {
+                error!(
+                    "Could not look up proposal {proposal_index} in tournament {}.",
+                    parent.index
+                );
+                continue;
+            };
+
+            info!(
+                "Submitting validity proof for own proposal {proposal_index} to tournament at index {}.",
+                parent.index
+            );
+            match parent_contract
+                .proveValidity(
+                    proof_journal.payout_recipient,
+                    *l1_head_contract,
+                    child_index,
+                    encoded_seal,
+                )
+                .timed_transact_with_urgency(
+                    context.clone(),
+                    "KailuaTournament::proveValidity",
+                    Some(Duration::from_secs(txn_timeout)),
+                    Urgency::Critical,
+                )
+                .await
+                .context("KailuaTournament::proveValidity")
+            {
+                Ok(receipt) if ReceiptResponse::status(&receipt) => {
+                    info!("Validity proof submitted: {:?}", receipt.transaction_hash);
+                    self.submit(proposal_index);
+                    self.meter_proofs_published.add(
+                        1,
+                        &[
+                            KeyValue::new("type", "validity"),
+                            KeyValue::new("proposal", proposal.contract.to_string()),
+                            KeyValue::new("l2_height", proposal.output_block_number.to_string()),
+                            KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
+                            KeyValue::new("txn_gas_used", receipt.gas_used.to_string()),
+                            KeyValue::new("txn_gas_price", receipt.effective_gas_price.to_string()),
+                        ],
+                    );
+                }
+                Ok(receipt) => {
+                    error!(
+                        "KailuaTournament::proveValidity reverted: {:?}",
+                        receipt.transaction_hash
+                    );
+                    self.meter_proofs_fail.add(
+                        1,
+                        &[
+                            KeyValue::new("proposal", proposal.contract.to_string()),
+                            KeyValue::new("msg", "reverted"),
+                        ],
+                    );
+                    // The proof is computed again with a later l1 head instead of resubmitted
+                    self.receipts.remove(&proposal_index);
+                    self.meter_proofs_discarded
+                        .add(1, &[KeyValue::new("reason", "reverted")]);
+                }
+                // Simulated proofs are not resubmitted
+                Err(err) if is_dry_run(&err) => self.submit(proposal_index),
+                Err(err) => {
+                    error!("Failed to confirm validity proof txn: {err:?}");
+                    self.meter_proofs_fail.add(
+                        1,
+                        &[
+                            KeyValue::new("proposal", proposal.contract.to_string()),
+                            KeyValue::new("msg", err.to_string()),
+                        ],
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn submit(&mut self, proposal_index: u64) {
+        self.receipts.remove(&proposal_index);
+        self.last_proof_l1_head.remove(&proposal_index);
+        self.submitted.insert(proposal_index);
+    }
+
+    fn discard(&mut self, proposal_index: u64, reason: &'static str) {
+        self.receipts.remove(&proposal_index);
+        self.last_proof_l1_head.remove(&proposal_index);
+        self.meter_proofs_discarded
+            .add(1, &[KeyValue::new("reason", reason)]);
+    }
+}
+
+/// Returns whether another proposal by a proposer that was not yet eliminated competes with the
+/// given proposal in its parent's tournament.
+pub fn is_contested(agent: &SyncAgent, parent: &Proposal, proposal: &Proposal) -> bool {
+    parent
+        .children
+        .iter()
+        .filter(|index| **index != proposal.index)
+        .any(|index| {
+            agent
+                .proposals
+                .get(index)
+                .is_none_or(|sibling| !agent.was_proposer_eliminated_before(sibling))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::B256;
+    use kailua_sync::provider::memory::fixtures::*;
+    use kailua_sync::provider::memory::{InMemoryChain, ScriptedProposal};
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, ReceiptClaim};
+    use std::sync::{Arc, Mutex};
+
+    async fn contested_agent(chain: &InMemoryChain) -> (SyncAgent, tempfile::TempDir) {
+        let deployment = deployment();
+        let honest_proposal = game_proposal(chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        let faulty_proposal = ScriptedProposal {
+            output_root: B256::repeat_byte(0xff),
+            ..game_proposal(chain, &deployment, FAULTY_GAME, 2, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(chain, data_dir.path(), None).await;
+        agent.sync(0, None).await.unwrap();
+        (agent, data_dir)
+    }
+
+    /// Returns a self-prover whose pipelines never terminate on their own, along with the
+    /// pipeline end of each channel it spawned.
+    fn test_prover(always_submit: bool) -> (SelfProver, Arc<Mutex<Vec<DuplexChannel<Message>>>>) {
+        let pipelines = Arc::new(Mutex::new(vec![]));
+        let spawned = pipelines.clone();
+        let prover = SelfProver::new(
+            HONEST_PROPOSER,
+            always_submit,
+            Box::new(move || {
+                let channel_pair = DuplexChannel::new_pair(16);
+                spawned.lock().unwrap().push(channel_pair.1);
+                let handle = spawn(std::future::pending::<anyhow::Result<()>>());
+                (channel_pair.0, handle)
+            }),
+        );
+        (prover, pipelines)
+    }
+
+    fn fake_receipt() -> Receipt {
+        Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(B256::ZERO.0, vec![]))),
+            vec![],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_is_contested() {
+        let chain = anchored_chain(&deployment());
+        let (mut agent, _data_dir) = contested_agent(&chain).await;
+        let anchor = agent.proposals[&0].clone();
+        // the faulty proposal competes until its proposer is eliminated
+        assert!(is_contested(&agent, &anchor, &agent.proposals[&1]));
+        assert!(is_contested(&agent, &anchor, &agent.proposals[&2]));
+        agent.eliminations.insert(FAULTY_PROPOSER, 1);
+        assert!(!is_contested(&agent, &anchor, &agent.proposals[&1]));
+        assert!(is_contested(&agent, &anchor, &agent.proposals[&2]));
+        // unknown siblings are assumed to compete
+        let mut anchor = anchor;
+        anchor.children.insert(3);
+        assert!(is_contested(&agent, &anchor, &agent.proposals[&1]));
+    }
+
+    #[tokio::test]
+    async fn test_self_prover() {
+        let chain = anchored_chain(&deployment());
+        let (mut agent, _data_dir) = contested_agent(&chain).await;
+        let (mut prover, pipelines) = test_prover(false);
+
+        // only the own correct proposal is requested, and only once
+        prover.request_proofs(&agent).await.unwrap();
+        prover.request_proofs(&agent).await.unwrap();
+        assert_eq!(prover.requested, BTreeSet::from([1]));
+        assert!(prover.last_proof_l1_head.contains_key(&1));
+        {
+            let mut pipelines = pipelines.lock().unwrap();
+            let Ok(Message::Proposal { index, l1_head, .. }) = pipelines[0].receiver.try_recv()
+            else {
+                panic!("Missing proof request");
+            };
+            assert_eq!(index, 1);
+            assert_eq!(l1_head, agent.proposals[&1].l1_head);
+            assert!(pipelines[0].receiver.try_recv().is_err());
+        }
+
+        // a terminated pipeline is restarted and its requests are made again
+        prover.handle.abort();
+        while !prover.handle.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        prover.restart_pipeline().await;
+        assert_eq!(pipelines.lock().unwrap().len(), 2);
+        assert!(prover.requested.is_empty());
+        prover.restart_pipeline().await;
+        assert_eq!(pipelines.lock().unwrap().len(), 2);
+
+        // computed proofs are kept until needed
+        pipelines.lock().unwrap()[1]
+            .sender
+            .try_send(Message::Proof(1, Some(fake_receipt())))
+            .unwrap();
+        prover.receive_proofs().await;
+        assert!(prover.receipts.contains_key(&1));
+        prover.request_proofs(&agent).await.unwrap();
+        assert!(prover.requested.is_empty());
+        agent.eliminations.insert(FAULTY_PROPOSER, 1);
+        prover
+            .publish_proofs(&agent, &agent.provider.l1_provider, 1)
+            .await
+            .unwrap();
+        assert!(prover.receipts.contains_key(&1));
+
+        // proofs are discarded once another validity proof is accepted
+        chain.mock_call(
+            ANCHOR,
+            KailuaTournament::validChildSignatureCall {},
+            B256::repeat_byte(0x01),
+        );
+        prover
+            .publish_proofs(&agent, &agent.provider.l1_provider, 1)
+            .await
+            .unwrap();
+        assert!(prover.receipts.is_empty());
+        assert!(prover.last_proof_l1_head.is_empty());
+        prover.request_proofs(&agent).await.unwrap();
+        assert!(prover.requested.is_empty());
+
+        // submitted proofs are not requested again until their proposal is resolved
+        chain.mock_call(
+            ANCHOR,
+            KailuaTournament::validChildSignatureCall {},
+            B256::ZERO,
+        );
+        prover.submit(1);
+        prover.request_proofs(&agent).await.unwrap();
+        assert!(prover.requested.is_empty());
+        agent.cursor.last_resolved_game = 1;
+        prover.request_proofs(&agent).await.unwrap();
+        assert!(prover.submitted.is_empty());
+    }
+}
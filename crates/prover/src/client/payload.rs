@@ -57,12 +57,12 @@ pub async fn run_payload_client(
             context,
             tracer,
             "l2_provider get_block_by_number claimed_l2_block_number",
-            retry_res_ctx_timeout!(l2_provider
+            retry_res_ctx_timeout!(OpGeth => l2_provider
                 .get_block_by_number(BlockNumberOrTag::Number(boot_info.claimed_l2_block_number))
                 .await
                 .context("l2_provider get_block_by_number claimed_l2_block_number")?
                 .ok_or_else(|| anyhow!("Failed to claimed l2 block")))
-        )
+        )?
         .header
         .hash;
         // Go back one block
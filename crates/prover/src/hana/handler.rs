@@ -45,11 +45,11 @@ impl HanaHintHandler {
         let blobstream_address = blobstream_address(chain_id)
             .expect("No canonical Blobstream address found for chain id");
         let blobstream_contract = SP1BlobstreamInstance::new(blobstream_address, l1_provider);
-        Ok(blobstream_contract
+        blobstream_contract
             .latestBlock()
             .block(BlockId::Hash(l1_head.into()))
             .stall("SP1Blobstream::latestBlock")
-            .await)
+            .await
     }
 
     pub async fn blobstream_height_proof(
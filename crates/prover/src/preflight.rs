@@ -47,13 +47,13 @@ pub async fn get_blob_fetch_request(
         context,
         tracer,
         "get_block_by_hash",
-        retry_res_ctx_timeout!(l1_provider
+        retry_res_ctx_timeout!(EthRpc => l1_provider
             .get_block_by_hash(block_hash)
             .full()
             .await
             .context("get_block_by_hash")?
             .ok_or_else(|| anyhow!("Failed to fetch starting block")))
-    );
+    )?;
     let mut blob_index = 0;
     let mut blob_found = false;
     for blob in block.transactions.into_transactions().flat_map(|tx| {
@@ -98,7 +98,7 @@ pub async fn fetch_precondition_data(
 
     // fetch necessary data to validate blob equivalence precondition
     if hash_arguments.iter().all(|arg| !arg) {
-        let providers = retry_res_ctx_timeout!(20, cfg.kona.create_providers().await).await;
+        let providers = retry_res_ctx_timeout!(20, cfg.kona.create_providers().await).await?;
         if cfg.precondition_block_hashes.len() != cfg.precondition_blob_hashes.len() {
             bail!(
                 "Blob reference mismatch. Found {} block hashes and {} blob hashes",
@@ -157,18 +157,18 @@ pub async fn concurrent_execution_preflight(
         opentelemetry::Context::current_with_span(tracer.start("concurrent_execution_preflight"));
 
     let l2_provider = retry_res_ctx_timeout!(20, args.kona.create_providers().await)
-        .await
+        .await?
         .l2;
     let starting_block = await_tel!(
         context,
         tracer,
         "l2_provider get_block_by_hash agreed_l2_head_hash",
-        retry_res_ctx_timeout!(l2_provider
+        retry_res_ctx_timeout!(OpGeth => l2_provider
             .get_block_by_hash(args.kona.agreed_l2_head_hash)
             .await
             .context("l2_provider get_block_by_hash agreed_l2_head_hash")?
             .ok_or_else(|| anyhow!("Failed to fetch agreed l2 block")))
-    )
+    )?
     .header
     .number;
 
@@ -194,12 +194,12 @@ pub async fn concurrent_execution_preflight(
             context,
             tracer,
             "l2_provider get_block_by_hash agreed_l2_head_hash",
-            retry_res_ctx_timeout!(l2_provider
+            retry_res_ctx_timeout!(OpGeth => l2_provider
                 .get_block_by_hash(args.kona.agreed_l2_head_hash)
                 .await
                 .context("l2_provider get_block_by_hash agreed_l2_head_hash")?
                 .ok_or_else(|| anyhow!("Failed to fetch agreed l2 block")))
-        )
+        )?
         .header
         .number
             + processed_blocks;
@@ -227,14 +227,14 @@ pub async fn concurrent_execution_preflight(
                 context,
                 tracer,
                 "l2_provider get_block_by_number claimed_l2_block_number",
-                retry_res_ctx_timeout!(l2_provider
+                retry_res_ctx_timeout!(OpGeth => l2_provider
                     .get_block_by_number(BlockNumberOrTag::Number(
                         args.kona.claimed_l2_block_number
                     ))
                     .await
                     .context("l2_provider get_block_by_number claimed_l2_block_number")?
                     .ok_or_else(|| anyhow!("Failed to claimed l2 block")))
-            )
+            )?
             .header
             .hash;
 
@@ -46,7 +46,7 @@ pub async fn prove(mut args: ProveArgs) -> anyhow::Result<()> {
     } else {
         Some(
             retry_res_ctx_timeout!(20, args.kona.create_providers().await)
-                .await
+                .await?
                 .l2,
         )
     };
@@ -140,12 +140,12 @@ pub async fn prove(mut args: ProveArgs) -> anyhow::Result<()> {
                     context,
                     tracer,
                     "l2_provider get_block_by_hash starting_block",
-                    retry_res_ctx_timeout!(l2_provider
+                    retry_res_ctx_timeout!(OpGeth => l2_provider
                         .get_block_by_hash(job_args.kona.agreed_l2_head_hash)
                         .await
                         .context("l2_provider get_block_by_hash starting_block")?
                         .ok_or_else(|| anyhow!("Failed to fetch starting block")))
-                )
+                )?
                 .header
                 .number
             } else {
@@ -287,22 +287,22 @@ pub async fn prove(mut args: ProveArgs) -> anyhow::Result<()> {
                     context,
                     tracer,
                     "op_node_provider output_at_block mid_output",
-                    retry_res_ctx_timeout!(op_node_provider
+                    retry_res_ctx_timeout!(OpNode => op_node_provider
                         .output_at_block(mid_point)
                         .await
                         .context("op_node_provider output_at_block mid_output"))
-                );
+                )?;
                 let l2_provider = l2_provider.as_ref().expect("Missing l2_provider");
                 let mid_block = await_tel!(
                     context,
                     tracer,
                     "l2_provider get_block_by_number mid_block",
-                    retry_res_ctx_timeout!(l2_provider
+                    retry_res_ctx_timeout!(OpGeth => l2_provider
                         .get_block_by_number(BlockNumberOrTag::Number(mid_point))
                         .await
                         .context("l2_provider get_block_by_number mid_block")?
                         .ok_or_else(|| anyhow!("Block {mid_point} not found")))
-                );
+                )?;
                 // Lower half workload ends at midpoint (inclusive)
                 let mut lower_job_args = job_args.clone();
                 lower_job_args.kona.claimed_l2_output_root = mid_output;
@@ -347,14 +347,14 @@ pub async fn prove(mut args: ProveArgs) -> anyhow::Result<()> {
                     context,
                     tracer,
                     "l2_provider get_block_by_number claimed_l2_block_number",
-                    retry_res_ctx_timeout!(l2_provider
+                    retry_res_ctx_timeout!(OpGeth => l2_provider
                         .get_block_by_number(BlockNumberOrTag::Number(
                             base_args.kona.claimed_l2_block_number,
                         ))
                         .await
                         .context("l2_provider get_block_by_number claimed_l2_block_number")?
                         .ok_or_else(|| anyhow!("Claimed L2 block not found")))
-                )
+                )?
                 .header
                 .hash;
             }
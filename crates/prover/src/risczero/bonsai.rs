@@ -85,7 +85,8 @@ pub async fn run_bonsai_client<A: NoUninit + Into<Digest>>(
         input.clone(),
         assumption_receipt_ids.clone(),
     )
-    .await;
+    .await
+    .map_err(ProvingError::OtherError)?;
 
     if proving_args.skip_await_proof {
         warn!("Skipping awaiting proof on Bonsai.");
@@ -105,7 +106,9 @@ pub async fn run_bonsai_client<A: NoUninit + Into<Digest>>(
     let stark_receipt = loop {
         // The session has already been started in the executor. Poll bonsai to check if
         // the proof request succeeded.
-        let res = retry_res!(stark_session.status(&client).await).await;
+        let res = retry_res!(stark_session.status(&client).await)
+            .await
+            .map_err(ProvingError::OtherError)?;
 
         match res.status.as_str() {
             "RUNNING" => tokio::time::sleep(polling_interval).await,
@@ -160,7 +163,8 @@ pub async fn run_bonsai_client<A: NoUninit + Into<Digest>>(
                     input.clone(),
                     assumption_receipt_ids.clone(),
                 )
-                .await;
+                .await
+                .map_err(ProvingError::OtherError)?;
             }
         }
     };
@@ -178,10 +182,13 @@ pub async fn run_bonsai_client<A: NoUninit + Into<Digest>>(
         stark_receipt_bincoded.clone(),
         Some(stark_session.uuid),
     )
-    .await;
+    .await
+    .map_err(ProvingError::OtherError)?;
 
     let groth16_receipt = loop {
-        let res = retry_res!(snark_session.status(&client).await).await;
+        let res = retry_res!(snark_session.status(&client).await)
+            .await
+            .map_err(ProvingError::OtherError)?;
 
         match res.status.as_str() {
             "RUNNING" => sleep(polling_interval).await,
@@ -214,8 +221,9 @@ pub async fn run_bonsai_client<A: NoUninit + Into<Digest>>(
                     "Bonsai prover workflow [{}] exited: {} err: {:?}",
                     snark_session.uuid, res.status, res.error_msg
                 );
-                snark_session =
-                    create_snark_session(&client, stark_receipt_bincoded.clone(), None).await;
+                snark_session = create_snark_session(&client, stark_receipt_bincoded.clone(), None)
+                    .await
+                    .map_err(ProvingError::OtherError)?;
             }
         }
     };
@@ -227,17 +235,17 @@ pub async fn create_snark_session(
     client: &Client,
     receipt: Vec<u8>,
     mut stark_id: Option<String>,
-) -> SnarkId {
+) -> anyhow::Result<SnarkId> {
     loop {
         // Reupload receipt if not first attempt
         let stark_id = match stark_id.take() {
             Some(id) => id,
-            None => retry_res!(client.upload_receipt(receipt.clone()).await).await,
+            None => retry_res!(client.upload_receipt(receipt.clone()).await).await?,
         };
 
         // Request that Bonsai compress further, to Groth16.
         if let Ok(result) = client.create_snark(stark_id.clone()).await {
-            break result;
+            break Ok(result);
         }
     }
 }
@@ -247,17 +255,17 @@ pub async fn create_stark_session<A: NoUninit + Into<Digest>>(
     client: &Client,
     input: Vec<u8>,
     assumption_receipt_ids: Vec<String>,
-) -> SessionId {
+) -> anyhow::Result<SessionId> {
     // Upload the ELF with the image_id as its key.
     let elf = image.1.to_vec();
     let image_id_hex = hex::encode(image.0.into());
-    let is_image_present = retry_res_timeout!(client.has_img(&image_id_hex).await).await;
+    let is_image_present = retry_res_timeout!(client.has_img(&image_id_hex).await).await?;
     if !is_image_present {
         info!(
             "Uploading {} Kailua ELF to Bonsai.",
             human_bytes(elf.len() as f64)
         );
-        retry_res!(client.upload_img(&image_id_hex, elf.clone()).await).await;
+        retry_res!(client.upload_img(&image_id_hex, elf.clone()).await).await?;
     } else {
         info!("Kailua ELF already exists on Bonsai.");
     }
@@ -269,7 +277,7 @@ pub async fn create_stark_session<A: NoUninit + Into<Digest>>(
             "Uploading {} input data to Bonsai.",
             human_bytes(input.len() as f64)
         );
-        let input_id = retry_res!(client.upload_input(input.clone()).await).await;
+        let input_id = retry_res!(client.upload_input(input.clone()).await).await?;
 
         // Create session on Bonsai
         info!("Creating Bonsai proving session.");
@@ -294,7 +302,7 @@ pub async fn create_stark_session<A: NoUninit + Into<Digest>>(
         info!("Bonsai proving SessionID: {}", session.uuid);
 
         sleep(Duration::from_millis(500)).await;
-        break session;
+        break Ok(session);
     }
 }
 
@@ -342,7 +342,8 @@ pub async fn run_boundless_client<A: NoUninit + Into<Digest>>(
             .await
             .context("ClientBuilder::build()")
     )
-    .await;
+    .await
+    .map_err(ProvingError::OtherError)?;
 
     // Report boundless deployment info
     info!(
@@ -499,7 +500,8 @@ pub async fn look_back(
             .get_status(request_id, Some(request.expires_at()))
             .await
             .context("get_status"))
-        .await;
+        .await
+        .map_err(ProvingError::OtherError)?;
 
         if matches!(request_status, RequestStatus::Expired) {
             // We found a duplicate but it was expired
@@ -635,7 +637,8 @@ pub async fn request_proof<A: NoUninit + Into<Digest>>(
                     .upload_program(image.1)
                     .await
                     .context("Client::upload_program"))
-                .await;
+                .await
+                .map_err(ProvingError::OtherError)?;
                 if let Err(err) =
                     save_to_bincoded_file(&program_url.to_string(), &bin_file_name).await
                 {
@@ -746,7 +749,8 @@ pub async fn request_proof<A: NoUninit + Into<Digest>>(
                 .upload_input(&input)
                 .await
                 .context("Client::upload_input"))
-            .await;
+            .await
+            .map_err(ProvingError::OtherError)?;
             // avoid api rate limits
             sleep(Duration::from_secs(2)).await;
             if let Err(err) = save_to_bincoded_file(&input_url.to_string(), &inp_file_name).await {
@@ -768,6 +772,7 @@ pub async fn request_proof<A: NoUninit + Into<Digest>>(
         .context("get_block_by_number latest")?
         .ok_or_else(|| anyhow!("Failed to fetch latest block from Boundless RPC")))
     .await
+    .map_err(ProvingError::OtherError)?
     .header
     .timestamp;
 
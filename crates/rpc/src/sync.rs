@@ -15,10 +15,8 @@
 use crate::api::KailuaServerCache;
 use crate::args::RpcArgs;
 use anyhow::Context;
-use kailua_contracts::*;
 use kailua_sync::agent::{SyncAgent, FINAL_L2_BLOCK_RESOLVED};
-use kailua_sync::stall::Stall;
-use kailua_sync::{await_tel, KAILUA_GAME_TYPE};
+use kailua_sync::await_tel;
 use opentelemetry::global::tracer;
 use opentelemetry::trace::FutureExt;
 use opentelemetry::trace::{TraceContextExt, Tracer};
@@ -35,10 +33,6 @@ pub async fn handle_sync(
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("handle_sync"));
 
-    // todo: auto update deployment
-    // check dgf for set implementation events
-    // order by their game index
-
     // todo: init from beginning instead of last resolved game
 
     // initialize sync agent
@@ -52,20 +46,6 @@ pub async fn handle_sync(
     .await?;
     info!("KailuaTreasury({:?})", agent.deployment.treasury);
 
-    // Check if deployment is still valid
-    let dispute_game_factory =
-        IDisputeGameFactory::new(agent.deployment.factory, &agent.provider.l1_provider);
-    let latest_game_impl_addr = dispute_game_factory
-        .gameImpls(KAILUA_GAME_TYPE)
-        .stall_with_context(context.clone(), "DisputeGameFactory::gameImpls")
-        .await;
-    if latest_game_impl_addr != agent.deployment.game {
-        warn!(
-            "Deployment {} outdated. Found new deployment {latest_game_impl_addr}.",
-            agent.deployment.game
-        );
-    }
-
     loop {
         // Wait for new data on every iteration
         sleep(Duration::from_secs(1)).await;
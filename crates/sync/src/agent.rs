@@ -12,17 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cursor::SyncCursor;
+use crate::checkpoint::SyncCheckpoint;
+use crate::cursor::{LoggedGame, SyncCursor};
 use crate::deployment::SyncDeployment;
+use crate::multicall::MulticallBatch;
 use crate::proposal::{Proposal, ProposalSync};
-use crate::provider::optimism::fetch_rollup_config;
+use crate::provider::optimism::fetch_rollup_config_from;
 use crate::provider::{ProviderArgs, SyncProvider};
 use crate::stall::Stall;
 use crate::telemetry::SyncTelemetry;
+use crate::transact::journal::journal;
 use crate::{await_tel, await_tel_res, retry_res_ctx_timeout, retry_res_timeout, KAILUA_GAME_TYPE};
+use alloy::eips::BlockNumberOrTag;
 use alloy::network::Network;
 use alloy::primitives::{Address, B256, U256};
 use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
+use alloy::sol_types::SolEvent;
 use anyhow::{anyhow, bail, Context};
 use futures::future::join_all;
 use itertools::Itertools;
@@ -40,11 +46,16 @@ use opentelemetry::KeyValue;
 use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
 pub const FINAL_L2_BLOCK_RESOLVED: &str = "Last resolved proposal l2 block reached final l2 block.";
 
+/// Number of games discovered through logs that are loaded together, chosen such that all their
+/// contract reads fit into a single multicall.
+pub const LOG_PROPOSALS_BATCH: usize = 16;
+
 /// A stateful agent object for synchronizing with an on-chain Kailua deployment.
 pub struct SyncAgent {
     /// RPC providers to use for querying chain data
@@ -55,11 +66,19 @@ pub struct SyncAgent {
     pub config: RollupConfig,
     /// Kailua deployment configuration for instance being synchronized
     pub deployment: SyncDeployment,
+    /// Kailua deployments observed so far by treasury address
+    pub deployments: BTreeMap<Address, SyncDeployment>,
+    /// Latest KailuaGame implementation set in the dispute game factory
+    pub latest_game_impl: Address,
+    /// Whether to hand over to newly set KailuaGame implementations
+    pub follow_upgrades: bool,
+    /// Root directory of the local persistent storage
+    pub data_dir: PathBuf,
     /// Local persistent key-value store
     pub db: Arc<rocksdb::DB>,
     /// Pointers to the latest synchronized items
     pub cursor: SyncCursor,
-    /// In-memory cache of op-node query results
+    /// In-memory cache of output root query results
     pub outputs: BTreeMap<u64, B256>,
     /// In-memory cache of on-chain proposal data
     pub proposals: BTreeMap<u64, Proposal>,
@@ -69,20 +88,30 @@ pub struct SyncAgent {
     pub l1_heads: BTreeMap<u64, (Address, B256)>,
     /// In-memory cache of available l1-heads for derivation (inverse map)
     pub l1_heads_inv: BTreeMap<B256, (Address, u64)>,
+    /// Number of L1 blocks to scan per log query if log discovery is enabled
+    pub log_sync_range: Option<u64>,
+    /// Indices of proposals modified since the last checkpoint
+    dirty_proposals: BTreeSet<u64>,
+    /// Proposals discovered through logs that were loaded ahead of processing by factory index
+    log_proposals: BTreeMap<u64, Proposal>,
+    /// Index of the first proposal restored from the last checkpoint
+    restored_from: Option<u64>,
+    /// Canonical proposal tip when an implementation upgrade under the same treasury was observed
+    upgrade_tip: Option<u64>,
+    /// Deployment to hand over to at the start of the next synchronization iteration
+    handover: Option<SyncDeployment>,
 }
 
 impl SyncAgent {
     pub async fn new(
         provider_args: &ProviderArgs,
-        mut data_dir: PathBuf,
+        data_dir: PathBuf,
         game_impl_address: Option<Address>,
         anchor_address: Option<Address>,
         bypass_chain_registry: bool,
     ) -> anyhow::Result<Self> {
         let tracer = tracer("kailua");
         let context = opentelemetry::Context::current_with_span(tracer.start("SymcAgemt::new"));
-        // Initialize telemetry first
-        let telemetry = SyncTelemetry::new();
 
         // Connect to RPC providers
         let provider = await_tel_res!(
@@ -95,9 +124,9 @@ impl SyncAgent {
         info!("Fetching rollup configuration from rpc endpoints.");
         let config = await_tel_res!(
             context,
-            fetch_rollup_config(
-                &provider_args.op_node_url,
-                &provider_args.op_geth_url,
+            fetch_rollup_config_from(
+                &provider.op_provider,
+                &provider.l2_provider,
                 None,
                 bypass_chain_registry
             ),
@@ -106,57 +135,239 @@ impl SyncAgent {
         let rollup_config_hash = config_hash(&config).expect("Configuration hash derivation error");
         info!("RollupConfigHash({})", hex::encode(rollup_config_hash));
 
+        await_tel_res!(
+            context,
+            Self::from_provider(
+                provider,
+                config,
+                data_dir,
+                game_impl_address,
+                anchor_address,
+                provider_args.log_sync_range
+            ),
+            "SyncAgent::from_provider"
+        )
+    }
+
+    /// Creates an agent that synchronizes through the given providers using the given rollup
+    /// configuration.
+    pub async fn from_provider(
+        provider: SyncProvider,
+        config: RollupConfig,
+        data_dir: PathBuf,
+        game_impl_address: Option<Address>,
+        anchor_address: Option<Address>,
+        log_sync_range: Option<u64>,
+    ) -> anyhow::Result<Self> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SyncAgent::from_provider"));
+        // Initialize telemetry first
+        let telemetry = SyncTelemetry::new();
+
         // Load target deployment data
         let deployment = await_tel_res!(
             context,
             SyncDeployment::load(&provider, &config, game_impl_address),
             "Deployment::load"
         )?;
-        #[cfg(not(feature = "devnet"))]
-        {
-            let known_image_ids = [
-                B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
-                    kailua_build::KAILUA_FPVM_KONA_ID,
-                )),
-                B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
-                    kailua_build::KAILUA_FPVM_HOKULEA_ID,
-                )),
-                B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
-                    kailua_build::KAILUA_FPVM_HANA_ID,
-                )),
-            ];
-            if !known_image_ids.contains(&deployment.image_id) {
-                bail!("Deployment image ID {:?} unknown.", deployment.image_id);
-            }
-        }
+        Self::check_image_id(&deployment)?;
 
         // Initialize persistent DB
-        data_dir.push(deployment.cfg_hash.to_string());
-        data_dir.push(deployment.treasury.to_string());
-        let db = Arc::new(
-            rocksdb::DB::open(&Self::db_options(), &data_dir).context("rocksdb::DB::open")?,
-        );
-
-        // Create cursor
-        let cursor = await_tel_res!(
-            context,
-            SyncCursor::load(&deployment, &provider, anchor_address),
-            "SyncCursor::load"
-        )?;
+        let db = Self::open_db(data_dir.clone(), &deployment)?;
 
-        Ok(Self {
+        let mut agent = Self {
             provider,
             telemetry,
             config,
+            deployments: BTreeMap::from([(deployment.treasury, deployment.clone())]),
+            latest_game_impl: deployment.game,
+            follow_upgrades: game_impl_address.is_none(),
             deployment,
+            data_dir,
             db,
-            cursor,
+            cursor: Default::default(),
             outputs: Default::default(),
             proposals: Default::default(),
             eliminations: Default::default(),
             l1_heads: Default::default(),
             l1_heads_inv: Default::default(),
-        })
+            log_sync_range,
+            dirty_proposals: Default::default(),
+            log_proposals: Default::default(),
+            restored_from: None,
+            upgrade_tip: None,
+            handover: None,
+        };
+        await_tel_res!(context, agent.load_state(anchor_address), "load_state")?;
+
+        Ok(agent)
+    }
+
+    fn check_image_id(deployment: &SyncDeployment) -> anyhow::Result<()> {
+        // Any image id is accepted on devnets
+        if cfg!(feature = "devnet") {
+            return Ok(());
+        }
+        let known_image_ids = [
+            B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
+                kailua_build::KAILUA_FPVM_KONA_ID,
+            )),
+            B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
+                kailua_build::KAILUA_FPVM_HOKULEA_ID,
+            )),
+            B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
+                kailua_build::KAILUA_FPVM_HANA_ID,
+            )),
+        ];
+        if !known_image_ids.contains(&deployment.image_id) {
+            bail!("Deployment image ID {:?} unknown.", deployment.image_id);
+        }
+        Ok(())
+    }
+
+    fn open_db(
+        mut data_dir: PathBuf,
+        deployment: &SyncDeployment,
+    ) -> anyhow::Result<Arc<rocksdb::DB>> {
+        data_dir.push(deployment.cfg_hash.to_string());
+        data_dir.push(deployment.treasury.to_string());
+        Ok(Arc::new(
+            rocksdb::DB::open(&Self::db_options(), &data_dir).context("rocksdb::DB::open")?,
+        ))
+    }
+
+    /// Returns the treasuries of all deployments synchronized so far, including those handed over
+    /// from in earlier runs whose state remains in the data directory.
+    pub fn known_treasuries(&self) -> BTreeSet<Address> {
+        let mut treasuries = self.deployments.keys().copied().collect::<BTreeSet<_>>();
+        let cfg_dir = self.data_dir.join(self.deployment.cfg_hash.to_string());
+        if let Ok(entries) = std::fs::read_dir(cfg_dir) {
+            treasuries.extend(
+                entries
+                    .flatten()
+                    .filter_map(|entry| Address::from_str(entry.file_name().to_str()?).ok()),
+            );
+        }
+        treasuries
+    }
+
+    /// Initializes the synchronization state of the current deployment from the last checkpoint
+    /// or from the given anchor (defaults to the last resolved proposal).
+    pub async fn load_state(&mut self, anchor_address: Option<Address>) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SyncAgent::load_state"));
+
+        // Clear any in-memory state
+        self.outputs.clear();
+        self.proposals.clear();
+        self.eliminations.clear();
+        self.l1_heads.clear();
+        self.l1_heads_inv.clear();
+        self.dirty_proposals.clear();
+        self.log_proposals.clear();
+        self.restored_from = None;
+        self.upgrade_tip = None;
+        self.handover = None;
+
+        // Attempt to resume from the last checkpoint unless an anchor is explicitly set
+        let checkpoint = match anchor_address {
+            Some(_) => None,
+            None => match SyncCheckpoint::read(&self.db) {
+                Ok(Some((checkpoint, proposals))) => match await_tel!(
+                    context,
+                    tracer,
+                    "SyncCheckpoint::verify",
+                    checkpoint.verify(&proposals, &self.provider, &self.deployment)
+                ) {
+                    Ok(()) => Some((checkpoint, proposals)),
+                    Err(err) => {
+                        warn!("Discarding sync checkpoint: {err:?}");
+                        None
+                    }
+                },
+                Ok(None) => None,
+                Err(err) => {
+                    error!("Failed to read sync checkpoint: {err:?}");
+                    None
+                }
+            },
+        };
+
+        let Some((checkpoint, proposals)) = checkpoint else {
+            // Discard any stale state
+            SyncCheckpoint::clear(&self.db).context("SyncCheckpoint::clear")?;
+            // Create cursor
+            self.cursor = await_tel_res!(
+                context,
+                SyncCursor::load(&self.deployment, &self.provider, anchor_address),
+                "SyncCursor::load"
+            )?;
+            return Ok(());
+        };
+
+        info!(
+            "Resuming from checkpoint at factory index {}.",
+            checkpoint.cursor.next_factory_index
+        );
+        self.restored_from = Some(checkpoint.cursor.last_resolved_game);
+        self.cursor = checkpoint.cursor;
+        for proposal in proposals.values() {
+            journal().register_proposal(proposal.contract, proposal.index);
+        }
+        self.proposals = proposals;
+        self.eliminations = checkpoint.eliminations;
+        self.l1_heads = checkpoint.l1_heads;
+        self.l1_heads_inv = checkpoint.l1_heads_inv;
+        self.restore_outputs();
+
+        Ok(())
+    }
+
+    /// Loads the persisted output commitments needed after the last resolved proposal
+    fn restore_outputs(&mut self) {
+        let Some(last_resolved_proposal) = self.proposals.get(&self.cursor.last_resolved_game)
+        else {
+            return;
+        };
+        let start = if last_resolved_proposal.has_parent() {
+            last_resolved_proposal
+                .output_block_number
+                .saturating_sub(self.deployment.blocks_per_proposal())
+        } else {
+            last_resolved_proposal.output_block_number
+        };
+        let end = self.cursor.last_output_index;
+        // resume output synchronization after the last contiguous stored output
+        self.cursor.last_output_index = start;
+        for i in (start..=end).step_by(self.deployment.output_block_span as usize) {
+            let Ok(Some(output)) = self.db.get(i.to_be_bytes()) else {
+                break;
+            };
+            self.outputs.insert(i, B256::from_slice(&output));
+            self.cursor.last_output_index = i;
+        }
+        info!("Restored {} outputs from storage.", self.outputs.len());
+    }
+
+    /// Persists the synchronization state and all modified proposals to storage
+    pub fn checkpoint(&mut self) -> anyhow::Result<()> {
+        let checkpoint = SyncCheckpoint {
+            game: self.deployment.game,
+            cursor: self.cursor.clone(),
+            eliminations: self.eliminations.clone(),
+            l1_heads: self.l1_heads.clone(),
+            l1_heads_inv: self.l1_heads_inv.clone(),
+        };
+        checkpoint.write(
+            &self.db,
+            self.dirty_proposals
+                .iter()
+                .filter_map(|i| self.proposals.get(i)),
+        )?;
+        self.dirty_proposals.clear();
+        Ok(())
     }
 
     fn db_options() -> rocksdb::Options {
@@ -174,6 +385,10 @@ impl SyncAgent {
         for i in earliest_proposal..self.cursor.last_resolved_game {
             if self.proposals.remove(&i).is_some() {
                 info!("Freed proposal {i} from memory.");
+                if let Err(err) = self.db.delete(SyncCheckpoint::proposal_key(i)) {
+                    error!("Failed to delete proposal {i} from storage: {err:?}.");
+                }
+                self.dirty_proposals.remove(&i);
                 proposals.insert(i);
             }
         }
@@ -181,6 +396,31 @@ impl SyncAgent {
         self.cursor
             .delayed_factory_indices
             .retain(|i| *i >= self.cursor.last_resolved_game);
+        // delete all discovered games prior to last resolved proposal
+        self.cursor
+            .log_games
+            .retain(|i, _| *i >= self.cursor.last_resolved_game);
+        self.log_proposals
+            .retain(|i, _| *i >= self.cursor.last_resolved_game);
+        self.cursor.log_resolutions.retain(|a, _| {
+            self.proposals
+                .values()
+                .chain(self.log_proposals.values())
+                .any(|p| &p.contract == a)
+        });
+        // delete all l1 observations superseded by one prior to last resolved proposal
+        while let Some(second_observation) = self
+            .cursor
+            .l1_observations
+            .values()
+            .nth(1)
+            .map(|(_, index)| *index)
+        {
+            if second_observation > self.cursor.last_resolved_game {
+                break;
+            }
+            self.cursor.l1_observations.pop_first();
+        }
         // fetch last resolved proposal
         let Some(last_resolved_proposal) = self.proposals.get(&self.cursor.last_resolved_game)
         else {
@@ -235,13 +475,28 @@ impl SyncAgent {
         let tracer = tracer("kailua");
         let context = opentelemetry::Context::current_with_span(tracer.start("SyncAgent::sync"));
 
+        // hand over to the deployment scheduled in the last iteration
+        if let Some(deployment) = self.handover.take() {
+            await_tel!(
+                context,
+                tracer,
+                "switch_deployment",
+                self.switch_deployment(deployment)
+            )
+            .context("switch_deployment")?;
+        }
+
+        // roll back proposals observed in reorged l1 blocks
+        await_tel!(context, tracer, "handle_reorgs", self.handle_reorgs())
+            .context("handle_reorgs")?;
+
         // more output commitments
         let sync_status = await_tel!(
             context,
             tracer,
             "sync_status",
-            retry_res_ctx_timeout!(self.provider.op_provider.sync_status().await)
-        );
+            retry_res_ctx_timeout!(OpNode => self.provider.chain.sync_status().await)
+        )?;
         let safe_l2_number = sync_status["safe_l2"]["number"]
             .as_u64()
             .ok_or_else(|| anyhow::anyhow!("failed to parse safe_l2"))?
@@ -262,18 +517,41 @@ impl SyncAgent {
                     output_block_number,
                     self.deployment.output_block_span
                 )
-            );
+            )
+            .context("sync_outputs")?;
         }
 
         // load new proposals
+        let observed_factory_index = self
+            .cursor
+            .delayed_factory_indices
+            .iter()
+            .copied()
+            .min()
+            .unwrap_or(self.cursor.next_factory_index)
+            .min(self.cursor.next_factory_index);
+        let observed_log_block = self.cursor.next_log_block;
         let dispute_game_factory =
             IDisputeGameFactory::new(self.deployment.factory, self.provider.l1_provider.clone());
-        let game_count: u64 = dispute_game_factory
-            .gameCount()
-            .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
-            .await
-            .to();
-        let first_factory_index = self.cursor.next_factory_index;
+        let game_count: u64 = match self.log_sync_range {
+            Some(log_sync_range) => await_tel!(
+                context,
+                tracer,
+                "sync_logs",
+                self.sync_logs(&dispute_game_factory, log_sync_range)
+            )
+            .context("sync_logs")?,
+            None => dispute_game_factory
+                .gameCount()
+                .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
+                .await?
+                .to(),
+        };
+        // Report restored proposals as newly processed after resuming from a checkpoint
+        let first_factory_index = self
+            .restored_from
+            .take()
+            .unwrap_or(self.cursor.next_factory_index);
         let mut delayed_indices = Vec::new();
         while self.cursor.has_next(game_count) {
             let proposal_index = self.cursor.next_index();
@@ -288,7 +566,8 @@ impl SyncAgent {
                     if !l1_head.is_zero() {
                         self.sync_l1_head(contract, l1_head)
                             .with_context(context.clone())
-                            .await;
+                            .await
+                            .context("sync_l1_head")?;
                     }
                 }
                 Ok(ProposalSync::DELAYED(proposal_block)) => {
@@ -303,7 +582,8 @@ impl SyncAgent {
                     // Record batcher nonce at proposal l1 head if needed
                     self.sync_l1_head(contract, l1_head)
                         .with_context(context.clone())
-                        .await;
+                        .await
+                        .context("sync_l1_head")?;
                     // Update state according to proposal
                     let proposal = self
                         .proposals
@@ -341,6 +621,25 @@ impl SyncAgent {
         }
         // Keep delayed indices in cursor
         self.cursor.load_delayed_indices(delayed_indices);
+        // Record the l1 block at which the newly processed games were observed
+        if observed_factory_index < self.cursor.next_factory_index
+            || observed_log_block < self.cursor.next_log_block
+        {
+            let header = await_tel!(
+                context,
+                tracer,
+                "l1_header",
+                retry_res_ctx_timeout!(EthRpc => self
+                    .provider
+                    .chain
+                    .l1_header(BlockNumberOrTag::Latest)
+                    .await?
+                    .ok_or_else(|| anyhow!("Failed to fetch latest l1 block")))
+            )?;
+            if let Entry::Vacant(entry) = self.cursor.l1_observations.entry(header.number) {
+                entry.insert((header.hash, observed_factory_index));
+            }
+        }
 
         // update proposal resolutions
         loop {
@@ -368,9 +667,31 @@ impl SyncAgent {
                 bail!("Last unresolved proposal {last_unresolved_proposal_index} missing from database.");
             };
 
-            let resolved_at = last_unresolved_proposal
-                .fetch_resolved_at(&self.provider.l1_provider)
-                .await;
+            let resolved_at = match self.log_sync_range {
+                // resolutions are discovered through logs
+                Some(_) if last_unresolved_proposal.resolved_at != 0 => {
+                    last_unresolved_proposal.resolved_at
+                }
+                Some(_) => match self
+                    .cursor
+                    .log_resolutions
+                    .get(&last_unresolved_proposal.contract)
+                {
+                    None => 0,
+                    // fall back to querying games whose resolution log lacked a block timestamp
+                    Some(0) => {
+                        last_unresolved_proposal
+                            .fetch_resolved_at(&self.provider.l1_provider)
+                            .await?
+                    }
+                    Some(resolved_at) => *resolved_at,
+                },
+                None => {
+                    last_unresolved_proposal
+                        .fetch_resolved_at(&self.provider.l1_provider)
+                        .await?
+                }
+            };
 
             // stop at last unresolved proposal
             if resolved_at == 0 {
@@ -382,6 +703,7 @@ impl SyncAgent {
             }
             // update resolved status
             last_unresolved_proposal.resolved_at = resolved_at;
+            self.dirty_proposals.insert(last_unresolved_proposal_index);
             // move cursor forward
             self.cursor.last_resolved_game = last_unresolved_proposal_index;
             // Prune memory and storage
@@ -390,6 +712,15 @@ impl SyncAgent {
             }
         }
 
+        // follow upgrades of the kailua deployment discovered and resolved above
+        await_tel!(context, tracer, "sync_deployment", self.sync_deployment())
+            .context("sync_deployment")?;
+
+        // Persist synchronization state
+        if let Err(err) = self.checkpoint() {
+            error!("Failed to checkpoint sync state: {err:?}.");
+        }
+
         // Update sync telemetry
         if let Some(canonical_tip) = self.proposals.get(&self.cursor.canonical_proposal_tip) {
             self.telemetry.sync_canonical.record(
@@ -438,34 +769,523 @@ impl SyncAgent {
         Ok(proposals)
     }
 
-    pub async fn sync_l1_head(&mut self, proposal: Address, l1_head: B256) {
+    /// Scans the L1 logs for newly created Kailua games and resolutions of unresolved proposals.
+    /// Returns the factory game count known up to the last scanned block.
+    pub async fn sync_logs<P: Provider<N>, N: Network>(
+        &mut self,
+        dispute_game_factory: &IDisputeGameFactoryInstance<P, N>,
+        log_sync_range: u64,
+    ) -> anyhow::Result<u64> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SyncAgent::sync_logs"));
+
+        let latest_block = await_tel!(
+            context,
+            tracer,
+            "l1_header",
+            retry_res_ctx_timeout!(EthRpc => self
+                .provider
+                .chain
+                .l1_header(BlockNumberOrTag::Latest)
+                .await?
+                .ok_or_else(|| anyhow!("Failed to fetch latest l1 block")))
+        )?
+        .number;
+        let game_type = B256::left_padding_from(&KAILUA_GAME_TYPE.to_be_bytes());
+        while self.cursor.next_log_block <= latest_block {
+            let to_block =
+                latest_block.min(self.cursor.next_log_block + log_sync_range.saturating_sub(1));
+            info!(
+                "Scanning logs from block {} until block {to_block}",
+                self.cursor.next_log_block
+            );
+            // discover new games
+            let created_filter = Filter::new()
+                .address(*dispute_game_factory.address())
+                .event_signature(IDisputeGameFactory::DisputeGameCreated::SIGNATURE_HASH)
+                .topic2(game_type)
+                .from_block(self.cursor.next_log_block)
+                .to_block(to_block);
+            let created_logs = await_tel!(context, self.get_logs(&created_filter))?;
+            let created_games = created_logs
+                .into_iter()
+                .filter_map(|log| {
+                    match log.log_decode::<IDisputeGameFactory::DisputeGameCreated>() {
+                        Ok(event) => Some((
+                            event.inner.data.disputeProxy,
+                            B256::from(event.inner.data.rootClaim.0),
+                            log.block_timestamp,
+                        )),
+                        Err(err) => {
+                            error!("Failed to decode DisputeGameCreated log: {err:?}");
+                            None
+                        }
+                    }
+                })
+                .collect_vec();
+            // read the factory index and parent of all new games at once
+            let mut batch = MulticallBatch::default();
+            let game_reads = created_games
+                .iter()
+                .map(|(game_address, ..)| {
+                    (
+                        batch.add(*game_address, KailuaTournament::gameIndexCall {}),
+                        batch.add(*game_address, KailuaTournament::parentGameCall {}),
+                    )
+                })
+                .collect_vec();
+            let results = await_tel!(context, batch.execute(&self.provider))?;
+            for ((contract, root_claim, created_at), (game_index, parent)) in
+                created_games.into_iter().zip(game_reads)
+            {
+                let game_index: u64 = results.get(game_index)?.to();
+                if game_index >= self.cursor.next_factory_index {
+                    let logged_game = LoggedGame {
+                        contract,
+                        is_treasury: results.get(parent)? == contract,
+                        root_claim,
+                        created_at,
+                    };
+                    self.cursor.log_games.insert(game_index, logged_game);
+                }
+            }
+            // discover kailua implementation upgrades
+            if self.follow_upgrades {
+                let implementation_filter = Filter::new()
+                    .address(*dispute_game_factory.address())
+                    .event_signature(IDisputeGameFactory::ImplementationSet::SIGNATURE_HASH)
+                    .topic2(game_type)
+                    .from_block(self.cursor.next_log_block)
+                    .to_block(to_block);
+                let implementation_logs =
+                    await_tel!(context, self.get_logs(&implementation_filter))?;
+                for log in implementation_logs {
+                    // the implementation address is the first indexed topic
+                    match log.topics().get(1) {
+                        Some(topic) => self.latest_game_impl = Address::from_word(*topic),
+                        None => error!("Failed to decode ImplementationSet log: {log:?}"),
+                    }
+                }
+            }
+            // discover resolutions of loaded proposals
+            let unresolved = self
+                .proposals
+                .values()
+                .chain(self.log_proposals.values())
+                .filter(|p| p.resolved_at == 0)
+                .map(|p| p.contract)
+                .collect_vec();
+            if !unresolved.is_empty() {
+                let resolved_filter = Filter::new()
+                    .address(unresolved)
+                    .event_signature(KailuaTournament::Resolved::SIGNATURE_HASH)
+                    .from_block(self.cursor.next_log_block)
+                    .to_block(to_block);
+                let resolved_logs = await_tel!(context, self.get_logs(&resolved_filter))?;
+                for log in resolved_logs {
+                    self.cursor
+                        .log_resolutions
+                        .insert(log.address(), log.block_timestamp.unwrap_or_default());
+                }
+            }
+            // move cursor forward
+            self.cursor.next_log_block = to_block + 1;
+        }
+
+        Ok(self
+            .cursor
+            .log_games
+            .last_key_value()
+            .map(|(index, _)| index + 1)
+            .unwrap_or_default()
+            .max(self.cursor.next_factory_index))
+    }
+
+    /// Tracks the KailuaGame implementation set in the factory and schedules the hand over of
+    /// synchronization to the deployment of a new implementation once the current one has been
+    /// fully resolved.
+    pub async fn sync_deployment(&mut self) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SyncAgent::sync_deployment"));
+
+        // Log discovery tracks implementation changes through ImplementationSet events
+        if self.log_sync_range.is_none() || !self.follow_upgrades {
+            self.latest_game_impl =
+                IDisputeGameFactory::new(self.deployment.factory, &self.provider.l1_provider)
+                    .gameImpls(KAILUA_GAME_TYPE)
+                    .stall_with_context(context.clone(), "DisputeGameFactory::gameImpls")
+                    .await?;
+        }
+        if !self.follow_upgrades || self.latest_game_impl == self.deployment.game {
+            return Ok(());
+        }
+        if self.latest_game_impl.is_zero() {
+            warn!("KailuaGame implementation was unset in DisputeGameFactory.");
+            return Ok(());
+        }
+
+        // Load the deployment of the new implementation
+        let deployment = match self
+            .deployments
+            .values()
+            .find(|d| d.game == self.latest_game_impl)
+        {
+            Some(deployment) => deployment.clone(),
+            None => {
+                let deployment = await_tel_res!(
+                    context,
+                    SyncDeployment::load(&self.provider, &self.config, Some(self.latest_game_impl)),
+                    "SyncDeployment::load"
+                )?;
+                info!(
+                    "Found new deployment KailuaGame({}) of KailuaTreasury({}).",
+                    deployment.game, deployment.treasury
+                );
+                self.deployments
+                    .insert(deployment.treasury, deployment.clone());
+                deployment
+            }
+        };
+
+        // Upgrades of the game implementation under the same treasury apply only after all
+        // canonical proposals made before the upgrade was observed are resolved
+        if deployment.treasury == self.deployment.treasury {
+            let upgrade_tip = *self
+                .upgrade_tip
+                .get_or_insert(self.cursor.canonical_proposal_tip);
+            if self.cursor.last_resolved_game < upgrade_tip {
+                info!(
+                    "Awaiting resolution of proposal {upgrade_tip} before updating KailuaGame implementation to {}.",
+                    deployment.game
+                );
+                return Ok(());
+            }
+            Self::check_image_id(&deployment)?;
+            info!("Updating KailuaGame implementation to {}.", deployment.game);
+            self.upgrade_tip = None;
+            self.deployment = deployment;
+            return Ok(());
+        }
+
+        // Hand over only after the canonical chain of the current deployment is resolved
+        if self.cursor.canonical_proposal_tip != self.cursor.last_resolved_game {
+            info!(
+                "Awaiting resolution of proposal {} before handing over to KailuaTreasury({}).",
+                self.cursor.canonical_proposal_tip, deployment.treasury
+            );
+            return Ok(());
+        }
+        // Hand over only after the new treasury instance has been resolved
+        let last_resolved = KailuaTreasury::new(deployment.treasury, &self.provider.l1_provider)
+            .lastResolved()
+            .stall_with_context(context.clone(), "KailuaTreasury::lastResolved")
+            .await?;
+        if last_resolved.is_zero() {
+            info!(
+                "Awaiting initialization of KailuaTreasury({}) before handing over.",
+                deployment.treasury
+            );
+            return Ok(());
+        }
+
+        // proposals processed in this iteration are reported before handing over
+        info!(
+            "Scheduling hand over to KailuaTreasury({}).",
+            deployment.treasury
+        );
+        self.handover = Some(deployment);
+        Ok(())
+    }
+
+    /// Persists the state of the current deployment and starts synchronizing the given one.
+    pub async fn switch_deployment(&mut self, deployment: SyncDeployment) -> anyhow::Result<()> {
+        Self::check_image_id(&deployment)?;
+        warn!(
+            "Handing over from KailuaTreasury({}) to KailuaTreasury({}).",
+            self.deployment.treasury, deployment.treasury
+        );
+        if let Err(err) = self.checkpoint() {
+            error!("Failed to checkpoint sync state: {err:?}.");
+        }
+        self.db = Self::open_db(self.data_dir.clone(), &deployment)?;
+        self.deployment = deployment;
+        self.load_state(None).await
+    }
+
+    /// Fetches the L1 logs matching the given filter.
+    async fn get_logs(&self, filter: &Filter) -> anyhow::Result<Vec<Log>> {
+        retry_res_ctx_timeout!(EthRpc => self.provider.chain.l1_logs(filter).await).await
+    }
+
+    /// Checks whether the latest l1 observation is still canonical and rolls back the state
+    /// synchronized past the point of any detected reorg.
+    pub async fn handle_reorgs(&mut self) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("SyncAgent::handle_reorgs"));
+
+        // A canonical latest observation implies all earlier observations are canonical too
+        let observations = self
+            .cursor
+            .l1_observations
+            .iter()
+            .rev()
+            .map(|(number, (hash, index))| (*number, *hash, *index))
+            .collect_vec();
+        let mut rollback_index = None;
+        let mut fork_block = None;
+        for (number, hash, index) in observations {
+            if await_tel!(context, self.is_l1_block_canonical(number, hash))? {
+                fork_block = Some(number + 1);
+                break;
+            }
+            rollback_index = Some(index);
+        }
+        let Some(rollback_index) = rollback_index else {
+            return Ok(());
+        };
+        self.telemetry.sync_reorgs.add(1, &[]);
+
+        let Some(fork_block) = fork_block else {
+            warn!("Detected L1 reorg past all observed blocks.");
+            return await_tel!(context, self.reset());
+        };
+        warn!("Detected L1 reorg after block {}.", fork_block - 1);
+        await_tel!(context, self.rollback(rollback_index, fork_block))
+    }
+
+    async fn is_l1_block_canonical(&self, number: u64, hash: B256) -> anyhow::Result<bool> {
+        let tracer = tracer("kailua");
+        let context = opentelemetry::Context::current_with_span(
+            tracer.start("SyncAgent::is_l1_block_canonical"),
+        );
+
+        Ok(await_tel!(
+            context,
+            tracer,
+            "l1_header",
+            retry_res_ctx_timeout!(EthRpc => self
+                .provider
+                .chain
+                .l1_header(BlockNumberOrTag::Number(number))
+                .await)
+        )?
+        .is_some_and(|header| header.hash == hash))
+    }
+
+    /// Discards all proposals from the given factory index onwards and all l1 data from the given
+    /// l1 block onwards, restoring consistency of the remaining state.
+    pub async fn rollback(&mut self, index: u64, fork_block: u64) -> anyhow::Result<()> {
+        // Resynchronize from scratch if resolved state is affected
+        if index <= self.cursor.last_resolved_game {
+            warn!(
+                "Reorg affects last resolved proposal {}.",
+                self.cursor.last_resolved_game
+            );
+            return self.reset().await;
+        }
+        let Some(last_resolved_proposal) = self.proposals.get(&self.cursor.last_resolved_game)
+        else {
+            bail!(
+                "Last resolved proposal {} missing from database.",
+                self.cursor.last_resolved_game
+            );
+        };
+        if last_resolved_proposal
+            .fetch_resolved_at(&self.provider.l1_provider)
+            .await?
+            == 0
+        {
+            warn!(
+                "Resolution of proposal {} was reorged.",
+                self.cursor.last_resolved_game
+            );
+            return self.reset().await;
+        }
+        let last_resolved_output = last_resolved_proposal.output_block_number;
+
+        warn!("Rolling back to factory index {index} and l1 block {fork_block}.");
+        // Drop outputs above the safe head derived from the remaining l1 chain
+        let safe_head = match self
+            .provider
+            .chain
+            .safe_head_at_l1_block(fork_block - 1)
+            .await
+        {
+            Ok(safe_head) => safe_head,
+            Err(err) => {
+                warn!(
+                    "Failed to query safe head at l1 block {}: {err:?}. Dropping all outputs after \
+                    last resolved proposal.",
+                    fork_block - 1
+                );
+                last_resolved_output
+            }
+        };
+        self.cursor.last_output_index = self.drop_outputs(safe_head, self.cursor.last_output_index);
+        // Drop proposals
+        let dropped = self.proposals.split_off(&index);
+        for i in dropped.keys() {
+            if let Err(err) = self.db.delete(SyncCheckpoint::proposal_key(*i)) {
+                error!("Failed to delete proposal {i} from storage: {err:?}.");
+            }
+            self.dirty_proposals.remove(i);
+        }
+        info!("Dropped {} proposals.", dropped.len());
+        // Unlink dropped proposals
+        for proposal in self.proposals.values_mut() {
+            if proposal.successor.is_some_and(|s| s >= index) {
+                proposal.successor = None;
+                self.dirty_proposals.insert(proposal.index);
+            }
+            if proposal.children.range(index..).next().is_some() {
+                proposal.children.retain(|c| *c < index);
+                self.dirty_proposals.insert(proposal.index);
+            }
+        }
+        // Re-query unresolved proposals whose resolution may have been reorged
+        for proposal in self
+            .proposals
+            .range_mut(self.cursor.last_resolved_game + 1..)
+            .map(|(_, p)| p)
+        {
+            if proposal.resolved_at == 0
+                && !self.cursor.log_resolutions.contains_key(&proposal.contract)
+            {
+                continue;
+            }
+            let resolved_at = proposal
+                .fetch_resolved_at(&self.provider.l1_provider)
+                .await?;
+            if resolved_at == 0 {
+                if proposal.resolved_at != 0 {
+                    warn!("Resolution of proposal {} was reorged.", proposal.index);
+                }
+                self.cursor.log_resolutions.remove(&proposal.contract);
+            }
+            if proposal.resolved_at != resolved_at {
+                proposal.resolved_at = resolved_at;
+                self.dirty_proposals.insert(proposal.index);
+            }
+        }
+        self.eliminations.retain(|_, i| *i < index);
+        // Move cursor back
+        self.cursor.next_factory_index = self.cursor.next_factory_index.min(index);
+        self.cursor.delayed_factory_indices.retain(|i| *i < index);
+        if self.cursor.canonical_proposal_tip >= index {
+            self.cursor.canonical_proposal_tip = self
+                .proposals
+                .values()
+                .rev()
+                .find(|p| p.canonical.unwrap_or_default())
+                .map(|p| p.index)
+                .unwrap_or(self.cursor.last_resolved_game);
+        }
+        self.cursor.next_log_block = self.cursor.next_log_block.min(fork_block);
+        self.cursor.log_games.retain(|i, _| *i < index);
+        self.log_proposals.clear();
+        self.cursor
+            .log_resolutions
+            .retain(|a, _| self.proposals.values().any(|p| &p.contract == a));
+        self.cursor.l1_observations.retain(|n, _| *n < fork_block);
+        // Drop l1 heads that may no longer be part of the chain
+        for (_, (_, l1_head)) in self.l1_heads.range(fork_block..) {
+            self.l1_heads_inv.remove(l1_head);
+        }
+        self.l1_heads.retain(|n, _| *n < fork_block);
+
+        self.checkpoint()
+    }
+
+    /// Discards all synchronized proposal data and restarts from the last resolved proposal.
+    pub async fn reset(&mut self) -> anyhow::Result<()> {
+        warn!("Resetting synchronization state.");
+        let last_output_index = self.cursor.last_output_index;
+        self.cursor = SyncCursor::load(&self.deployment, &self.provider, None)
+            .await
+            .context("SyncCursor::load")?;
+        // Drop outputs that may have been derived from reorged l1 blocks
+        self.drop_outputs(self.cursor.last_output_index, last_output_index);
+        self.proposals.clear();
+        self.eliminations.clear();
+        self.l1_heads.clear();
+        self.l1_heads_inv.clear();
+        self.dirty_proposals.clear();
+        self.log_proposals.clear();
+        SyncCheckpoint::clear(&self.db).context("SyncCheckpoint::clear")
+    }
+
+    /// Discards the output commitments after the given l2 block up to the given last output index
+    /// from memory and storage, returning the last output index retained.
+    fn drop_outputs(&mut self, l2_block: u64, mut last_output_index: u64) -> u64 {
+        let mut commitments = 0;
+        while last_output_index > l2_block {
+            self.outputs.remove(&last_output_index);
+            if let Err(err) = self.db.delete(last_output_index.to_be_bytes()) {
+                error!(
+                    "Failed to delete output commitment {last_output_index} from storage: {err:?}."
+                );
+            }
+            last_output_index = last_output_index.saturating_sub(self.deployment.output_block_span);
+            commitments += 1;
+        }
+        if commitments > 0 {
+            info!("Dropped {commitments} output commitments after l2 block {l2_block}.");
+        }
+        last_output_index
+    }
+
+    pub async fn sync_l1_head(&mut self, proposal: Address, l1_head: B256) -> anyhow::Result<()> {
         let tracer = tracer("kailua");
         let context = opentelemetry::Context::current_with_span(
             tracer.start("SyncAgent::sync_batcher_nonce"),
         );
 
-        let block = loop {
-            if let Some(block) = await_tel!(
+        let header = loop {
+            if let Some(header) = await_tel!(
                 context,
                 tracer,
-                "get_block_by_hash",
-                retry_res_ctx_timeout!(self
+                "l1_header_by_hash",
+                retry_res_ctx_timeout!(EthRpc => self
                     .provider
-                    .l1_provider
-                    .get_block_by_hash(l1_head)
-                    .await
-                    .context("get_block_by_hash"))
-            ) {
-                break block;
+                    .chain
+                    .l1_header_by_hash(l1_head)
+                    .await)
+            )? {
+                break header;
             }
         };
 
-        if let Entry::Vacant(vacancy) = self.l1_heads.entry(block.header.number) {
+        if let Entry::Vacant(vacancy) = self.l1_heads.entry(header.number) {
             vacancy.insert((proposal, l1_head));
         }
         if let Entry::Vacant(vacancy) = self.l1_heads_inv.entry(l1_head) {
-            vacancy.insert((proposal, block.header.number));
+            vacancy.insert((proposal, header.number));
         }
+        Ok(())
+    }
+
+    /// Loads the next games discovered through logs starting from the given factory index
+    /// together, so that their contract reads share the same multicalls.
+    pub async fn load_log_proposals(&mut self, index: u64) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context = opentelemetry::Context::current_with_span(
+            tracer.start("SyncAgent::load_log_proposals"),
+        );
+
+        let games = self
+            .cursor
+            .log_games
+            .range(index..)
+            .filter(|(i, _)| !self.log_proposals.contains_key(i))
+            .take(LOG_PROPOSALS_BATCH)
+            .map(|(i, game)| (*i, *game))
+            .collect::<BTreeMap<_, _>>();
+        let proposals = await_tel!(context, Proposal::load_logged(&self.provider, &games))?;
+        self.log_proposals.extend(proposals);
+        Ok(())
     }
 
     pub async fn sync_proposal<P: Provider<N>, N: Network>(
@@ -478,23 +1298,48 @@ impl SyncAgent {
             opentelemetry::Context::current_with_span(tracer.start("SyncAgent::sync_proposal"));
 
         // process game
-        let gameAtIndexReturn {
-            gameType_: game_type,
-            proxy_: game_address,
-            ..
-        } = dispute_game_factory
-            .gameAtIndex(U256::from(index))
-            .stall_with_context(context.clone(), "DisputeGameFactory::gameAtIndex")
-            .await;
-        // skip entries for other game types
-        if game_type != KAILUA_GAME_TYPE {
-            info!("Skipping proposal of different game type {game_type} at factory index {index}");
-            return Ok(ProposalSync::IGNORED(game_address, B256::ZERO));
-        }
-        info!("Processing tournament {index} at {game_address}");
-        let mut proposal = Proposal::load(&self.provider, game_address)
-            .with_context(context.clone())
-            .await?;
+        let mut proposal = if self.log_sync_range.is_some() {
+            // games absent from the scanned logs are not of the kailua type
+            if !self.cursor.log_games.contains_key(&index) {
+                return Ok(ProposalSync::IGNORED(Address::ZERO, B256::ZERO));
+            }
+            if !self.log_proposals.contains_key(&index) {
+                await_tel!(context, self.load_log_proposals(index))
+                    .context("load_log_proposals")?;
+            }
+            let mut proposal = self
+                .log_proposals
+                .remove(&index)
+                .ok_or_else(|| anyhow!("Failed to load logged proposal {index}"))?;
+            info!("Processing tournament {index} at {}", proposal.contract);
+            // apply resolutions observed in logs since the proposal was loaded
+            if let Some(resolved_at) = self.cursor.log_resolutions.get(&proposal.contract) {
+                if proposal.resolved_at == 0 && *resolved_at != 0 {
+                    proposal.resolved_at = *resolved_at;
+                }
+            }
+            proposal
+        } else {
+            let gameAtIndexReturn {
+                gameType_: game_type,
+                proxy_: game_address,
+                ..
+            } = dispute_game_factory
+                .gameAtIndex(U256::from(index))
+                .stall_with_context(context.clone(), "DisputeGameFactory::gameAtIndex")
+                .await?;
+            // skip entries for other game types
+            if game_type != KAILUA_GAME_TYPE {
+                info!(
+                    "Skipping proposal of different game type {game_type} at factory index {index}"
+                );
+                return Ok(ProposalSync::IGNORED(game_address, B256::ZERO));
+            }
+            info!("Processing tournament {index} at {game_address}");
+            Proposal::load(&self.provider, game_address)
+                .with_context(context.clone())
+                .await?
+        };
         // Skip proposals unrelated to current run
         if proposal.treasury != self.deployment.treasury {
             info!("Skipping proposal for different deployment.");
@@ -513,7 +1358,7 @@ impl SyncAgent {
             let elimination_round: u64 = treasury_contract
                 .eliminationRound(proposal.proposer)
                 .stall_with_context(context.clone(), "KailuaTreasury::eliminationRound")
-                .await
+                .await?
                 .to();
             if elimination_round > 0 {
                 vacancy.insert(elimination_round);
@@ -565,11 +1410,14 @@ impl SyncAgent {
         if is_proposal_canonical {
             if let Some(parent) = self.proposals.get_mut(&proposal.parent) {
                 parent.successor = Some(proposal.index);
+                self.dirty_proposals.insert(parent.index);
             }
         }
 
         // Store proposal and return inclusion
         let result = proposal.as_success();
+        self.dirty_proposals.insert(proposal.index);
+        journal().register_proposal(proposal.contract, proposal.index);
         self.proposals.insert(proposal.index, proposal);
         Ok(result)
     }
@@ -702,6 +1550,7 @@ impl SyncAgent {
                     proposal.index, parent.index
                 );
             }
+            self.dirty_proposals.insert(parent.index);
         }
 
         // Scope for immutable access to parent
@@ -733,7 +1582,12 @@ impl SyncAgent {
         self.outputs.get(&block_number).cloned()
     }
 
-    pub async fn sync_outputs(&mut self, mut start: u64, end: u64, step: u64) {
+    pub async fn sync_outputs(
+        &mut self,
+        mut start: u64,
+        end: u64,
+        step: u64,
+    ) -> anyhow::Result<()> {
         while start <= end {
             // perform at most 1024 tasks at a time
             let end = end.min(start + 128 * step);
@@ -753,16 +1607,18 @@ impl SyncAgent {
                 .step_by(step as usize)
                 .filter(|i| !self.outputs.contains_key(i))
                 .map(|i| {
-                    let provider = self.provider.op_provider.clone();
+                    let provider = self.provider.clone();
                     Box::pin(async move {
-                        (
-                            i,
-                            retry_res_timeout!(provider.output_at_block(i).await).await,
-                        )
+                        retry_res_timeout!(OpNode => provider.output_at_block(i).await)
+                            .await
+                            .map(|output| (i, output))
                     })
                 })
                 .collect_vec();
-            let outputs = join_all(outputs).await;
+            let outputs = join_all(outputs)
+                .await
+                .into_iter()
+                .collect::<anyhow::Result<Vec<_>>>()?;
 
             if !outputs.is_empty() {
                 info!("Fetched {} outputs.", outputs.len());
@@ -781,5 +1637,371 @@ impl SyncAgent {
             // jump forward
             start = end + step;
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::memory::fixtures::*;
+    use crate::provider::memory::{InMemoryChain, ScriptedProposal};
+    use alloy::primitives::{address, Bytes, LogData};
+    use serde_json::{json, Value};
+
+    const UPGRADED_GAME: Address = address!("0x0000000000000000000000000000000000000005");
+    const UPGRADED_TREASURY: Address = address!("0x0000000000000000000000000000000000000006");
+    const NEXT_GAME: Address = address!("0x0000000000000000000000000000000000000013");
+    const UPGRADED_ANCHOR: Address = address!("0x0000000000000000000000000000000000000014");
+    const FOREIGN_GAME: Address = address!("0x0000000000000000000000000000000000000020");
+
+    /// Returns the synchronized state that must not depend on how proposals were discovered.
+    fn synced_state(agent: &SyncAgent) -> Value {
+        json!({
+            "proposals": agent.proposals,
+            "eliminations": agent.eliminations,
+            "l1_heads": agent.l1_heads,
+            "canonical_proposal_tip": agent.cursor.canonical_proposal_tip,
+            "last_resolved_game": agent.cursor.last_resolved_game,
+            "next_factory_index": agent.cursor.next_factory_index,
+        })
+    }
+
+    /// Registers a game of another type in the factory of the deployment.
+    fn script_foreign_game(chain: &InMemoryChain, deployment: &SyncDeployment, index: u64) {
+        chain.mine_l1_block();
+        chain.mock_call(
+            deployment.factory,
+            IDisputeGameFactory::gameAtIndexCall::new((U256::from(index),)),
+            (KAILUA_GAME_TYPE + 1, 0u64, FOREIGN_GAME),
+        );
+        chain.mock_call(
+            deployment.factory,
+            IDisputeGameFactory::gameCountCall {},
+            U256::from(index + 1),
+        );
+        chain.mock_log(
+            deployment.factory,
+            LogData::new_unchecked(
+                vec![
+                    IDisputeGameFactory::DisputeGameCreated::SIGNATURE_HASH,
+                    FOREIGN_GAME.into_word(),
+                    B256::from(U256::from(KAILUA_GAME_TYPE + 1)),
+                    B256::ZERO,
+                ],
+                Bytes::new(),
+            ),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_logs_matches_factory() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        script_foreign_game(&chain, &deployment, 1);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 2, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        let faulty_proposal = ScriptedProposal {
+            output_root: B256::repeat_byte(0xff),
+            ..game_proposal(&chain, &deployment, FAULTY_GAME, 3, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+
+        let factory_dir = tempfile::tempdir().unwrap();
+        let mut factory_agent = sync_agent(&chain, factory_dir.path(), None).await;
+        let logs_dir = tempfile::tempdir().unwrap();
+        let mut logs_agent = sync_agent(&chain, logs_dir.path(), Some(3)).await;
+
+        assert_eq!(factory_agent.sync(0, None).await.unwrap(), vec![0, 2, 3]);
+        assert_eq!(logs_agent.sync(0, None).await.unwrap(), vec![0, 2, 3]);
+        assert_eq!(synced_state(&factory_agent), synced_state(&logs_agent));
+        // only kailua games are discovered through logs
+        assert!(!logs_agent.cursor.log_games.contains_key(&1));
+
+        // resolutions are discovered alike
+        chain.resolve_proposal(&deployment, HONEST_GAME, true);
+        assert!(factory_agent.sync(0, None).await.unwrap().is_empty());
+        assert!(logs_agent.sync(0, None).await.unwrap().is_empty());
+        assert_eq!(factory_agent.cursor.last_resolved_game, 2);
+        assert_eq!(synced_state(&factory_agent), synced_state(&logs_agent));
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0, 1]);
+        let state = synced_state(&agent);
+        drop(agent);
+
+        // restored proposals are reported as newly processed after restarting
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.cursor.next_factory_index, 2);
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0, 1]);
+        assert_eq!(synced_state(&agent), state);
+
+        // synchronization continues past the checkpoint
+        let next_proposal = child_proposal(
+            &chain,
+            &deployment,
+            &honest_proposal,
+            NEXT_GAME,
+            2,
+            HONEST_PROPOSER,
+        );
+        chain.script_proposal(&deployment, &next_proposal).unwrap();
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![2]);
+        assert_eq!(agent.proposals[&1].successor, Some(2));
+        assert_eq!(agent.cursor.canonical_proposal_tip, 2);
+        drop(agent);
+
+        // checkpoints inconsistent with the chain are discarded
+        chain.mock_call(
+            deployment.factory,
+            IDisputeGameFactory::gameAtIndexCall::new((U256::from(2),)),
+            (KAILUA_GAME_TYPE, 0u64, FOREIGN_GAME),
+        );
+        let agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.cursor.next_factory_index, 0);
+        assert!(agent.proposals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reorg_rollback() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0]);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![1]);
+        assert_eq!(agent.cursor.l1_observations.len(), 2);
+
+        // a different proposal takes the place of the reorged one
+        chain.reorg_l1(1);
+        let next_proposal = game_proposal(&chain, &deployment, NEXT_GAME, 1, FAULTY_PROPOSER);
+        chain.script_proposal(&deployment, &next_proposal).unwrap();
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![1]);
+        assert_eq!(agent.proposals.len(), 2);
+        assert_eq!(agent.proposals[&1].contract, NEXT_GAME);
+        assert_eq!(agent.proposals[&0].successor, Some(1));
+        assert_eq!(agent.cursor.canonical_proposal_tip, 1);
+        let (l1_head, ..) = chain.l1_head();
+        assert!(agent.cursor.l1_observations.contains_key(&l1_head));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_requeries_resolutions() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0]);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        let faulty_proposal = ScriptedProposal {
+            output_root: B256::repeat_byte(0xff),
+            ..game_proposal(&chain, &deployment, FAULTY_GAME, 2, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+        chain.resolve_proposal(&deployment, FAULTY_GAME, false);
+        let (resolution_block, _, resolved_at) = chain.l1_head();
+        let next_proposal = child_proposal(
+            &chain,
+            &deployment,
+            &honest_proposal,
+            NEXT_GAME,
+            3,
+            HONEST_PROPOSER,
+        );
+        chain.script_proposal(&deployment, &next_proposal).unwrap();
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![1, 2, 3]);
+        assert_eq!(agent.proposals[&2].resolved_at, resolved_at);
+
+        // resolutions prior to the fork are retained
+        agent.rollback(3, resolution_block + 1).await.unwrap();
+        assert_eq!(agent.cursor.next_factory_index, 3);
+        assert!(!agent.proposals.contains_key(&3));
+        assert_eq!(agent.proposals[&1].successor, None);
+        assert_eq!(agent.proposals[&2].resolved_at, resolved_at);
+
+        // resolutions in reorged blocks are reverted
+        chain.reorg_l1(2);
+        chain.mock_call(FAULTY_GAME, KailuaGame::resolvedAtCall {}, 0u64);
+        chain.mock_call(FAULTY_GAME, KailuaGame::statusCall {}, 0u8);
+        agent.rollback(3, resolution_block).await.unwrap();
+        assert_eq!(agent.proposals[&2].resolved_at, 0);
+        assert_eq!(agent.cursor.last_resolved_game, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_refetches_outputs() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0]);
+        let last_output_index = agent.cursor.last_output_index;
+        assert!(agent.outputs.contains_key(&last_output_index));
+
+        // outputs above the safe head derived from the remaining l1 chain are dropped
+        let (l1_head, ..) = chain.l1_head();
+        let safe_head = last_output_index - 2;
+        chain.set_safe_head_at_l1(l1_head, safe_head);
+        chain.set_output(last_output_index, B256::repeat_byte(0xee));
+        agent
+            .rollback(agent.cursor.next_factory_index, l1_head + 1)
+            .await
+            .unwrap();
+        assert_eq!(agent.cursor.last_output_index, safe_head);
+        assert!(agent.outputs.contains_key(&safe_head));
+        assert!(!agent.outputs.contains_key(&last_output_index));
+        assert!(agent
+            .db
+            .get(last_output_index.to_be_bytes())
+            .unwrap()
+            .is_none());
+
+        // dropped output roots are fetched again
+        agent.sync(0, None).await.unwrap();
+        assert_eq!(agent.outputs[&last_output_index], B256::repeat_byte(0xee));
+    }
+
+    #[tokio::test]
+    async fn test_game_upgrade() {
+        for log_sync_range in [None, Some(3)] {
+            let deployment = deployment();
+            let chain = anchored_chain(&deployment);
+            let honest_proposal =
+                game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+            chain
+                .script_proposal(&deployment, &honest_proposal)
+                .unwrap();
+            let data_dir = tempfile::tempdir().unwrap();
+            let mut agent = sync_agent(&chain, data_dir.path(), log_sync_range).await;
+            assert_eq!(agent.sync(0, None).await.unwrap(), vec![0, 1]);
+
+            // the upgrade does not apply to proposals made before it
+            let upgraded = SyncDeployment {
+                game: UPGRADED_GAME,
+                timeout: 2 * deployment.timeout,
+                ..deployment.clone()
+            };
+            chain.mine_l1_block();
+            chain.script_game(&upgraded);
+            chain.script_game_impl(deployment.factory, upgraded.game);
+            assert!(agent.sync(0, None).await.unwrap().is_empty());
+            assert_eq!(agent.deployment.game, GAME);
+
+            // proposals made after the upgrade do not delay it
+            let next_proposal = child_proposal(
+                &chain,
+                &deployment,
+                &honest_proposal,
+                NEXT_GAME,
+                2,
+                HONEST_PROPOSER,
+            );
+            chain.script_proposal(&upgraded, &next_proposal).unwrap();
+            assert_eq!(agent.sync(0, None).await.unwrap(), vec![2]);
+            assert_eq!(agent.deployment.game, GAME);
+
+            // the upgrade applies in the same iteration the last prior proposal resolves
+            chain.resolve_proposal(&deployment, HONEST_GAME, true);
+            assert!(agent.sync(0, None).await.unwrap().is_empty());
+            assert_eq!(agent.cursor.last_resolved_game, 1);
+            assert_eq!(agent.deployment.game, UPGRADED_GAME);
+            assert_eq!(agent.deployment.timeout, upgraded.timeout);
+            assert!(agent.proposals.contains_key(&2));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_treasury_handover() {
+        for log_sync_range in [None, Some(3)] {
+            let deployment = deployment();
+            let chain = anchored_chain(&deployment);
+            let honest_proposal =
+                game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+            chain
+                .script_proposal(&deployment, &honest_proposal)
+                .unwrap();
+            let data_dir = tempfile::tempdir().unwrap();
+            let mut agent = sync_agent(&chain, data_dir.path(), log_sync_range).await;
+            assert_eq!(agent.sync(0, None).await.unwrap(), vec![0, 1]);
+
+            // the new treasury is anchored after the canonical tip of the current one
+            let upgraded = SyncDeployment {
+                treasury: UPGRADED_TREASURY,
+                game: UPGRADED_GAME,
+                ..deployment.clone()
+            };
+            chain.mine_l1_block();
+            chain.script_game(&upgraded);
+            chain.script_game_impl(deployment.factory, upgraded.game);
+            let upgraded_anchor = ScriptedProposal {
+                contract: UPGRADED_ANCHOR,
+                index: 2,
+                parent: 2,
+                parent_contract: UPGRADED_ANCHOR,
+                ..honest_proposal.clone()
+            };
+            chain.script_proposal(&upgraded, &upgraded_anchor).unwrap();
+            chain.resolve_proposal(&upgraded, UPGRADED_ANCHOR, true);
+
+            // hand over only after the canonical tip is resolved
+            assert!(agent.sync(0, None).await.unwrap().is_empty());
+            assert_eq!(agent.deployment.treasury, TREASURY);
+            assert_eq!(agent.cursor.next_factory_index, 3);
+
+            // proposals processed while the canonical tip resolves are reported before handing over
+            chain.resolve_proposal(&deployment, HONEST_GAME, true);
+            let faulty_proposal = ScriptedProposal {
+                output_root: B256::repeat_byte(0xff),
+                ..child_proposal(
+                    &chain,
+                    &deployment,
+                    &honest_proposal,
+                    FAULTY_GAME,
+                    3,
+                    FAULTY_PROPOSER,
+                )
+            };
+            chain
+                .script_proposal(&deployment, &faulty_proposal)
+                .unwrap();
+            assert_eq!(agent.sync(0, None).await.unwrap(), vec![3]);
+            assert_eq!(agent.deployment.treasury, TREASURY);
+            assert_eq!(agent.cursor.last_resolved_game, 1);
+            assert_eq!(agent.proposals[&3].canonical, Some(false));
+            // the processed state is checkpointed before handing over
+            let (checkpoint, _) = SyncCheckpoint::read(&agent.db).unwrap().unwrap();
+            assert_eq!(checkpoint.cursor.next_factory_index, 4);
+
+            // proposals of the new treasury are synchronized from its anchor
+            assert_eq!(agent.sync(0, None).await.unwrap(), vec![2]);
+            assert_eq!(agent.deployment.treasury, UPGRADED_TREASURY);
+            assert_eq!(agent.cursor.last_resolved_game, 2);
+            assert_eq!(agent.proposals.keys().copied().collect_vec(), vec![2]);
+            assert_eq!(agent.cursor.canonical_proposal_tip, 2);
+        }
     }
 }
@@ -0,0 +1,218 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cursor::SyncCursor;
+use crate::deployment::SyncDeployment;
+use crate::proposal::Proposal;
+use crate::provider::SyncProvider;
+use crate::stall::Stall;
+use crate::{await_tel, retry_res_ctx_timeout};
+use alloy::primitives::{Address, B256, U256};
+use anyhow::{bail, Context as AnyhowContext};
+use kailua_contracts::*;
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use opentelemetry::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Database key of the serialized checkpoint
+pub const CHECKPOINT_KEY: &[u8] = b"checkpoint";
+/// Database key prefix of serialized proposals
+pub const PROPOSAL_KEY_PREFIX: &[u8] = b"proposal/";
+
+/// A persistent snapshot of the in-memory synchronization state
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncCheckpoint {
+    /// Address of the game implementation the state was synchronized against
+    pub game: Address,
+    /// Pointers to the latest synchronized items
+    pub cursor: SyncCursor,
+    /// Proposer elimination rounds
+    pub eliminations: BTreeMap<Address, u64>,
+    /// Available l1-heads for derivation
+    pub l1_heads: BTreeMap<u64, (Address, B256)>,
+    /// Available l1-heads for derivation (inverse map)
+    pub l1_heads_inv: BTreeMap<B256, (Address, u64)>,
+}
+
+impl SyncCheckpoint {
+    pub fn proposal_key(index: u64) -> Vec<u8> {
+        [PROPOSAL_KEY_PREFIX, &index.to_be_bytes()].concat()
+    }
+
+    /// Writes the checkpoint alongside the given proposals in a single atomic batch.
+    pub fn write<'a>(
+        &self,
+        db: &rocksdb::DB,
+        proposals: impl IntoIterator<Item = &'a Proposal>,
+    ) -> anyhow::Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        for proposal in proposals {
+            batch.put(
+                Self::proposal_key(proposal.index),
+                serde_json::to_vec(proposal).context("serde_json::to_vec")?,
+            );
+        }
+        batch.put(
+            CHECKPOINT_KEY,
+            serde_json::to_vec(self).context("serde_json::to_vec")?,
+        );
+        db.write(batch).context("rocksdb::DB::write")
+    }
+
+    /// Reads the last checkpoint and all persisted proposals, if any.
+    pub fn read(db: &rocksdb::DB) -> anyhow::Result<Option<(Self, BTreeMap<u64, Proposal>)>> {
+        let Some(checkpoint) = db.get(CHECKPOINT_KEY).context("rocksdb::DB::get")? else {
+            return Ok(None);
+        };
+        let checkpoint: Self =
+            serde_json::from_slice(&checkpoint).context("serde_json::from_slice")?;
+        let mut proposals = BTreeMap::new();
+        for entry in db.prefix_iterator(PROPOSAL_KEY_PREFIX) {
+            let (key, value) = entry.context("rocksdb::DB::prefix_iterator")?;
+            if !key.starts_with(PROPOSAL_KEY_PREFIX) {
+                break;
+            }
+            let proposal: Proposal =
+                serde_json::from_slice(&value).context("serde_json::from_slice")?;
+            proposals.insert(proposal.index, proposal);
+        }
+        Ok(Some((checkpoint, proposals)))
+    }
+
+    /// Removes the checkpoint and all persisted proposals.
+    pub fn clear(db: &rocksdb::DB) -> anyhow::Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        batch.delete(CHECKPOINT_KEY);
+        for entry in db.prefix_iterator(PROPOSAL_KEY_PREFIX) {
+            let (key, _) = entry.context("rocksdb::DB::prefix_iterator")?;
+            if !key.starts_with(PROPOSAL_KEY_PREFIX) {
+                break;
+            }
+            batch.delete(key);
+        }
+        db.write(batch).context("rocksdb::DB::write")
+    }
+
+    /// Checks that the restored state is consistent with itself and the current chain state.
+    pub async fn verify(
+        &self,
+        proposals: &BTreeMap<u64, Proposal>,
+        provider: &SyncProvider,
+        deployment: &SyncDeployment,
+    ) -> anyhow::Result<()> {
+        let tracer = tracer("kailua");
+        let context = Context::current_with_span(tracer.start("SyncCheckpoint::verify"));
+
+        if self.game != deployment.game {
+            bail!(
+                "Checkpoint game implementation {} does not match deployment {}.",
+                self.game,
+                deployment.game
+            );
+        }
+        let Some(last_resolved) = proposals.get(&self.cursor.last_resolved_game) else {
+            bail!(
+                "Last resolved proposal {} missing from checkpoint.",
+                self.cursor.last_resolved_game
+            );
+        };
+        let Some(canonical_tip) = proposals.get(&self.cursor.canonical_proposal_tip) else {
+            bail!(
+                "Canonical proposal tip {} missing from checkpoint.",
+                self.cursor.canonical_proposal_tip
+            );
+        };
+        if proposals
+            .values()
+            .any(|p| p.treasury != deployment.treasury)
+        {
+            bail!("Checkpoint contains proposals of a different deployment.");
+        }
+
+        // Check that the stored games are still at their factory indices
+        let dispute_game_factory =
+            IDisputeGameFactory::new(deployment.factory, &provider.l1_provider);
+        for proposal in [last_resolved, canonical_tip] {
+            let game_address = dispute_game_factory
+                .gameAtIndex(U256::from(proposal.index))
+                .stall_with_context(context.clone(), "DisputeGameFactory::gameAtIndex")
+                .await?
+                .proxy_;
+            if game_address != proposal.contract {
+                bail!(
+                    "Proposal {} at {} no longer found in factory (found {game_address}).",
+                    proposal.index,
+                    proposal.contract
+                );
+            }
+        }
+
+        // Check that the last resolved proposal has not been rolled back
+        if last_resolved
+            .fetch_resolved_at(&provider.l1_provider)
+            .await?
+            == 0
+        {
+            bail!(
+                "Last resolved proposal {} is unresolved on chain.",
+                last_resolved.index
+            );
+        }
+        let treasury_last_resolved =
+            KailuaTreasury::new(deployment.treasury, &provider.l1_provider)
+                .lastResolved()
+                .stall_with_context(context.clone(), "KailuaTreasury::lastResolved")
+                .await?;
+        let treasury_last_resolved_index: u64 =
+            KailuaTournament::new(treasury_last_resolved, &provider.l1_provider)
+                .gameIndex()
+                .stall_with_context(context.clone(), "KailuaTournament::gameIndex")
+                .await?
+                .to();
+        if treasury_last_resolved_index < self.cursor.last_resolved_game {
+            bail!(
+                "Treasury last resolved proposal {treasury_last_resolved_index} precedes checkpoint {}.",
+                self.cursor.last_resolved_game
+            );
+        }
+
+        // Check that the canonical tip l1 head is still part of the chain
+        let canonical_tip_l1_head = await_tel!(
+            context,
+            tracer,
+            "l1_header_by_hash",
+            retry_res_ctx_timeout!(EthRpc => provider
+                .chain
+                .l1_header_by_hash(canonical_tip.l1_head)
+                .await)
+        )?;
+        if canonical_tip_l1_head.is_none() {
+            bail!(
+                "Canonical proposal tip l1 head {} no longer available.",
+                canonical_tip.l1_head
+            );
+        }
+
+        info!(
+            "Verified checkpoint with {} proposals (last resolved: {}, canonical tip: {}).",
+            proposals.len(),
+            self.cursor.last_resolved_game,
+            self.cursor.canonical_proposal_tip
+        );
+        Ok(())
+    }
+}
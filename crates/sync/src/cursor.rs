@@ -16,15 +16,18 @@ use crate::deployment::SyncDeployment;
 use crate::proposal::Proposal;
 use crate::provider::SyncProvider;
 use crate::stall::Stall;
-use alloy::primitives::Address;
-use anyhow::bail;
+use crate::{await_tel, retry_res_ctx_timeout};
+use alloy::primitives::{Address, B256};
+use anyhow::{anyhow, bail};
 use kailua_contracts::*;
 use opentelemetry::global::tracer;
-use opentelemetry::trace::{TraceContextExt, Tracer};
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use opentelemetry::Context;
-use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 
 /// A collection of pointers to statefully track synchrony information
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct SyncCursor {
     /// Factory game index of the latest canonical proposal
     pub canonical_proposal_tip: u64,
@@ -36,6 +39,27 @@ pub struct SyncCursor {
     pub last_output_index: u64,
     /// Index of the last proposal resolved on chain
     pub last_resolved_game: u64,
+    /// Number of the next L1 block whose logs should be scanned
+    pub next_log_block: u64,
+    /// Kailua games discovered through factory logs by index
+    pub log_games: BTreeMap<u64, LoggedGame>,
+    /// Resolution timestamps of kailua games observed in logs
+    pub log_resolutions: BTreeMap<Address, u64>,
+    /// L1 block hashes at which games starting from a factory index were observed by block number
+    pub l1_observations: BTreeMap<u64, (B256, u64)>,
+}
+
+/// A Kailua game whose creation was observed in the factory logs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedGame {
+    /// Address of the contract instance
+    pub contract: Address,
+    /// Whether the game is a treasury instance
+    pub is_treasury: bool,
+    /// Root claim emitted with the creation event
+    pub root_claim: B256,
+    /// Timestamp of the creation block, if reported alongside the log
+    pub created_at: Option<u64>,
 }
 
 impl SyncCursor {
@@ -68,7 +92,7 @@ impl SyncCursor {
                 KailuaTreasury::new(deployment.treasury, &provider.l1_provider)
                     .lastResolved()
                     .stall_with_context(context.clone(), "KailuaTreasury::lastResolved")
-                    .await
+                    .await?
             }
         };
 
@@ -81,7 +105,7 @@ impl SyncCursor {
         let anchor_treasury = anchor
             .KAILUA_TREASURY()
             .stall_with_context(context.clone(), "KailuaTournament::KAILUA_TREASURY")
-            .await;
+            .await?;
         if anchor_treasury != deployment.treasury {
             bail!("Anchor is not part of the correct deployment.");
         }
@@ -89,14 +113,14 @@ impl SyncCursor {
         let anchor_index: u64 = anchor
             .gameIndex()
             .stall_with_context(context.clone(), "KailuaTournament::gameIndex")
-            .await
+            .await?
             .to();
 
         let Some(true) = Proposal::parse_finality(
             anchor
                 .status()
                 .stall_with_context(context.clone(), "KailuaTournament::status")
-                .await,
+                .await?,
         )?
         else {
             bail!("Anchor game is not finalized.");
@@ -105,13 +129,29 @@ impl SyncCursor {
         let anchor_block_height: u64 = anchor
             .l2BlockNumber()
             .stall_with_context(context.clone(), "KailuaTournament::l2BlockNumber")
-            .await
+            .await?
             .to();
 
         let parent_address = anchor
             .parentGame()
             .stall_with_context(context.clone(), "KailuaTournament::parentGame")
-            .await;
+            .await?;
+
+        // the factory sets the l1 head to the parent of the block the game was created in
+        let anchor_l1_head = anchor
+            .l1Head()
+            .stall_with_context(context.clone(), "KailuaTournament::l1Head")
+            .await?;
+        let anchor_l1_header = await_tel!(
+            context,
+            tracer,
+            "l1_header_by_hash",
+            retry_res_ctx_timeout!(EthRpc => provider
+                .chain
+                .l1_header_by_hash(anchor_l1_head)
+                .await?
+                .ok_or_else(|| anyhow!("Failed to fetch anchor l1 head block")))
+        )?;
 
         let last_output_index = if parent_address == anchor_address {
             // get block height of treasury instance
@@ -127,6 +167,10 @@ impl SyncCursor {
             delayed_factory_indices: VecDeque::new(),
             last_output_index,
             last_resolved_game: anchor_index,
+            next_log_block: anchor_l1_header.number + 1,
+            log_games: Default::default(),
+            log_resolutions: Default::default(),
+            l1_observations: Default::default(),
         })
     }
 }
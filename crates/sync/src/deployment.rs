@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::multicall::MulticallBatch;
 use crate::provider::SyncProvider;
 use crate::stall::Stall;
 use crate::KAILUA_GAME_TYPE;
@@ -19,7 +20,7 @@ use alloy::primitives::{Address, B256};
 use kailua_contracts::*;
 use kona_genesis::RollupConfig;
 use opentelemetry::global::tracer;
-use opentelemetry::trace::{TraceContextExt, Tracer};
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use opentelemetry::Context;
 use std::process::exit;
 use tracing::{error, info, warn};
@@ -70,7 +71,7 @@ impl SyncDeployment {
         let dgf_address = system_config
             .disputeGameFactory()
             .stall_with_context(context.clone(), "SystemConfig::disputeGameFactory")
-            .await;
+            .await?;
 
         // Init registry and factory contracts
         let dispute_game_factory = IDisputeGameFactory::new(dgf_address, &provider.l1_provider);
@@ -78,7 +79,7 @@ impl SyncDeployment {
         let game_count: u64 = dispute_game_factory
             .gameCount()
             .stall_with_context(context.clone(), "DisputeGameFactory::gameCount")
-            .await
+            .await?
             .to();
         info!("There have been {game_count} games created using DisputeGameFactory");
 
@@ -86,7 +87,7 @@ impl SyncDeployment {
         let latest_game_impl_addr = dispute_game_factory
             .gameImpls(KAILUA_GAME_TYPE)
             .stall_with_context(context.clone(), "DisputeGameFactory::gameImpls")
-            .await;
+            .await?;
         let kailua_game_implementation_address = game_impl_address.unwrap_or(latest_game_impl_addr);
         if game_impl_address.is_some() {
             warn!("Using provided KailuaGame implementation {kailua_game_implementation_address}.");
@@ -102,57 +103,37 @@ impl SyncDeployment {
             exit(1);
         }
 
-        let treasury = kailua_game_implementation
-            .KAILUA_TREASURY()
-            .stall_with_context(context.clone(), "KailuaGame::KAILUA_TREASURY")
-            .await;
         let game = *kailua_game_implementation.address();
-        let verifier = kailua_game_implementation
-            .RISC_ZERO_VERIFIER()
-            .stall_with_context(context.clone(), "KailuaGame::RISC_ZERO_VERIFIER")
-            .await;
-        let image_id = kailua_game_implementation
-            .FPVM_IMAGE_ID()
-            .stall_with_context(context.clone(), "KailuaGame::FPVM_IMAGE_ID")
-            .await;
-        let cfg_hash = kailua_game_implementation
-            .ROLLUP_CONFIG_HASH()
-            .stall_with_context(context.clone(), "KailuaGame::ROLLUP_CONFIG_HASH")
-            .await;
-        let proposal_output_count = kailua_game_implementation
-            .PROPOSAL_OUTPUT_COUNT()
-            .stall_with_context(context.clone(), "KailuaGame::PROPOSAL_OUTPUT_COUNT")
-            .await;
-        let output_block_span = kailua_game_implementation
-            .OUTPUT_BLOCK_SPAN()
-            .stall_with_context(context.clone(), "KailuaGame::OUTPUT_BLOCK_SPAN")
-            .await;
-        let proposal_blobs = kailua_game_implementation
-            .PROPOSAL_BLOBS()
-            .stall_with_context(context.clone(), "KailuaGame::PROPOSAL_BLOBS")
-            .await;
-        let game_type = kailua_game_implementation
-            .GAME_TYPE()
-            .stall_with_context(context.clone(), "KailuaGame::GAME_TYPE")
-            .await as u8;
-        let factory = kailua_game_implementation
-            .DISPUTE_GAME_FACTORY()
-            .stall_with_context(context.clone(), "KailuaGame::DISPUTE_GAME_FACTORY")
-            .await;
-        let timeout = kailua_game_implementation
-            .MAX_CLOCK_DURATION()
-            .stall_with_context(context.clone(), "KailuaGame::MAX_CLOCK_DURATION")
-            .await;
-        let genesis_time = kailua_game_implementation
-            .GENESIS_TIME_STAMP()
-            .stall_with_context(context.clone(), "KailuaGame::GENESIS_TIME_STAMP")
-            .await
-            .to();
-        let block_time = kailua_game_implementation
-            .L2_BLOCK_TIME()
-            .stall_with_context(context.clone(), "KailuaGame::L2_BLOCK_TIME")
-            .await
-            .to();
+        let mut batch = MulticallBatch::default();
+        let treasury = batch.add(game, KailuaGame::KAILUA_TREASURYCall {});
+        let verifier = batch.add(game, KailuaGame::RISC_ZERO_VERIFIERCall {});
+        let image_id = batch.add(game, KailuaGame::FPVM_IMAGE_IDCall {});
+        let cfg_hash = batch.add(game, KailuaGame::ROLLUP_CONFIG_HASHCall {});
+        let proposal_output_count = batch.add(game, KailuaGame::PROPOSAL_OUTPUT_COUNTCall {});
+        let output_block_span = batch.add(game, KailuaGame::OUTPUT_BLOCK_SPANCall {});
+        let proposal_blobs = batch.add(game, KailuaGame::PROPOSAL_BLOBSCall {});
+        let game_type = batch.add(game, KailuaGame::GAME_TYPECall {});
+        let factory = batch.add(game, KailuaGame::DISPUTE_GAME_FACTORYCall {});
+        let timeout = batch.add(game, KailuaGame::MAX_CLOCK_DURATIONCall {});
+        let genesis_time = batch.add(game, KailuaGame::GENESIS_TIME_STAMPCall {});
+        let block_time = batch.add(game, KailuaGame::L2_BLOCK_TIMECall {});
+        let results = batch
+            .execute(provider)
+            .with_context(context.clone())
+            .await?;
+
+        let treasury = results.get(treasury)?;
+        let verifier = results.get(verifier)?;
+        let image_id = results.get(image_id)?;
+        let cfg_hash = results.get(cfg_hash)?;
+        let proposal_output_count = results.get(proposal_output_count)?;
+        let output_block_span = results.get(output_block_span)?;
+        let proposal_blobs = results.get(proposal_blobs)?;
+        let game_type = results.get(game_type)? as u8;
+        let factory = results.get(factory)?;
+        let timeout = results.get(timeout)?;
+        let genesis_time = results.get(genesis_time)?.to();
+        let block_time = results.get(block_time)?.to();
         Ok(Self {
             treasury,
             game,
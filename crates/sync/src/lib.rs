@@ -15,9 +15,11 @@
 pub mod agent;
 pub mod args;
 pub mod blobs;
+pub mod checkpoint;
 pub mod cursor;
 pub mod deployment;
 pub mod fault;
+pub mod multicall;
 pub mod proposal;
 pub mod provider;
 pub mod retry;
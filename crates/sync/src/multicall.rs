@@ -0,0 +1,179 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::provider::SyncProvider;
+use crate::stall::Stall;
+use crate::{await_tel, retry_res_ctx_timeout};
+use alloy::primitives::{address, Address, Bytes};
+use alloy::providers::{Provider, RootProvider};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::Context;
+use futures::future::join_all;
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use std::marker::PhantomData;
+use tracing::warn;
+
+sol!(
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Call3Result[] memory returnData);
+    }
+);
+
+/// Address of the canonical Multicall3 deployment
+pub const MULTICALL3_ADDRESS: Address = address!("0xcA11bde05977b3631167028862bE2a173976CA11");
+/// Maximum number of calls aggregated in a single request
+pub const MULTICALL_BATCH_LIMIT: usize = 256;
+
+/// A reference to the result of a call added to a [MulticallBatch]
+pub struct MulticallHandle<C: SolCall>(usize, PhantomData<C>);
+
+impl<C: SolCall> Clone for MulticallHandle<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: SolCall> Copy for MulticallHandle<C> {}
+
+/// A batch of read-only contract calls that is executed through Multicall3 if it is deployed on
+/// the parent chain, or as individual concurrent calls otherwise.
+#[derive(Debug, Default)]
+pub struct MulticallBatch {
+    calls: Vec<(Address, Bytes)>,
+}
+
+/// The raw return data of each call in an executed [MulticallBatch]
+#[derive(Debug)]
+pub struct MulticallResults(Vec<Bytes>);
+
+impl MulticallBatch {
+    /// Queues the given call to the target contract and returns a handle to its result.
+    pub fn add<C: SolCall>(&mut self, target: Address, call: C) -> MulticallHandle<C> {
+        self.calls.push((target, call.abi_encode().into()));
+        MulticallHandle(self.calls.len() - 1, PhantomData)
+    }
+
+    /// Executes all queued calls, retrying until every call succeeds.
+    pub async fn execute(self, provider: &SyncProvider) -> anyhow::Result<MulticallResults> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("MulticallBatch::execute"));
+
+        let Some(multicall) = provider.multicall else {
+            let results = join_all(
+                self.calls
+                    .into_iter()
+                    .map(|(target, data)| call_raw(provider, target, data)),
+            );
+            return await_tel!(context, results)
+                .into_iter()
+                .collect::<anyhow::Result<_>>()
+                .map(MulticallResults);
+        };
+
+        let multicall = IMulticall3::new(multicall, &provider.l1_provider);
+        let mut results = Vec::with_capacity(self.calls.len());
+        for chunk in self.calls.chunks(MULTICALL_BATCH_LIMIT) {
+            let calls = chunk
+                .iter()
+                .map(|(target, data)| IMulticall3::Call3 {
+                    target: *target,
+                    allowFailure: true,
+                    callData: data.clone(),
+                })
+                .collect();
+            let returns = multicall
+                .aggregate3(calls)
+                .stall_with_context(context.clone(), "IMulticall3::aggregate3")
+                .await?;
+            for ((target, data), result) in chunk.iter().zip(returns) {
+                if result.success {
+                    results.push(result.returnData);
+                    continue;
+                }
+                // Retry failed calls individually
+                warn!("Aggregated call to {target} failed.");
+                results.push(await_tel!(
+                    context,
+                    call_raw(provider, *target, data.clone())
+                )?);
+            }
+        }
+        Ok(MulticallResults(results))
+    }
+}
+
+impl MulticallResults {
+    /// Decodes the result of the call referenced by the given handle.
+    pub fn get<C: SolCall>(&self, handle: MulticallHandle<C>) -> anyhow::Result<C::Return> {
+        C::abi_decode_returns(&self.0[handle.0]).context(C::SIGNATURE)
+    }
+}
+
+/// Returns whether Multicall3 is deployed at its canonical address.
+pub async fn is_multicall_deployed(provider: &RootProvider) -> anyhow::Result<bool> {
+    let code = provider
+        .get_code_at(MULTICALL3_ADDRESS)
+        .await
+        .context("get_code_at")?;
+    Ok(!code.is_empty())
+}
+
+async fn call_raw(provider: &SyncProvider, target: Address, data: Bytes) -> anyhow::Result<Bytes> {
+    retry_res_ctx_timeout!(EthRpc => provider.chain.l1_call(target, data.clone()).await).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::memory::InMemoryChain;
+    use alloy::primitives::{B256, U256};
+    use kailua_contracts::*;
+
+    #[tokio::test]
+    async fn test_multicall_batch() {
+        let chain = InMemoryChain::new();
+        let game = Address::repeat_byte(0x01);
+        chain.mock_call(game, KailuaGame::gameIndexCall {}, U256::from(7));
+        chain.mock_call(game, KailuaGame::rootClaimCall {}, B256::repeat_byte(0x02));
+        chain.mock_call(game, KailuaGame::createdAtCall {}, 12u64);
+
+        let mut provider = SyncProvider::in_memory(&chain);
+        for multicall in [Some(MULTICALL3_ADDRESS), None] {
+            provider.multicall = multicall;
+            let mut batch = MulticallBatch::default();
+            let index = batch.add(game, KailuaGame::gameIndexCall {});
+            let root_claim = batch.add(game, KailuaGame::rootClaimCall {});
+            let created_at = batch.add(game, KailuaGame::createdAtCall {});
+            let results = batch.execute(&provider).await.unwrap();
+            assert_eq!(results.get(index).unwrap(), U256::from(7));
+            assert_eq!(results.get(root_claim).unwrap().0, B256::repeat_byte(0x02));
+            assert_eq!(results.get(created_at).unwrap(), 12);
+        }
+    }
+}
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use crate::blobs::blob_fe_proof;
+use crate::cursor::LoggedGame;
 use crate::fault::Fault;
+use crate::multicall::{MulticallBatch, MulticallHandle};
 use crate::provider::beacon::blob_sidecar;
 use crate::provider::SyncProvider;
 use crate::stall::Stall;
@@ -32,7 +34,7 @@ use opentelemetry::global::tracer;
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::future::IntoFuture;
 use tracing::{error, info};
 
@@ -104,7 +106,7 @@ impl Proposal {
         let parent_address = tournament_instance
             .parentGame()
             .stall_with_context(context.clone(), "KailuaTournament::parentGame")
-            .await;
+            .await?;
         if parent_address == address {
             info!("Loading KailuaTreasury instance");
             await_tel!(context, Self::load_treasury(provider, address))
@@ -119,107 +121,33 @@ impl Proposal {
         let context =
             opentelemetry::Context::current_with_span(tracer.start("Proposal::load_treasury"));
 
-        let treasury = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .KAILUA_TREASURY()
-                    .stall_with_context(context, "KailuaGame::KAILUA_TREASURY")
-                    .await
-            }
-        });
-        let index = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .gameIndex()
-                    .stall_with_context(context.clone(), "KailuaTreasury::gameIndex")
-                    .await
-                    .to()
-            }
-        });
-        let created_at = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .createdAt()
-                    .stall_with_context(context.clone(), "KailuaTreasury::createdAt")
-                    .await
-            }
-        });
+        let mut batch = MulticallBatch::default();
+        let treasury = batch.add(address, KailuaTreasury::KAILUA_TREASURYCall {});
+        let index = batch.add(address, KailuaTreasury::gameIndexCall {});
+        let created_at = batch.add(address, KailuaTreasury::createdAtCall {});
         // claim data
-        let output_root = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .rootClaim()
-                    .stall_with_context(context.clone(), "KailuaTreasury::rootClaim")
-                    .await
-                    .0
-                    .into()
-            }
-        });
-        let output_block_number = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .l2BlockNumber()
-                    .stall_with_context(context.clone(), "KailuaTreasury::l2BlockNumber")
-                    .await
-                    .to()
-            }
-        });
-        let l1_head = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .l1Head()
-                    .stall_with_context(context.clone(), "KailuaTreasury::l1Head")
-                    .await
-            }
-        });
-        let signature = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .signature()
-                    .stall_with_context(context.clone(), "KailuaTreasury::signature")
-                    .await
-            }
-        });
-        let resolved_at = tokio::task::spawn({
-            let context = context.clone();
-            let treasury_instance = KailuaTreasury::new(address, provider.l1_provider.clone());
-            async move {
-                treasury_instance
-                    .resolvedAt()
-                    .stall_with_context(context.clone(), "KailuaTreasury::resolvedAt")
-                    .await
-            }
-        });
-
-        let index = index.await?;
+        let output_root = batch.add(address, KailuaTreasury::rootClaimCall {});
+        let output_block_number = batch.add(address, KailuaTreasury::l2BlockNumberCall {});
+        let l1_head = batch.add(address, KailuaTreasury::l1HeadCall {});
+        let signature = batch.add(address, KailuaTreasury::signatureCall {});
+        let resolved_at = batch.add(address, KailuaTreasury::resolvedAtCall {});
+        let results = await_tel!(context, batch.execute(provider))?;
+
+        let index = results.get(index)?.to();
         Ok(Self {
             contract: address,
-            treasury: treasury.await?,
+            treasury: results.get(treasury)?,
             index,
             parent: index,
             proposer: address,
-            created_at: created_at.await?,
+            created_at: results.get(created_at)?,
             io_blobs: vec![],
             io_field_elements: vec![],
             trail_field_elements: vec![],
-            output_root: output_root.await?,
-            output_block_number: output_block_number.await?,
-            l1_head: l1_head.await?,
-            signature: signature.await?,
+            output_root: results.get(output_root)?.0.into(),
+            output_block_number: results.get(output_block_number)?.to(),
+            l1_head: results.get(l1_head)?,
+            signature: results.get(signature)?,
             children: Default::default(),
             successor: None,
             correct_io: vec![],
@@ -227,7 +155,7 @@ impl Proposal {
             correct_claim: Some(true),
             correct_parent: Some(true),
             canonical: None,
-            resolved_at: resolved_at.await?,
+            resolved_at: results.get(resolved_at)?,
         })
     }
 
@@ -236,148 +164,222 @@ impl Proposal {
         let context =
             opentelemetry::Context::current_with_span(tracer.start("Proposal::load_game"));
 
-        let treasury = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .KAILUA_TREASURY()
-                    .stall_with_context(context, "KailuaGame::KAILUA_TREASURY")
-                    .await
-            }
-        });
-        let index = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .gameIndex()
-                    .stall_with_context(context.clone(), "KailuaGame::gameIndex")
-                    .await
-                    .to()
-            }
-        });
-        let parent = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .parentGameIndex()
-                    .stall_with_context(context.clone(), "KailuaGame::parentGameIndex")
-                    .await
-            }
-        });
-        let proposer = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .proposer()
-                    .stall_with_context(context.clone(), "KailuaGame::proposer")
-                    .await
-            }
-        });
-        let created_at = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .createdAt()
-                    .stall_with_context(context.clone(), "KailuaGame::createdAt")
-                    .await
-            }
-        });
+        let mut batch = MulticallBatch::default();
+        let treasury = batch.add(address, KailuaGame::KAILUA_TREASURYCall {});
+        let index = batch.add(address, KailuaGame::gameIndexCall {});
+        let parent = batch.add(address, KailuaGame::parentGameIndexCall {});
+        let proposer = batch.add(address, KailuaGame::proposerCall {});
+        let created_at = batch.add(address, KailuaGame::createdAtCall {});
         // claim data
-        let output_root = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .rootClaim()
-                    .stall_with_context(context.clone(), "KailuaGame::rootClaim")
-                    .await
-                    .0
-                    .into()
-            }
-        });
-        let output_block_number = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .l2BlockNumber()
-                    .stall_with_context(context.clone(), "KailuaGame::l2BlockNumber")
-                    .await
-                    .to()
-            }
-        });
-        let l1_head = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .l1Head()
-                    .stall_with_context(context.clone(), "KailuaGame::l1Head")
-                    .await
-                    .0
-                    .into()
-            }
-        });
-        let signature = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .signature()
-                    .stall_with_context(context.clone(), "KailuaGame::signature")
-                    .await
-                    .0
-                    .into()
-            }
-        });
-        let resolved_at = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .resolvedAt()
-                    .stall_with_context(context.clone(), "KailuaTreasury::resolvedAt")
-                    .await
-            }
-        });
+        let output_root = batch.add(address, KailuaGame::rootClaimCall {});
+        let output_block_number = batch.add(address, KailuaGame::l2BlockNumberCall {});
+        let l1_head = batch.add(address, KailuaGame::l1HeadCall {});
+        let signature = batch.add(address, KailuaGame::signatureCall {});
+        let resolved_at = batch.add(address, KailuaGame::resolvedAtCall {});
+        // blob data
+        let proposal_blobs = batch.add(address, KailuaGame::PROPOSAL_BLOBSCall {});
+        let proposal_output_count = batch.add(address, KailuaGame::PROPOSAL_OUTPUT_COUNTCall {});
+        let results = await_tel!(context, batch.execute(provider))?;
+
+        let created_at: u64 = results.get(created_at)?;
+        let proposal_blobs: u64 = results.get(proposal_blobs)?;
+        let proposal_output_count: u64 = results.get(proposal_output_count)?;
+
+        // fetch blob hashes
+        let mut batch = MulticallBatch::default();
+        let blob_hashes = (0..proposal_blobs)
+            .map(|i| {
+                batch.add(
+                    address,
+                    KailuaGame::proposalBlobHashesCall::new((U256::from(i),)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let blob_hash_results = await_tel!(context, batch.execute(provider))?;
+        let blob_hashes = blob_hashes
+            .into_iter()
+            .map(|blob_hash| blob_hash_results.get(blob_hash))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
         // fetch blob data
-        let proposal_blobs = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .PROPOSAL_BLOBS()
-                    .stall_with_context(context.clone(), "KailuaGame::PROPOSAL_BLOBS")
-                    .await
-            }
-        });
-        let proposal_output_count = tokio::task::spawn({
-            let context = context.clone();
-            let game_instance = KailuaGame::new(address, provider.l1_provider.clone());
-            async move {
-                game_instance
-                    .PROPOSAL_OUTPUT_COUNT()
-                    .stall_with_context(context.clone(), "KailuaGame::PROPOSAL_OUTPUT_COUNT")
-                    .await
+        let (io_blobs, io_field_elements, trail_field_elements) = await_tel!(
+            context,
+            Self::load_blobs(provider, created_at, blob_hashes, proposal_output_count)
+        )?;
+        let trail_len = trail_field_elements.len();
+        Ok(Self {
+            contract: address,
+            treasury: results.get(treasury)?,
+            index: results.get(index)?.to(),
+            parent: results.get(parent)?,
+            proposer: results.get(proposer)?,
+            created_at,
+            io_blobs,
+            io_field_elements,
+            trail_field_elements,
+            output_root: results.get(output_root)?.0.into(),
+            output_block_number: results.get(output_block_number)?.to(),
+            l1_head: results.get(l1_head)?.0.into(),
+            signature: results.get(signature)?.0.into(),
+            children: Default::default(),
+            successor: None,
+            correct_io: vec![None; (proposal_output_count - 1) as usize],
+            correct_trail: vec![None; trail_len],
+            correct_claim: None,
+            correct_parent: None,
+            canonical: None,
+            resolved_at: results.get(resolved_at)?,
+        })
+    }
+
+    /// Loads the given games discovered in the factory logs by index, deriving what is known from
+    /// their creation events and batching the remaining reads of all games into shared multicalls.
+    pub async fn load_logged(
+        provider: &SyncProvider,
+        games: &BTreeMap<u64, LoggedGame>,
+    ) -> anyhow::Result<BTreeMap<u64, Self>> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("Proposal::load_logged"));
+
+        struct GameReads {
+            parent: MulticallHandle<KailuaGame::parentGameIndexCall>,
+            proposer: MulticallHandle<KailuaGame::proposerCall>,
+            proposal_blobs: MulticallHandle<KailuaGame::PROPOSAL_BLOBSCall>,
+            proposal_output_count: MulticallHandle<KailuaGame::PROPOSAL_OUTPUT_COUNTCall>,
+        }
+        struct Reads {
+            treasury: MulticallHandle<KailuaGame::KAILUA_TREASURYCall>,
+            created_at: Option<MulticallHandle<KailuaGame::createdAtCall>>,
+            output_block_number: MulticallHandle<KailuaGame::l2BlockNumberCall>,
+            l1_head: MulticallHandle<KailuaGame::l1HeadCall>,
+            signature: MulticallHandle<KailuaGame::signatureCall>,
+            resolved_at: MulticallHandle<KailuaGame::resolvedAtCall>,
+            game: Option<GameReads>,
+        }
+
+        // fetch the game data not carried by the creation events
+        let mut batch = MulticallBatch::default();
+        let reads = games
+            .values()
+            .map(|game| {
+                let address = game.contract;
+                Reads {
+                    treasury: batch.add(address, KailuaGame::KAILUA_TREASURYCall {}),
+                    created_at: game
+                        .created_at
+                        .is_none()
+                        .then(|| batch.add(address, KailuaGame::createdAtCall {})),
+                    output_block_number: batch.add(address, KailuaGame::l2BlockNumberCall {}),
+                    l1_head: batch.add(address, KailuaGame::l1HeadCall {}),
+                    signature: batch.add(address, KailuaGame::signatureCall {}),
+                    resolved_at: batch.add(address, KailuaGame::resolvedAtCall {}),
+                    game: (!game.is_treasury).then(|| GameReads {
+                        parent: batch.add(address, KailuaGame::parentGameIndexCall {}),
+                        proposer: batch.add(address, KailuaGame::proposerCall {}),
+                        proposal_blobs: batch.add(address, KailuaGame::PROPOSAL_BLOBSCall {}),
+                        proposal_output_count: batch
+                            .add(address, KailuaGame::PROPOSAL_OUTPUT_COUNTCall {}),
+                    }),
+                }
+            })
+            .collect::<Vec<_>>();
+        let results = await_tel!(context, batch.execute(provider))?;
+
+        // fetch the blob hashes of all game instances
+        let mut batch = MulticallBatch::default();
+        let mut blob_hashes = Vec::with_capacity(reads.len());
+        for (game, read) in games.values().zip(&reads) {
+            let Some(game_reads) = &read.game else {
+                blob_hashes.push(vec![]);
+                continue;
+            };
+            let proposal_blobs: u64 = results.get(game_reads.proposal_blobs)?;
+            blob_hashes.push(
+                (0..proposal_blobs)
+                    .map(|i| {
+                        batch.add(
+                            game.contract,
+                            KailuaGame::proposalBlobHashesCall::new((U256::from(i),)),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let blob_hash_results = await_tel!(context, batch.execute(provider))?;
+
+        let mut proposals = BTreeMap::new();
+        for (((index, game), read), blob_hashes) in games.iter().zip(reads).zip(blob_hashes) {
+            let created_at = match read.created_at {
+                Some(created_at) => results.get(created_at)?,
+                None => game.created_at.unwrap_or_default(),
+            };
+            let mut proposal = Self {
+                contract: game.contract,
+                treasury: results.get(read.treasury)?,
+                index: *index,
+                parent: *index,
+                proposer: game.contract,
+                created_at,
+                io_blobs: vec![],
+                io_field_elements: vec![],
+                trail_field_elements: vec![],
+                output_root: game.root_claim,
+                output_block_number: results.get(read.output_block_number)?.to(),
+                l1_head: results.get(read.l1_head)?.0.into(),
+                signature: results.get(read.signature)?.0.into(),
+                children: Default::default(),
+                successor: None,
+                correct_io: vec![],
+                correct_trail: vec![],
+                correct_claim: Some(true),
+                correct_parent: Some(true),
+                canonical: None,
+                resolved_at: results.get(read.resolved_at)?,
+            };
+            if let Some(game_reads) = read.game {
+                let proposal_output_count: u64 = results.get(game_reads.proposal_output_count)?;
+                let blob_hashes = blob_hashes
+                    .into_iter()
+                    .map(|blob_hash| blob_hash_results.get(blob_hash))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let (io_blobs, io_field_elements, trail_field_elements) = await_tel!(
+                    context,
+                    Self::load_blobs(provider, created_at, blob_hashes, proposal_output_count)
+                )?;
+                proposal.parent = results.get(game_reads.parent)?;
+                proposal.proposer = results.get(game_reads.proposer)?;
+                proposal.correct_io = vec![None; (proposal_output_count - 1) as usize];
+                proposal.correct_trail = vec![None; trail_field_elements.len()];
+                proposal.io_blobs = io_blobs;
+                proposal.io_field_elements = io_field_elements;
+                proposal.trail_field_elements = trail_field_elements;
+                proposal.correct_claim = None;
+                proposal.correct_parent = None;
             }
-        });
+            proposals.insert(*index, proposal);
+        }
+
+        Ok(proposals)
+    }
+
+    /// Fetches the given proposal blobs and splits them into intermediate output and trailing
+    /// data field elements.
+    #[allow(clippy::type_complexity)]
+    async fn load_blobs(
+        provider: &SyncProvider,
+        created_at: u64,
+        blob_hashes: Vec<B256>,
+        proposal_output_count: u64,
+    ) -> anyhow::Result<(Vec<(B256, BlobData)>, Vec<U256>, Vec<U256>)> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("Proposal::load_blobs"));
+
         let mut io_blobs = Vec::new();
         let mut io_field_elements = Vec::new();
         let mut trail_field_elements = Vec::new();
-        let created_at: u64 = created_at.await?;
-        let proposal_blobs: u64 = proposal_blobs.await?;
-        let proposal_output_count: u64 = proposal_output_count.await?;
-        let game_instance = KailuaGame::new(address, &provider.l1_provider);
-        for _ in 0..proposal_blobs {
-            let blob_kzg_hash = game_instance
-                .proposalBlobHashes(U256::from(io_blobs.len()))
-                .stall_with_context(context.clone(), "KailuaGame::proposalBlobHashes")
-                .await;
+        for blob_kzg_hash in blob_hashes {
             let blob_data = await_tel!(
                 context,
                 provider.da_provider.get_blob(created_at, blob_kzg_hash)
@@ -390,30 +392,7 @@ impl Proposal {
             trail_field_elements.extend(trail_data(&blob_data.blob, io_in_blob)?);
             io_blobs.push((blob_kzg_hash, blob_data));
         }
-        let trail_len = trail_field_elements.len();
-        Ok(Self {
-            contract: address,
-            treasury: treasury.await?,
-            index: index.await?,
-            parent: parent.await?,
-            proposer: proposer.await?,
-            created_at,
-            io_blobs,
-            io_field_elements,
-            trail_field_elements,
-            output_root: output_root.await?,
-            output_block_number: output_block_number.await?,
-            l1_head: l1_head.await?,
-            signature: signature.await?,
-            children: Default::default(),
-            successor: None,
-            correct_io: vec![None; (proposal_output_count - 1) as usize],
-            correct_trail: vec![None; trail_len],
-            correct_claim: None,
-            correct_parent: None,
-            canonical: None,
-            resolved_at: resolved_at.await?,
-        })
+        Ok((io_blobs, io_field_elements, trail_field_elements))
     }
 
     pub fn as_delayed(&self) -> ProposalSync {
@@ -588,12 +567,12 @@ impl Proposal {
             .tournament_contract_instance(&provider)
             .parentGame()
             .stall_with_context(context.clone(), "KailuaTournament::parentGame")
-            .await;
+            .await?;
         let parent_tournament_instance = KailuaTournament::new(parent_tournament, &provider);
         let children = parent_tournament_instance
             .childCount()
             .stall_with_context(context.clone(), "KailuaTournament::childCount")
-            .await;
+            .await?;
         let survivor = await_tel_res!(
             context,
             tracer,
@@ -645,11 +624,14 @@ impl Proposal {
             self.tournament_contract_instance(provider)
                 .status()
                 .stall_with_context(context.clone(), "KailuaTournament::status")
-                .await,
+                .await?,
         )
     }
 
-    pub async fn fetch_resolved_at<P: Provider<N>, N: Network>(&self, provider: P) -> u64 {
+    pub async fn fetch_resolved_at<P: Provider<N>, N: Network>(
+        &self,
+        provider: P,
+    ) -> anyhow::Result<u64> {
         let tracer = tracer("kailua");
         let context =
             opentelemetry::Context::current_with_span(tracer.start("Proposal::fetch_resolved_at"));
@@ -657,7 +639,7 @@ impl Proposal {
         self.tournament_contract_instance(provider)
             .resolvedAt()
             .stall_with_context(context.clone(), "KailuaTournament::resolvedAt")
-            .await
+            .await?
     }
 
     pub fn parse_finality(game_status: u8) -> anyhow::Result<Option<bool>> {
@@ -672,16 +654,16 @@ impl Proposal {
     pub async fn fetch_is_successor_validity_proven<P: Provider<N>, N: Network>(
         &self,
         provider: P,
-    ) -> bool {
+    ) -> anyhow::Result<bool> {
         let tracer = tracer("kailua");
         let context =
             opentelemetry::Context::current_with_span(tracer.start("Proposal::fetch_finality"));
 
-        !self
+        Ok(!self
             .tournament_contract_instance(provider)
             .validChildSignature()
             .stall_with_context(context.clone(), "KailuaTournament::validChildSignature")
-            .await
-            .is_zero()
+            .await?
+            .is_zero())
     }
 }
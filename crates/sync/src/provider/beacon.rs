@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::provider::failover::EndpointHealth;
 use crate::{await_tel, retry_res_timeout};
 use alloy::consensus::{Blob, BlobTransactionSidecar};
 use alloy::eips::eip4844::kzg_to_versioned_hash;
@@ -23,62 +24,74 @@ use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
-use tracing::debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
 
 #[derive(Clone, Debug)]
 pub struct BlobProvider {
-    pub cl_node_endpoint: String,
+    pub cl_node_endpoints: Vec<String>,
+    pub health: Arc<EndpointHealth>,
     pub client: Client,
     pub genesis_time: u64,
     pub seconds_per_slot: u64,
 }
 
 impl BlobProvider {
-    pub async fn new(cl_node_endpoint: String) -> anyhow::Result<Self> {
+    pub async fn new(cl_node_endpoints: Vec<String>) -> anyhow::Result<Self> {
         let tracer = tracer("kailua");
         let context = opentelemetry::Context::current_with_span(tracer.start("BlobProvider::new"));
 
-        let cl_node_endpoint = cl_node_endpoint.trim_end_matches('/').to_owned();
-        let client = Client::new();
+        if cl_node_endpoints.is_empty() {
+            bail!("No beacon endpoints configured.");
+        }
+        let cl_node_endpoints = cl_node_endpoints
+            .into_iter()
+            .map(|endpoint| endpoint.trim_end_matches('/').to_owned())
+            .collect::<Vec<_>>();
+        let mut provider = Self {
+            health: Arc::new(EndpointHealth::new(cl_node_endpoints.len())),
+            cl_node_endpoints,
+            client: Client::new(),
+            genesis_time: 0,
+            seconds_per_slot: 0,
+        };
 
         let genesis = await_tel!(
             context,
             tracer,
-            "BlobProvider::client_get (genesis)",
+            "BlobProvider::get (genesis)",
             retry_res_timeout!(
-                10,
-                Self::client_get::<Value>(&client, &cl_node_endpoint, "eth/v1/beacon/genesis")
+                BeaconRpc => 10,
+                provider
+                    .get::<Value>("eth/v1/beacon/genesis")
                     .with_context(context.clone())
                     .await
             )
-        );
+        )?;
         debug!("genesis {:?}", &genesis);
-        let genesis_time = genesis["data"]["genesis_time"]
+        provider.genesis_time = genesis["data"]["genesis_time"]
             .as_str()
             .unwrap()
             .parse::<u64>()?;
         let spec = await_tel!(
             context,
             tracer,
-            "BlobProvider::client_get (spec)",
+            "BlobProvider::get (spec)",
             retry_res_timeout!(
-                10,
-                Self::client_get::<Value>(&client, &cl_node_endpoint, "eth/v1/config/spec")
+                BeaconRpc => 10,
+                provider
+                    .get::<Value>("eth/v1/config/spec")
                     .with_context(context.clone())
                     .await
             )
-        );
+        )?;
         debug!("spec {:?}", &spec);
-        let seconds_per_slot = spec["data"]["SECONDS_PER_SLOT"]
+        provider.seconds_per_slot = spec["data"]["SECONDS_PER_SLOT"]
             .as_str()
             .unwrap()
             .parse::<u64>()?;
-        Ok(Self {
-            cl_node_endpoint,
-            client,
-            genesis_time,
-            seconds_per_slot,
-        })
+        Ok(provider)
     }
 
     pub fn slot(&self, timestamp: u64) -> u64 {
@@ -106,8 +119,23 @@ impl BlobProvider {
             .context("json")
     }
 
+    /// Queries the healthiest beacon endpoint, failing over to the next one on error.
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
-        Self::client_get(&self.client, &self.cl_node_endpoint, path).await
+        let mut last_error = None;
+        for i in self.health.ranked() {
+            match Self::client_get(&self.client, &self.cl_node_endpoints[i], path).await {
+                Ok(result) => {
+                    self.health.record(i, true);
+                    return Ok(result);
+                }
+                Err(err) => {
+                    self.health.record(i, false);
+                    warn!("Request to beacon endpoint {i} failed: {err:?}");
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.expect("No beacon endpoints"))
     }
 
     pub async fn get_blob(&self, timestamp: u64, blob_hash: B256) -> anyhow::Result<BlobData> {
@@ -115,18 +143,18 @@ impl BlobProvider {
         let context =
             opentelemetry::Context::current_with_span(tracer.start("BlobProvider::get_blob"));
 
+        // Fail fast so that fallback blob sources can be queried
         let slot = self.slot(timestamp);
         let blobs = await_tel!(
             context,
             tracer,
             "BlobProvider::get",
-            retry_res_timeout!(
-                10,
+            tokio::time::timeout(
+                Duration::from_secs(10),
                 self.get::<BeaconBlobBundle>(&format!("eth/v1/beacon/blob_sidecars/{slot}"))
-                    .with_context(context.clone())
-                    .await
             )
-        );
+        )
+        .context("timeout")??;
 
         let blob_count = blobs.len();
         for blob in blobs {
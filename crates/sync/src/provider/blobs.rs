@@ -0,0 +1,342 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::provider::beacon::BlobProvider;
+use crate::provider::BlobSource;
+use alloy::eips::eip4844::kzg_to_versioned_hash;
+use alloy::primitives::B256;
+use alloy_rpc_types_beacon::sidecar::{BeaconBlobBundle, BlobData};
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use opentelemetry::global::tracer;
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use reqwest::Client;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Checks that the blob data matches the versioned hash and carries a valid KZG proof.
+pub fn verify_blob(blob_hash: B256, blob_data: &BlobData) -> anyhow::Result<()> {
+    let versioned_hash = kzg_to_versioned_hash(blob_data.kzg_commitment.as_slice());
+    if versioned_hash != blob_hash {
+        bail!("Blob commitment hash {versioned_hash} does not match {blob_hash}.");
+    }
+    let c_kzg_blob = c_kzg::Blob::from_bytes(blob_data.blob.as_slice())?;
+    let settings = alloy::consensus::EnvKzgSettings::default();
+    let valid = settings.get().verify_blob_kzg_proof(
+        &c_kzg_blob,
+        &c_kzg::Bytes48::from(blob_data.kzg_commitment.0),
+        &c_kzg::Bytes48::from(blob_data.kzg_proof.0),
+    )?;
+    if !valid {
+        bail!("Invalid KZG proof for blob {blob_hash}.");
+    }
+    Ok(())
+}
+
+/// A persistent on-disk cache of verified blobs keyed by versioned hash.
+///
+/// Each blob is kept as a JSON file named after its versioned hash, so that cached blobs can be
+/// moved between machines by copying files.
+#[derive(Clone, Debug)]
+pub struct BlobStore {
+    pub dir: PathBuf,
+}
+
+impl BlobStore {
+    pub fn open(dir: PathBuf) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(&dir).context(format!("create_dir_all {dir:?}"))?;
+        Ok(Self { dir })
+    }
+
+    pub fn path(&self, blob_hash: B256) -> PathBuf {
+        self.dir.join(format!("{blob_hash}.json"))
+    }
+
+    /// Returns the cached blob with the given versioned hash, discarding it if corrupted.
+    pub async fn get(&self, blob_hash: B256) -> anyhow::Result<Option<BlobData>> {
+        let path = self.path(blob_hash);
+        let data = match tokio::fs::read(&path).await {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).context(format!("read {path:?}")),
+        };
+        let blob_data = serde_json::from_slice::<BlobData>(&data)
+            .context("serde_json::from_slice")
+            .and_then(|blob_data| verify_blob(blob_hash, &blob_data).map(|_| blob_data));
+        match blob_data {
+            Ok(blob_data) => Ok(Some(blob_data)),
+            Err(err) => {
+                warn!("Discarding corrupted cached blob {blob_hash}: {err:?}");
+                tokio::fs::remove_file(&path)
+                    .await
+                    .context(format!("remove_file {path:?}"))?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Caches the given blob after verifying it against its versioned hash.
+    pub async fn put(&self, blob_hash: B256, blob_data: &BlobData) -> anyhow::Result<()> {
+        verify_blob(blob_hash, blob_data)?;
+        let path = self.path(blob_hash);
+        let tmp_path = path.with_extension("tmp");
+        let data = serde_json::to_vec(blob_data).context("serde_json::to_vec")?;
+        tokio::fs::write(&tmp_path, data)
+            .await
+            .context(format!("write {tmp_path:?}"))?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .context(format!("rename {tmp_path:?}"))
+    }
+
+    /// Copies all cached blobs into the given directory and returns their count.
+    pub async fn export(&self, dir: &Path) -> anyhow::Result<usize> {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .context(format!("create_dir_all {dir:?}"))?;
+        let mut count = 0;
+        for path in Self::blob_files(&self.dir).await? {
+            let target = dir.join(path.file_name().unwrap());
+            tokio::fs::copy(&path, &target)
+                .await
+                .context(format!("copy {path:?}"))?;
+            count += 1;
+        }
+        info!("Exported {count} blobs to {dir:?}.");
+        Ok(count)
+    }
+
+    /// Caches all valid blobs found in the given directory and returns their count.
+    pub async fn import(&self, dir: &Path) -> anyhow::Result<usize> {
+        let mut count = 0;
+        for path in Self::blob_files(dir).await? {
+            let data = tokio::fs::read(&path)
+                .await
+                .context(format!("read {path:?}"))?;
+            let blob_data = match serde_json::from_slice::<BlobData>(&data) {
+                Ok(blob_data) => blob_data,
+                Err(err) => {
+                    warn!("Skipping unreadable blob file {path:?}: {err:?}");
+                    continue;
+                }
+            };
+            let blob_hash = kzg_to_versioned_hash(blob_data.kzg_commitment.as_slice());
+            if let Err(err) = self.put(blob_hash, &blob_data).await {
+                warn!("Skipping invalid blob file {path:?}: {err:?}");
+                continue;
+            }
+            count += 1;
+        }
+        info!("Imported {count} blobs from {dir:?}.");
+        Ok(count)
+    }
+
+    async fn blob_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let mut entries = tokio::fs::read_dir(dir)
+            .await
+            .context(format!("read_dir {dir:?}"))?;
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await.context("next_entry")? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// A blob-archiver style HTTP API serving blob sidecars beyond the beacon retention window.
+#[derive(Clone, Debug)]
+pub struct BlobArchive {
+    pub endpoints: Vec<String>,
+    pub client: Client,
+    pub genesis_time: u64,
+    pub seconds_per_slot: u64,
+}
+
+impl BlobArchive {
+    /// Creates an archive client that resolves slots using the chain spec of the beacon provider.
+    pub fn new(endpoints: Vec<String>, beacon: &BlobProvider) -> Self {
+        Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|endpoint| endpoint.trim_end_matches('/').to_owned())
+                .collect(),
+            client: beacon.client.clone(),
+            genesis_time: beacon.genesis_time,
+            seconds_per_slot: beacon.seconds_per_slot,
+        }
+    }
+
+    pub fn slot(&self, timestamp: u64) -> u64 {
+        (timestamp - self.genesis_time) / self.seconds_per_slot
+    }
+}
+
+#[async_trait]
+impl BlobSource for BlobArchive {
+    async fn get_blob(&self, timestamp: u64, blob_hash: B256) -> anyhow::Result<BlobData> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("BlobArchive::get_blob"));
+
+        let path = format!("eth/v1/beacon/blob_sidecars/{}", self.slot(timestamp));
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            let blobs =
+                match BlobProvider::client_get::<BeaconBlobBundle>(&self.client, endpoint, &path)
+                    .with_context(context.clone())
+                    .await
+                {
+                    Ok(blobs) => blobs,
+                    Err(err) => {
+                        warn!("Request to blob archive endpoint {i} failed: {err:?}");
+                        continue;
+                    }
+                };
+            if let Some(blob) = blobs
+                .into_iter()
+                .find(|blob| kzg_to_versioned_hash(blob.kzg_commitment.as_slice()) == blob_hash)
+            {
+                return Ok(blob);
+            }
+        }
+        bail!("Blob {blob_hash} @ {timestamp} not found in any archive.");
+    }
+}
+
+/// A chain of blob sources queried in order, backed by an optional local blob store.
+///
+/// Blobs from any source are only returned after verification against their versioned hash, and
+/// are cached in the store for later use.
+pub struct BlobSources {
+    /// Local cache queried before all sources
+    pub store: Option<BlobStore>,
+    /// Named remote sources in order of preference
+    pub sources: Vec<(&'static str, Arc<dyn BlobSource>)>,
+}
+
+#[async_trait]
+impl BlobSource for BlobSources {
+    async fn get_blob(&self, timestamp: u64, blob_hash: B256) -> anyhow::Result<BlobData> {
+        if let Some(store) = &self.store {
+            match store.get(blob_hash).await {
+                Ok(Some(blob_data)) => return Ok(blob_data),
+                Ok(None) => {}
+                Err(err) => warn!("Failed to read cached blob {blob_hash}: {err:?}"),
+            }
+        }
+        for (name, source) in &self.sources {
+            let blob_data = match source.get_blob(timestamp, blob_hash).await {
+                Ok(blob_data) => blob_data,
+                Err(err) => {
+                    warn!("Blob {blob_hash} unavailable from {name}: {err:?}");
+                    continue;
+                }
+            };
+            if let Err(err) = verify_blob(blob_hash, &blob_data) {
+                warn!("Rejecting blob {blob_hash} from {name}: {err:?}");
+                continue;
+            }
+            if let Some(store) = &self.store {
+                if let Err(err) = store.put(blob_hash, &blob_data).await {
+                    warn!("Failed to cache blob {blob_hash}: {err:?}");
+                }
+            }
+            return Ok(blob_data);
+        }
+        bail!("Blob {blob_hash} @ {timestamp} unavailable from all sources.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::beacon::blob_sidecar;
+    use crate::provider::memory::InMemoryChain;
+    use alloy::consensus::Blob;
+
+    fn sidecar_chain(count: usize) -> (InMemoryChain, Vec<B256>) {
+        let chain = InMemoryChain::new();
+        let blobs = (0..count)
+            .map(|i| {
+                let mut blob = Blob::default();
+                blob[1] = i as u8 + 1;
+                blob
+            })
+            .collect();
+        let blob_hashes = chain.add_sidecar(&blob_sidecar(blobs).unwrap());
+        (chain, blob_hashes)
+    }
+
+    #[tokio::test]
+    async fn test_blob_store() {
+        let (chain, blob_hashes) = sidecar_chain(2);
+        let blob_data = chain.get_blob(0, blob_hashes[0]).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let store = BlobStore::open(dir.path().join("blobs")).unwrap();
+
+        // only verified blobs are cached
+        assert!(store.put(blob_hashes[1], &blob_data).await.is_err());
+        store.put(blob_hashes[0], &blob_data).await.unwrap();
+        assert_eq!(store.get(blob_hashes[0]).await.unwrap(), Some(blob_data));
+        assert_eq!(store.get(blob_hashes[1]).await.unwrap(), None);
+
+        // corrupted blobs are discarded
+        let mut corrupted = chain.get_blob(0, blob_hashes[1]).await.unwrap();
+        corrupted.blob[1] = 0xff;
+        std::fs::write(
+            store.path(blob_hashes[1]),
+            serde_json::to_vec(&corrupted).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(store.get(blob_hashes[1]).await.unwrap(), None);
+        assert!(!store.path(blob_hashes[1]).exists());
+
+        // cached blobs can be moved between stores
+        let export_dir = dir.path().join("export");
+        assert_eq!(store.export(&export_dir).await.unwrap(), 1);
+        let imported = BlobStore::open(dir.path().join("imported")).unwrap();
+        assert_eq!(imported.import(&export_dir).await.unwrap(), 1);
+        assert!(imported.get(blob_hashes[0]).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_blob_sources() {
+        let (chain, blob_hashes) = sidecar_chain(1);
+        let dir = tempfile::tempdir().unwrap();
+        let store = BlobStore::open(dir.path().to_path_buf()).unwrap();
+        let sources = BlobSources {
+            store: Some(store.clone()),
+            sources: vec![
+                ("empty", Arc::new(InMemoryChain::new())),
+                ("archive", Arc::new(chain)),
+            ],
+        };
+
+        // blobs missing from earlier sources are fetched from later ones and cached
+        let blob_data = sources.get_blob(0, blob_hashes[0]).await.unwrap();
+        assert_eq!(store.get(blob_hashes[0]).await.unwrap(), Some(blob_data));
+        assert!(sources.get_blob(0, B256::ZERO).await.is_err());
+
+        // cached blobs remain available without any source
+        let offline = BlobSources {
+            store: Some(store),
+            sources: vec![],
+        };
+        assert!(offline.get_blob(0, blob_hashes[0]).await.is_ok());
+    }
+}
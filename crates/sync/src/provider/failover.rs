@@ -0,0 +1,658 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::primitives::U64;
+use alloy::rpc::json_rpc::{
+    ErrorPayload, Id, Request, RequestPacket, ResponsePacket, ResponsePayload, SerializedRequest,
+};
+use alloy::transports::http::Http;
+use alloy::transports::{TransportError, TransportErrorKind, TransportFut};
+use anyhow::{bail, Context as AnyhowContext};
+use futures::future::join_all;
+use itertools::Itertools;
+use opentelemetry::global::meter;
+use opentelemetry::metrics::Counter;
+use opentelemetry::KeyValue;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::Service;
+use tracing::{error, warn};
+
+/// Highest health score of an endpoint
+pub const MAX_HEALTH_SCORE: u64 = 100;
+/// Health score penalty for a failed request
+pub const FAILURE_PENALTY: u64 = 25;
+/// JSON-RPC error code of reverted calls
+pub const EXECUTION_REVERTED_CODE: i64 = 3;
+
+/// Returns whether the error was caused by the endpoint rather than by the request itself.
+///
+/// Reverted calls are reported alike by all healthy endpoints and are not failed over.
+pub fn is_endpoint_error(error: &ErrorPayload) -> bool {
+    error.code != EXECUTION_REVERTED_CODE && !error.message.starts_with("execution reverted")
+}
+
+/// Returns the first endpoint error contained in the response.
+pub fn endpoint_error(response: &ResponsePacket) -> Option<&ErrorPayload> {
+    let responses = match response {
+        ResponsePacket::Single(response) => std::slice::from_ref(response),
+        ResponsePacket::Batch(responses) => responses.as_slice(),
+    };
+    responses
+        .iter()
+        .filter_map(|response| match &response.payload {
+            ResponsePayload::Failure(error) => Some(error),
+            ResponsePayload::Success(_) => None,
+        })
+        .find(|error| is_endpoint_error(error))
+}
+
+/// Returns the position of the block parameter of state reads.
+pub fn block_param_index(method: &str) -> Option<usize> {
+    match method {
+        "eth_call" | "eth_getBalance" | "eth_getCode" | "eth_getTransactionCount" => Some(1),
+        "eth_getStorageAt" => Some(2),
+        _ => None,
+    }
+}
+
+/// Returns whether the block parameter at the given position refers to the moving chain tip.
+pub fn is_unpinned(params: &[Value], index: usize) -> bool {
+    params
+        .get(index)
+        .is_none_or(|block| block.is_null() || matches!(block.as_str(), Some("latest" | "pending")))
+}
+
+/// Returns the number and hash of the safe head in an `optimism_syncStatus` result.
+pub fn safe_head(status: &Value) -> Option<(u64, String)> {
+    let safe_l2 = &status["safe_l2"];
+    Some((
+        safe_l2["number"].as_u64()?,
+        safe_l2["hash"].as_str()?.to_string(),
+    ))
+}
+
+/// Rewinds the safe head of an `optimism_syncStatus` result to the given L2 block reference,
+/// such that the finalized head does not exceed it.
+pub fn rewind_safe_head(mut status: Value, block_ref: Value) -> Value {
+    let number = block_ref["number"].as_u64();
+    if status["finalized_l2"]["number"].as_u64() > number {
+        status["finalized_l2"] = block_ref.clone();
+    }
+    status["safe_l2"] = block_ref;
+    status
+}
+
+/// Health scores of a list of redundant endpoints
+#[derive(Debug)]
+pub struct EndpointHealth(Vec<AtomicU64>);
+
+impl EndpointHealth {
+    pub fn new(count: usize) -> Self {
+        Self(
+            (0..count)
+                .map(|_| AtomicU64::new(MAX_HEALTH_SCORE))
+                .collect(),
+        )
+    }
+
+    /// Returns the endpoint indices from the healthiest to the least healthy one, preferring the
+    /// configured order among equally healthy endpoints.
+    pub fn ranked(&self) -> Vec<usize> {
+        (0..self.0.len())
+            .sorted_by_key(|i| Reverse(self.0[*i].load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Rewards successful requests slowly and penalizes failed requests quickly.
+    pub fn record(&self, index: usize, success: bool) {
+        let _ = self.0[index].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |score| {
+            Some(match success {
+                true => (score + 1).min(MAX_HEALTH_SCORE),
+                false => score.saturating_sub(FAILURE_PENALTY),
+            })
+        });
+    }
+}
+
+/// An alloy transport that fails over between redundant JSON-RPC endpoints according to their
+/// health, and optionally requires a quorum of endpoints to agree on security-critical reads.
+#[derive(Clone)]
+pub struct FailoverTransport(Arc<FailoverEndpoints>);
+
+struct FailoverEndpoints {
+    /// Name of the endpoint list for reporting
+    name: &'static str,
+    /// Endpoint addresses
+    urls: Vec<String>,
+    /// Endpoint transports
+    transports: Vec<Http<reqwest::Client>>,
+    /// Endpoint health scores
+    health: EndpointHealth,
+    /// Number of endpoints that must agree on security-critical reads
+    quorum: Option<usize>,
+    /// Methods that are security-critical
+    quorum_methods: &'static [&'static str],
+    /// Counter for reporting endpoint disagreements
+    disagreements: Counter<u64>,
+}
+
+impl Debug for FailoverTransport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FailoverTransport")
+            .field("name", &self.0.name)
+            .field("urls", &self.0.urls)
+            .field("quorum", &self.0.quorum)
+            .finish()
+    }
+}
+
+impl FailoverTransport {
+    pub fn new(
+        name: &'static str,
+        urls: Vec<String>,
+        quorum: Option<usize>,
+        quorum_methods: &'static [&'static str],
+    ) -> anyhow::Result<Self> {
+        if urls.is_empty() {
+            bail!("No {name} endpoints configured.");
+        }
+        if let Some(quorum) = quorum {
+            if quorum == 0 || quorum > urls.len() {
+                bail!(
+                    "Quorum of {quorum} unreachable with {} {name} endpoints.",
+                    urls.len()
+                );
+            }
+        }
+        let transports = urls
+            .iter()
+            .map(|url| Ok(Http::new(url.as_str().try_into().context(url.clone())?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self(Arc::new(FailoverEndpoints {
+            name,
+            health: EndpointHealth::new(urls.len()),
+            urls,
+            transports,
+            quorum,
+            quorum_methods,
+            disagreements: meter("kailua")
+                .u64_counter("sync.rpc.disagreements")
+                .build(),
+        })))
+    }
+}
+
+impl FailoverEndpoints {
+    /// Sends the request to the healthiest endpoint, failing over to the next one on error.
+    async fn failover(&self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut last_result = None;
+        for i in self.health.ranked() {
+            match self.transports[i].clone().call(request.clone()).await {
+                Ok(response) => {
+                    if let Some(err) = endpoint_error(&response) {
+                        self.health.record(i, false);
+                        warn!("{} endpoint {i} returned an error: {err:?}", self.name);
+                        last_result = Some(Ok(response));
+                        continue;
+                    }
+                    self.health.record(i, true);
+                    return Ok(response);
+                }
+                Err(err) => {
+                    self.health.record(i, false);
+                    warn!("Request to {} endpoint {} failed: {err:?}", self.name, i);
+                    last_result = Some(Err(err));
+                }
+            }
+        }
+        last_result.expect("No endpoints")
+    }
+
+    /// Returns the highest block number reached by enough endpoints.
+    async fn common_block_number(&self, quorum: usize) -> Result<u64, TransportError> {
+        let request = Request::new("eth_blockNumber", Id::Number(0), ())
+            .serialize()
+            .map_err(TransportErrorKind::custom)?;
+        let responses = join_all(self.transports.iter().map(|transport| {
+            transport
+                .clone()
+                .call(RequestPacket::Single(request.clone()))
+        }))
+        .await;
+        let block_numbers = responses
+            .into_iter()
+            .filter_map(|response| match response {
+                Ok(ResponsePacket::Single(response)) => response
+                    .payload
+                    .as_success()
+                    .and_then(|result| serde_json::from_str::<U64>(result.get()).ok()),
+                _ => None,
+            })
+            .map(|block_number| block_number.to::<u64>())
+            .sorted_by_key(|block_number| Reverse(*block_number))
+            .collect_vec();
+        block_numbers.get(quorum - 1).copied().ok_or_else(|| {
+            TransportErrorKind::custom_str(&format!(
+                "Only {} of {quorum} required {} endpoints reported a block number.",
+                block_numbers.len(),
+                self.name
+            ))
+        })
+    }
+
+    /// Pins reads of the latest state to the highest block reached by enough endpoints, so that
+    /// endpoints a block apart can agree on the result.
+    async fn pin_block(
+        &self,
+        request: SerializedRequest,
+        quorum: usize,
+    ) -> Result<SerializedRequest, TransportError> {
+        let Some(index) = block_param_index(request.method()) else {
+            return Ok(request);
+        };
+        let mut params = match request.params() {
+            Some(params) => serde_json::from_str::<Vec<Value>>(params.get())
+                .map_err(TransportErrorKind::custom)?,
+            None => vec![],
+        };
+        if !is_unpinned(&params, index) {
+            return Ok(request);
+        }
+        let block_number = self.common_block_number(quorum).await?;
+        if params.len() <= index {
+            params.resize(index + 1, Value::Null);
+        }
+        params[index] = Value::String(format!("{block_number:#x}"));
+        Request::new(request.method().to_string(), request.id().clone(), params)
+            .serialize()
+            .map_err(TransportErrorKind::custom)
+    }
+
+    /// Returns whether the method is security-critical.
+    fn is_quorum_method(&self, method: &str) -> bool {
+        self.quorum_methods.iter().any(|m| *m == method)
+    }
+
+    /// Reports that the endpoints returned distinct responses to the method.
+    fn report_disagreement(&self, method: &str, distinct: usize, responses: usize) {
+        self.disagreements.add(
+            1,
+            &[
+                KeyValue::new("endpoints", self.name),
+                KeyValue::new("method", method.to_string()),
+            ],
+        );
+        error!(
+            "{} endpoints disagree on {method}: {distinct} distinct responses from {responses} endpoints.",
+            self.name,
+        );
+    }
+
+    /// Sends each request of the batch on its own, such that security-critical reads are only
+    /// answered once enough endpoints agree on them.
+    async fn quorum_batch(
+        &self,
+        requests: Vec<SerializedRequest>,
+        quorum: usize,
+    ) -> Result<ResponsePacket, TransportError> {
+        let responses = join_all(requests.into_iter().map(|request| async move {
+            let response = match self.is_quorum_method(request.method()) {
+                true => self.quorum(request, quorum).await?,
+                false => self.failover(RequestPacket::Single(request)).await?,
+            };
+            match response {
+                ResponsePacket::Single(response) => Ok(response),
+                ResponsePacket::Batch(_) => Err(TransportErrorKind::custom_str(&format!(
+                    "Unexpected batch response from {} endpoints.",
+                    self.name
+                ))),
+            }
+        }))
+        .await;
+        Ok(ResponsePacket::Batch(
+            responses.into_iter().collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+
+    /// Returns the reference to the given L2 block as reported by the endpoint.
+    async fn l2_block_ref(&self, index: usize, block_number: u64) -> anyhow::Result<Value> {
+        let request = Request::new(
+            "optimism_outputAtBlock",
+            Id::Number(0),
+            [format!("{block_number:#x}")],
+        )
+        .serialize()?;
+        let ResponsePacket::Single(response) = self.transports[index]
+            .clone()
+            .call(RequestPacket::Single(request))
+            .await?
+        else {
+            bail!("Unexpected batch response.");
+        };
+        let Some(result) = response.payload.as_success() else {
+            bail!("Error response: {:?}", response.payload);
+        };
+        let output = serde_json::from_str::<Value>(result.get())?;
+        Ok(output["blockRef"].clone())
+    }
+
+    /// Returns the sync status at the highest safe head reached by enough endpoints, provided
+    /// that enough endpoints agree on the hash of that block.
+    async fn quorum_sync_status(
+        &self,
+        id: Id,
+        results: Vec<(usize, Value)>,
+        quorum: usize,
+    ) -> Result<ResponsePacket, TransportError> {
+        let method = "optimism_syncStatus";
+        let heads = results
+            .iter()
+            .filter_map(|(_, status)| safe_head(status))
+            .map(|(number, _)| number)
+            .sorted_by_key(|number| Reverse(*number))
+            .collect_vec();
+        let Some(block_number) = heads.get(quorum - 1).copied() else {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "Only {} of {quorum} required {} endpoints reported a safe head.",
+                heads.len(),
+                self.name
+            )));
+        };
+        // Look up the agreed block on the endpoints that are further ahead
+        let block_refs = join_all(results.iter().map(|(i, status)| async move {
+            match safe_head(status) {
+                Some((number, _)) if number == block_number => Some(status["safe_l2"].clone()),
+                Some((number, _)) if number > block_number => {
+                    match self.l2_block_ref(*i, block_number).await {
+                        Ok(block_ref) => Some(block_ref),
+                        Err(err) => {
+                            self.health.record(*i, false);
+                            warn!(
+                                "{} endpoint {i} failed to report block {block_number}: {err:?}",
+                                self.name
+                            );
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            }
+        }))
+        .await;
+        // Group the endpoints by block hash
+        let candidates = results
+            .into_iter()
+            .zip(block_refs)
+            .filter_map(|((_, status), block_ref)| {
+                let block_ref = block_ref?;
+                let hash = block_ref["hash"].as_str()?.to_string();
+                Some((hash, status, block_ref))
+            })
+            .collect_vec();
+        let counts = candidates.iter().map(|(hash, ..)| hash).counts();
+        let Some((agreed, count)) = counts.iter().max_by_key(|(_, count)| **count) else {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "No {} endpoints reported safe head {block_number}.",
+                self.name
+            )));
+        };
+        if counts.len() > 1 {
+            self.report_disagreement(method, counts.len(), candidates.len());
+        }
+        if *count < quorum {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "No quorum of {quorum} {} endpoints on safe head {block_number} (best agreement: {count}).",
+                self.name
+            )));
+        }
+        let agreed = (*agreed).clone();
+        let (_, status, block_ref) = candidates
+            .into_iter()
+            .find(|(hash, ..)| *hash == agreed)
+            .unwrap();
+        let status = rewind_safe_head(status, block_ref);
+        Ok(ResponsePacket::Single(alloy::rpc::json_rpc::Response {
+            id,
+            payload: ResponsePayload::Success(
+                serde_json::value::to_raw_value(&status).map_err(TransportErrorKind::custom)?,
+            ),
+        }))
+    }
+
+    /// Sends the request to all endpoints and returns a response only if enough endpoints agree.
+    async fn quorum(
+        &self,
+        request: SerializedRequest,
+        quorum: usize,
+    ) -> Result<ResponsePacket, TransportError> {
+        let request = self.pin_block(request, quorum).await?;
+        let id = request.id().clone();
+        let method = request.method().to_string();
+        let request = RequestPacket::Single(request);
+        let responses = join_all(
+            self.transports
+                .iter()
+                .map(|transport| transport.clone().call(request.clone())),
+        )
+        .await;
+        let mut results = Vec::with_capacity(responses.len());
+        for (i, response) in responses.into_iter().enumerate() {
+            match response {
+                Ok(ResponsePacket::Single(response)) => {
+                    if let ResponsePayload::Failure(err) = &response.payload {
+                        if is_endpoint_error(err) {
+                            self.health.record(i, false);
+                            warn!("{} endpoint {i} returned an error: {err:?}", self.name);
+                            continue;
+                        }
+                    }
+                    self.health.record(i, true);
+                    results.push((i, response));
+                }
+                Ok(ResponsePacket::Batch(_)) => {
+                    self.health.record(i, false);
+                    warn!("Unexpected batch response from {} endpoint {i}.", self.name);
+                }
+                Err(err) => {
+                    self.health.record(i, false);
+                    warn!("Request to {} endpoint {i} failed: {err:?}", self.name);
+                }
+            }
+        }
+        if results.len() < quorum {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "Only {} of {quorum} required {} endpoints responded to {method}.",
+                results.len(),
+                self.name
+            )));
+        }
+
+        // Agree on the highest safe head reached by enough endpoints
+        if method == "optimism_syncStatus" {
+            let results = results
+                .into_iter()
+                .filter_map(|(i, response)| {
+                    let status = response.payload.as_success()?.get();
+                    Some((i, serde_json::from_str::<Value>(status).ok()?))
+                })
+                .collect_vec();
+            return self.quorum_sync_status(id, results, quorum).await;
+        }
+
+        // Group identical responses
+        let key = |payload: &ResponsePayload| match payload {
+            ResponsePayload::Success(result) => Ok(result.get().to_string()),
+            ResponsePayload::Failure(err) => Err((err.code, err.message.to_string())),
+        };
+        let counts = results.iter().map(|(_, r)| key(&r.payload)).counts();
+        let Some((agreed, count)) = counts.iter().max_by_key(|(_, count)| **count) else {
+            unreachable!("Quorum of zero endpoints");
+        };
+        if counts.len() > 1 {
+            self.report_disagreement(&method, counts.len(), results.len());
+        }
+        if *count < quorum {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "No quorum of {quorum} {} endpoints on {method} (best agreement: {count}).",
+                self.name
+            )));
+        }
+        let (_, response) = results
+            .into_iter()
+            .find(|(_, r)| &key(&r.payload) == agreed)
+            .unwrap();
+        Ok(ResponsePacket::Single(response))
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let endpoints = self.0.clone();
+        Box::pin(async move {
+            match (endpoints.quorum, request) {
+                (Some(quorum), RequestPacket::Single(request))
+                    if endpoints.is_quorum_method(request.method()) =>
+                {
+                    endpoints.quorum(request, quorum).await
+                }
+                (Some(quorum), RequestPacket::Batch(requests))
+                    if requests
+                        .iter()
+                        .any(|request| endpoints.is_quorum_method(request.method())) =>
+                {
+                    endpoints.quorum_batch(requests, quorum).await
+                }
+                (_, request) => endpoints.failover(request).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::B256;
+
+    #[test]
+    fn test_endpoint_health() {
+        let health = EndpointHealth::new(3);
+        assert_eq!(health.ranked(), vec![0, 1, 2]);
+        // failing endpoints are demoted
+        health.record(0, false);
+        assert_eq!(health.ranked(), vec![1, 2, 0]);
+        health.record(1, false);
+        health.record(1, false);
+        assert_eq!(health.ranked(), vec![2, 0, 1]);
+        // recovered endpoints are promoted again
+        for _ in 0..FAILURE_PENALTY {
+            health.record(0, true);
+        }
+        assert_eq!(health.ranked(), vec![0, 2, 1]);
+        // scores are bounded
+        health.record(2, true);
+        assert_eq!(health.0[2].load(Ordering::Relaxed), MAX_HEALTH_SCORE);
+        for _ in 0..8 {
+            health.record(1, false);
+        }
+        assert_eq!(health.0[1].load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_endpoint_errors() {
+        let error = |code: i64, message: &'static str| ErrorPayload {
+            code,
+            message: message.into(),
+            data: None,
+        };
+        assert!(is_endpoint_error(&error(-32000, "header not found")));
+        assert!(is_endpoint_error(&error(-32603, "internal error")));
+        assert!(!is_endpoint_error(&error(
+            3,
+            "execution reverted: custom error"
+        )));
+        assert!(!is_endpoint_error(&error(-32000, "execution reverted")));
+
+        let response = |payload| alloy::rpc::json_rpc::Response {
+            id: Id::Number(0),
+            payload,
+        };
+        let success =
+            || ResponsePayload::Success(serde_json::value::to_raw_value(&Value::Null).unwrap());
+        assert!(endpoint_error(&ResponsePacket::Single(response(success()))).is_none());
+        assert!(
+            endpoint_error(&ResponsePacket::Single(response(ResponsePayload::Failure(
+                error(3, "execution reverted")
+            ))))
+            .is_none()
+        );
+        let batch = ResponsePacket::Batch(vec![
+            response(success()),
+            response(ResponsePayload::Failure(error(-32000, "missing trie node"))),
+        ]);
+        assert_eq!(endpoint_error(&batch).unwrap().code, -32000);
+    }
+
+    #[test]
+    fn test_block_pinning() {
+        assert_eq!(block_param_index("eth_call"), Some(1));
+        assert_eq!(block_param_index("eth_getStorageAt"), Some(2));
+        assert_eq!(block_param_index("eth_blockNumber"), None);
+        let call = serde_json::json!({"to": "0x0000000000000000000000000000000000000001"});
+        assert!(is_unpinned(&[call.clone()], 1));
+        assert!(is_unpinned(&[call.clone(), Value::Null], 1));
+        assert!(is_unpinned(&[call.clone(), "latest".into()], 1));
+        assert!(is_unpinned(&[call.clone(), "pending".into()], 1));
+        assert!(!is_unpinned(&[call.clone(), "0x10".into()], 1));
+        assert!(!is_unpinned(&[call.clone(), "finalized".into()], 1));
+        assert!(!is_unpinned(
+            &[call, serde_json::json!({"blockHash": B256::ZERO})],
+            1
+        ));
+    }
+
+    #[test]
+    fn test_safe_head_agreement() {
+        let block_ref =
+            |number: u64, hash: &str| serde_json::json!({"number": number, "hash": hash});
+        let status = serde_json::json!({
+            "safe_l2": block_ref(12, "0x0c"),
+            "finalized_l2": block_ref(11, "0x0b"),
+        });
+        assert_eq!(safe_head(&status), Some((12, "0x0c".to_string())));
+        assert_eq!(
+            safe_head(&serde_json::json!({"safe_l2": {"number": 12}})),
+            None
+        );
+        // rewinding keeps the finalized head below the safe head
+        let rewound = rewind_safe_head(status.clone(), block_ref(11, "0x0b"));
+        assert_eq!(safe_head(&rewound), Some((11, "0x0b".to_string())));
+        assert_eq!(rewound["finalized_l2"], block_ref(11, "0x0b"));
+        let rewound = rewind_safe_head(status, block_ref(10, "0x0a"));
+        assert_eq!(safe_head(&rewound), Some((10, "0x0a".to_string())));
+        assert_eq!(rewound["finalized_l2"], block_ref(10, "0x0a"));
+    }
+}
@@ -0,0 +1,1336 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::deployment::SyncDeployment;
+use crate::multicall::{IMulticall3, MULTICALL3_ADDRESS};
+use crate::proposal::Proposal;
+use crate::provider::optimism::L2_TO_L1_MESSAGE_PASSER;
+use crate::provider::{BlobSource, ChainReader};
+use crate::KAILUA_GAME_TYPE;
+use alloy::consensus::BlobTransactionSidecar;
+use alloy::eips::eip4844::kzg_to_versioned_hash;
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::{keccak256, Address, Bytes, LogData, B256, U128, U256, U64};
+use alloy::rpc::json_rpc::{
+    ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest,
+};
+use alloy::rpc::types::{Block, BlockTransactions, Filter, Header, Log, TransactionRequest};
+use alloy::sol_types::{SolCall, SolEvent, SolValue};
+use alloy::transports::{TransportError, TransportFut};
+use alloy_rpc_types_beacon::sidecar::BlobData;
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use kailua_contracts::*;
+use kailua_kona::precondition::blobs_hash;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::value::RawValue;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::iter::once;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+
+/// Number of seconds between two consecutive in-memory L1 blocks
+pub const IN_MEMORY_L1_BLOCK_TIME: u64 = 12;
+
+/// The RPC endpoint served by an [InMemoryTransport]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InMemoryLayer {
+    /// Ethereum RPC of the L1 chain
+    L1,
+    /// Optimism RPC of the op-node
+    OpNode,
+    /// Ethereum RPC of the L2 chain
+    L2,
+}
+
+/// A scripted chain backend that serves L1 blocks, logs, contract calls, blobs and L2 outputs from
+/// memory for deterministic offline testing.
+///
+/// Contract calls are matched exactly by target address and calldata. Unscripted calls revert with
+/// a JSON-RPC execution error, which tests surface by installing a bounded retry policy.
+#[derive(Clone, Debug)]
+pub struct InMemoryChain(Arc<Mutex<InMemoryState>>);
+
+#[derive(Debug)]
+struct InMemoryState {
+    /// Chain id of the L1 chain
+    l1_chain_id: u64,
+    /// Chain id of the L2 chain
+    l2_chain_id: u64,
+    /// Canonical L1 blocks by number
+    l1_blocks: Vec<Block>,
+    /// Number of reorgs applied to the L1 chain
+    l1_forks: u64,
+    /// Scripted contract call results by target address and calldata
+    calls: HashMap<(Address, Bytes), Bytes>,
+    /// Logs emitted in canonical L1 blocks
+    logs: Vec<Log>,
+    /// Scripted L2 output roots by block number
+    outputs: BTreeMap<u64, B256>,
+    /// Number of the safe L2 block
+    safe_l2: u64,
+    /// Number of the finalized L2 block, if it differs from the safe L2 block
+    finalized_l2: Option<u64>,
+    /// Safe L2 block numbers by the L1 block number they were derived from
+    safe_heads: BTreeMap<u64, u64>,
+    /// Blob base fees by the L1 block number from which they apply
+    blob_fees: BTreeMap<u64, u128>,
+    /// Published blobs by versioned hash
+    blobs: HashMap<B256, BlobData>,
+}
+
+impl InMemoryState {
+    fn output_at(&self, l2_block: u64) -> B256 {
+        self.outputs
+            .get(&l2_block)
+            .copied()
+            .unwrap_or_else(|| l2_output_root(l2_block))
+    }
+
+    fn l2_block(&self, tag: BlockNumberOrTag) -> Option<Block> {
+        let number = match tag {
+            BlockNumberOrTag::Number(number) => number,
+            BlockNumberOrTag::Earliest => 0,
+            _ => self.safe_l2,
+        };
+        (number <= self.safe_l2).then(|| Block {
+            header: l2_header(number),
+            uncles: vec![],
+            transactions: BlockTransactions::Hashes(vec![]),
+            withdrawals: None,
+        })
+    }
+
+    fn l1_block(&self, tag: BlockNumberOrTag) -> Option<&Block> {
+        match tag {
+            BlockNumberOrTag::Number(number) => self.l1_blocks.get(number as usize),
+            BlockNumberOrTag::Earliest => self.l1_blocks.first(),
+            _ => self.l1_blocks.last(),
+        }
+    }
+
+    fn blob_fee(&self, l1_block: u64) -> u128 {
+        self.blob_fees
+            .range(..=l1_block)
+            .next_back()
+            .map_or(1, |(_, fee)| *fee)
+    }
+
+    fn call(&self, to: Address, input: Bytes) -> Result<Bytes, String> {
+        self.calls
+            .get(&(to, input.clone()))
+            .cloned()
+            .ok_or_else(|| {
+                format!("execution reverted: unscripted call to {to} with input {input}")
+            })
+    }
+
+    fn l1_head(&self) -> &Block {
+        self.l1_blocks.last().expect("Missing genesis block")
+    }
+
+    fn handle(&self, layer: InMemoryLayer, request: &SerializedRequest) -> Result<Value, String> {
+        let params = request
+            .params()
+            .map(|params| serde_json::from_str::<Vec<Value>>(params.get()))
+            .transpose()
+            .map_err(|err| err.to_string())?
+            .unwrap_or_default();
+        let param = |i: usize| params.get(i).cloned().unwrap_or_default();
+        let method = request.method();
+        let result = match (layer, method) {
+            (InMemoryLayer::L1, "eth_chainId") => json!(U64::from(self.l1_chain_id)),
+            (InMemoryLayer::L2, "eth_chainId") => json!(U64::from(self.l2_chain_id)),
+            (InMemoryLayer::L1, "eth_blockNumber") => {
+                json!(U64::from(self.l1_head().header.number))
+            }
+            (InMemoryLayer::L2, "eth_blockNumber") => json!(U64::from(self.safe_l2)),
+            (InMemoryLayer::L1, "eth_getBlockByNumber") => {
+                json!(self.l1_block(from_param(param(0))?))
+            }
+            (InMemoryLayer::L1, "eth_getBlockByHash") => {
+                let hash: B256 = from_param(param(0))?;
+                json!(self.l1_blocks.iter().find(|b| b.header.hash == hash))
+            }
+            (InMemoryLayer::L1, "eth_blobBaseFee") => {
+                json!(U128::from(self.blob_fee(self.l1_head().header.number + 1)))
+            }
+            (InMemoryLayer::L1, "eth_feeHistory") => {
+                let block_count = from_param::<U64>(param(0))?.to::<u64>();
+                let last_block = self
+                    .l1_block(from_param(param(1))?)
+                    .ok_or_else(|| String::from("unknown block"))?
+                    .header
+                    .number;
+                let oldest_block = last_block + 1 - block_count.clamp(1, last_block + 1);
+                // Fees are reported up to the block following the last one
+                let blocks = oldest_block..=last_block + 1;
+                let count = (last_block + 1 - oldest_block) as usize;
+                json!({
+                    "oldestBlock": U64::from(oldest_block),
+                    "baseFeePerGas": vec![U128::from(1); count + 1],
+                    "gasUsedRatio": vec![0.0; count],
+                    "baseFeePerBlobGas": blocks
+                        .map(|block| U128::from(self.blob_fee(block)))
+                        .collect::<Vec<_>>(),
+                    "blobGasUsedRatio": vec![0.0; count],
+                })
+            }
+            (InMemoryLayer::L1, "eth_getLogs") => {
+                let filter: Filter = from_param(param(0))?;
+                let logs = self
+                    .logs
+                    .iter()
+                    .filter(|log| log_matches(&filter, log))
+                    .collect::<Vec<_>>();
+                json!(logs)
+            }
+            (InMemoryLayer::L1, "eth_call") => {
+                let request: TransactionRequest = from_param(param(0))?;
+                let to = request
+                    .to
+                    .and_then(|kind| kind.to().copied())
+                    .ok_or_else(|| String::from("eth_call without target"))?;
+                let input = request.input.input().cloned().unwrap_or_default();
+                if to == MULTICALL3_ADDRESS {
+                    let calls = IMulticall3::aggregate3Call::abi_decode(&input)
+                        .map_err(|err| err.to_string())?
+                        .calls;
+                    // unscripted calls fail individually like reverting calls
+                    let results = calls
+                        .into_iter()
+                        .map(|call| match self.call(call.target, call.callData) {
+                            Ok(return_data) => IMulticall3::Call3Result {
+                                success: true,
+                                returnData: return_data,
+                            },
+                            Err(_) => IMulticall3::Call3Result {
+                                success: false,
+                                returnData: Bytes::new(),
+                            },
+                        })
+                        .collect::<Vec<_>>();
+                    return Ok(json!(Bytes::from(
+                        IMulticall3::aggregate3Call::abi_encode_returns(&results)
+                    )));
+                }
+                json!(self.call(to, input)?)
+            }
+            (InMemoryLayer::L2, "eth_getBlockByNumber") => {
+                json!(self.l2_block(from_param(param(0))?))
+            }
+            (InMemoryLayer::L2, "eth_getProof") => {
+                let address: Address = from_param(param(0))?;
+                let Some(block) = self.l2_block(from_param(param(2))?) else {
+                    return Err(String::from("L2 block not yet safe"));
+                };
+                let storage_hash = if address == L2_TO_L1_MESSAGE_PASSER {
+                    l2_message_passer_root(block.header.number)
+                } else {
+                    B256::ZERO
+                };
+                json!({
+                    "address": address,
+                    "balance": U256::ZERO,
+                    "codeHash": B256::ZERO,
+                    "nonce": U64::ZERO,
+                    "storageHash": storage_hash,
+                    "accountProof": [],
+                    "storageProof": [],
+                })
+            }
+            (InMemoryLayer::OpNode, "optimism_syncStatus") => json!({
+                "safe_l2": { "number": self.safe_l2 },
+                "finalized_l2": { "number": self.finalized_l2.unwrap_or(self.safe_l2) },
+            }),
+            (InMemoryLayer::OpNode, "optimism_safeHeadAtL1Block") => {
+                let l1_block = from_param::<U64>(param(0))?.to::<u64>();
+                let Some((l1_block, safe_head)) = self.safe_heads.range(..=l1_block).next_back()
+                else {
+                    return Err(format!("No safe head derived by L1 block {l1_block}"));
+                };
+                json!({
+                    "l1Block": { "number": l1_block },
+                    "safeHead": { "number": safe_head },
+                })
+            }
+            (InMemoryLayer::OpNode, "optimism_outputAtBlock") => {
+                let l2_block = from_param::<U64>(param(0))?.to::<u64>();
+                if l2_block > self.safe_l2 {
+                    return Err(format!("L2 block {l2_block} not yet safe"));
+                }
+                json!({ "outputRoot": self.output_at(l2_block) })
+            }
+            _ => return Err(format!("Method {method} not supported on {layer:?}")),
+        };
+        Ok(result)
+    }
+}
+
+/// Returns the synthetic header of the given L2 block.
+fn l2_header(l2_block: u64) -> Header {
+    let inner = alloy::consensus::Header {
+        number: l2_block,
+        state_root: keccak256([b"state".as_slice(), &l2_block.to_be_bytes()].concat()),
+        ..Default::default()
+    };
+    Header {
+        hash: inner.hash_slow(),
+        inner,
+        total_difficulty: None,
+        size: None,
+    }
+}
+
+/// Returns the synthetic storage root of the L2ToL1MessagePasser at the given L2 block.
+fn l2_message_passer_root(l2_block: u64) -> B256 {
+    keccak256([b"withdrawals".as_slice(), &l2_block.to_be_bytes()].concat())
+}
+
+/// Returns the output root of the given L2 block derived from its synthetic execution data.
+fn l2_output_root(l2_block: u64) -> B256 {
+    let header = l2_header(l2_block);
+    keccak256(
+        [
+            B256::ZERO.as_slice(),
+            header.state_root.as_slice(),
+            l2_message_passer_root(l2_block).as_slice(),
+            header.hash.as_slice(),
+        ]
+        .concat(),
+    )
+}
+
+fn from_param<T: DeserializeOwned>(value: Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|err| err.to_string())
+}
+
+fn log_matches(filter: &Filter, log: &Log) -> bool {
+    let block_number = log.block_number.unwrap_or_default();
+    if filter
+        .get_from_block()
+        .is_some_and(|from_block| block_number < from_block)
+        || filter
+            .get_to_block()
+            .is_some_and(|to_block| block_number > to_block)
+    {
+        return false;
+    }
+    if !filter.address.matches(&log.address()) {
+        return false;
+    }
+    filter.topics.iter().enumerate().all(|(i, topic)| {
+        topic.is_empty()
+            || log
+                .topics()
+                .get(i)
+                .is_some_and(|log_topic| topic.matches(log_topic))
+    })
+}
+
+impl Default for InMemoryChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryChain {
+    /// Creates a chain containing only the L1 genesis block.
+    pub fn new() -> Self {
+        let chain = Self(Arc::new(Mutex::new(InMemoryState {
+            l1_chain_id: 1,
+            l2_chain_id: 10,
+            l1_blocks: vec![],
+            l1_forks: 0,
+            calls: Default::default(),
+            logs: vec![],
+            outputs: Default::default(),
+            safe_l2: 0,
+            finalized_l2: None,
+            safe_heads: Default::default(),
+            blob_fees: Default::default(),
+            blobs: Default::default(),
+        })));
+        chain.mine_l1_block();
+        chain
+    }
+
+    fn state(&self) -> MutexGuard<'_, InMemoryState> {
+        self.0.lock().expect("Poisoned in-memory chain")
+    }
+
+    /// Returns a transport serving the given layer from this chain.
+    pub fn transport(&self, layer: InMemoryLayer) -> InMemoryTransport {
+        InMemoryTransport {
+            chain: self.clone(),
+            layer,
+        }
+    }
+
+    /// Appends a new block to the L1 chain and returns its hash.
+    pub fn mine_l1_block(&self) -> B256 {
+        let mut state = self.state();
+        let (number, parent_hash, timestamp) = match state.l1_blocks.last() {
+            Some(parent) => (
+                parent.header.number + 1,
+                parent.header.hash,
+                parent.header.timestamp + IN_MEMORY_L1_BLOCK_TIME,
+            ),
+            None => (0, B256::ZERO, 0),
+        };
+        // Blocks mined after a reorg must not collide with the ones they replace
+        let inner = alloy::consensus::Header {
+            number,
+            parent_hash,
+            timestamp,
+            extra_data: Bytes::from(state.l1_forks.to_be_bytes().to_vec()),
+            ..Default::default()
+        };
+        let header = Header {
+            hash: inner.hash_slow(),
+            inner,
+            total_difficulty: None,
+            size: None,
+        };
+        let hash = header.hash;
+        state.l1_blocks.push(Block {
+            header,
+            uncles: vec![],
+            transactions: BlockTransactions::Hashes(vec![]),
+            withdrawals: None,
+        });
+        hash
+    }
+
+    /// Returns the number, hash and timestamp of the latest L1 block.
+    pub fn l1_head(&self) -> (u64, B256, u64) {
+        let state = self.state();
+        let header = &state.l1_head().header;
+        (header.number, header.hash, header.timestamp)
+    }
+
+    /// Replaces the given number of latest L1 blocks with an empty fork of the same length.
+    ///
+    /// Logs emitted in the removed blocks are discarded. Scripted calls remain unchanged.
+    pub fn reorg_l1(&self, depth: u64) {
+        {
+            let mut state = self.state();
+            let fork_block = state
+                .l1_blocks
+                .len()
+                .checked_sub(depth as usize)
+                .filter(|n| *n > 0)
+                .expect("Cannot reorg the L1 genesis block") as u64;
+            state.l1_blocks.truncate(fork_block as usize);
+            state
+                .logs
+                .retain(|log| log.block_number.is_some_and(|n| n < fork_block));
+            state.l1_forks += 1;
+        }
+        for _ in 0..depth {
+            self.mine_l1_block();
+        }
+    }
+
+    /// Scripts the result of calling the given contract function.
+    ///
+    /// The returned value is encoded as a single value or as a tuple of static values.
+    pub fn mock_call<C: SolCall, R: SolValue>(&self, to: Address, call: C, ret: R) {
+        self.state()
+            .calls
+            .insert((to, call.abi_encode().into()), ret.abi_encode().into());
+    }
+
+    /// Emits a log in the latest L1 block.
+    pub fn mock_log(&self, address: Address, data: LogData) {
+        let mut state = self.state();
+        let header = &state.l1_head().header;
+        let (block_number, block_hash, block_timestamp) =
+            (header.number, header.hash, header.timestamp);
+        let log_index = state.logs.len() as u64;
+        state.logs.push(Log {
+            inner: alloy::primitives::Log { address, data },
+            block_hash: Some(block_hash),
+            block_number: Some(block_number),
+            block_timestamp: Some(block_timestamp),
+            log_index: Some(log_index),
+            ..Default::default()
+        });
+    }
+
+    /// Emits the given event in the latest L1 block.
+    pub fn mock_event<E: SolEvent>(&self, address: Address, event: &E) {
+        self.mock_log(address, event.encode_log_data());
+    }
+
+    /// Returns the output root reported by the op-node at the given L2 block.
+    ///
+    /// Unless explicitly scripted, output roots match the ones computed from the synthetic L2
+    /// execution data served by the chain.
+    pub fn output_at(&self, l2_block: u64) -> B256 {
+        self.state().output_at(l2_block)
+    }
+
+    /// Overrides the output root reported by the op-node at the given L2 block.
+    pub fn set_output(&self, l2_block: u64, output_root: B256) {
+        self.state().outputs.insert(l2_block, output_root);
+    }
+
+    /// Sets the number of the safe L2 block up to which outputs are available.
+    pub fn set_safe_l2(&self, l2_block: u64) {
+        self.state().safe_l2 = l2_block;
+    }
+
+    /// Sets the number of the finalized L2 block reported in the sync status.
+    pub fn set_finalized_l2(&self, l2_block: u64) {
+        self.state().finalized_l2 = Some(l2_block);
+    }
+
+    /// Records the safe L2 block derived from L1 blocks up to the given one.
+    pub fn set_safe_head_at_l1(&self, l1_block: u64, l2_block: u64) {
+        self.state().safe_heads.insert(l1_block, l2_block);
+    }
+
+    /// Sets the blob base fee of the given L1 block and all blocks after it.
+    pub fn set_blob_base_fee(&self, l1_block: u64, blob_fee: u128) {
+        self.state().blob_fees.insert(l1_block, blob_fee);
+    }
+
+    /// Publishes the blobs of the given sidecar and returns their versioned hashes.
+    pub fn add_sidecar(&self, sidecar: &BlobTransactionSidecar) -> Vec<B256> {
+        let mut state = self.state();
+        sidecar
+            .blobs
+            .iter()
+            .zip(&sidecar.commitments)
+            .zip(&sidecar.proofs)
+            .enumerate()
+            .map(|(index, ((blob, commitment), proof))| {
+                let blob_hash = kzg_to_versioned_hash(commitment.as_slice());
+                state.blobs.insert(
+                    blob_hash,
+                    BlobData {
+                        index: index as u64,
+                        blob: Box::new(*blob),
+                        kzg_commitment: *commitment,
+                        kzg_proof: *proof,
+                        signed_block_header: Default::default(),
+                        kzg_commitment_inclusion_proof: vec![],
+                    },
+                );
+                blob_hash
+            })
+            .collect()
+    }
+
+    /// Scripts the contracts queried to load the given Kailua deployment.
+    pub fn script_deployment(&self, system_config: Address, deployment: &SyncDeployment) {
+        self.mock_call(
+            system_config,
+            SystemConfig::disputeGameFactoryCall {},
+            deployment.factory,
+        );
+        self.mock_call(
+            deployment.factory,
+            IDisputeGameFactory::gameCountCall {},
+            U256::ZERO,
+        );
+        self.script_game_impl(deployment.factory, deployment.game);
+        self.script_game(deployment);
+    }
+
+    /// Scripts the parameters of the KailuaGame implementation of the given deployment.
+    pub fn script_game(&self, deployment: &SyncDeployment) {
+        let game = deployment.game;
+        self.mock_call(
+            game,
+            KailuaGame::KAILUA_TREASURYCall {},
+            deployment.treasury,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::RISC_ZERO_VERIFIERCall {},
+            deployment.verifier,
+        );
+        self.mock_call(game, KailuaGame::FPVM_IMAGE_IDCall {}, deployment.image_id);
+        self.mock_call(
+            game,
+            KailuaGame::ROLLUP_CONFIG_HASHCall {},
+            deployment.cfg_hash,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::PROPOSAL_OUTPUT_COUNTCall {},
+            deployment.proposal_output_count,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::OUTPUT_BLOCK_SPANCall {},
+            deployment.output_block_span,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::PROPOSAL_BLOBSCall {},
+            deployment.proposal_blobs,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::GAME_TYPECall {},
+            deployment.game_type as u32,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::DISPUTE_GAME_FACTORYCall {},
+            deployment.factory,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::MAX_CLOCK_DURATIONCall {},
+            deployment.timeout,
+        );
+        self.mock_call(
+            game,
+            KailuaGame::GENESIS_TIME_STAMPCall {},
+            U256::from(deployment.genesis_time),
+        );
+        self.mock_call(
+            game,
+            KailuaGame::L2_BLOCK_TIMECall {},
+            U256::from(deployment.block_time),
+        );
+
+        // treasuries start out unresolved
+        self.state()
+            .calls
+            .entry((
+                deployment.treasury,
+                KailuaTreasury::lastResolvedCall {}.abi_encode().into(),
+            ))
+            .or_insert_with(|| Address::ZERO.abi_encode().into());
+    }
+
+    /// Sets the KailuaGame implementation in the dispute game factory.
+    pub fn script_game_impl(&self, factory: Address, game: Address) {
+        self.mock_call(
+            factory,
+            IDisputeGameFactory::gameImplsCall::new((KAILUA_GAME_TYPE,)),
+            game,
+        );
+        self.mock_log(
+            factory,
+            LogData::new_unchecked(
+                vec![
+                    IDisputeGameFactory::ImplementationSet::SIGNATURE_HASH,
+                    game.into_word(),
+                    B256::from(U256::from(KAILUA_GAME_TYPE)),
+                ],
+                Bytes::new(),
+            ),
+        );
+    }
+
+    /// Creates the given proposal in a new L1 block and registers it in the factory of the
+    /// deployment. Returns the versioned hashes of the published proposal blobs.
+    pub fn script_proposal(
+        &self,
+        deployment: &SyncDeployment,
+        proposal: &ScriptedProposal,
+    ) -> anyhow::Result<Vec<B256>> {
+        // the factory sets the l1 head to the parent of the block the game was created in
+        let (_, l1_head, _) = self.l1_head();
+        self.mine_l1_block();
+        let (_, _, created_at) = self.l1_head();
+
+        let contract = proposal.contract;
+        let is_treasury = proposal.parent_contract == contract;
+        let proposer = if is_treasury {
+            contract
+        } else {
+            proposal.proposer
+        };
+        self.mock_call(
+            contract,
+            KailuaGame::parentGameCall {},
+            proposal.parent_contract,
+        );
+        self.mock_call(
+            contract,
+            KailuaGame::KAILUA_TREASURYCall {},
+            deployment.treasury,
+        );
+        self.mock_call(
+            contract,
+            KailuaGame::gameIndexCall {},
+            U256::from(proposal.index),
+        );
+        self.mock_call(contract, KailuaGame::createdAtCall {}, created_at);
+        self.mock_call(contract, KailuaGame::rootClaimCall {}, proposal.output_root);
+        self.mock_call(
+            contract,
+            KailuaGame::l2BlockNumberCall {},
+            U256::from(proposal.output_block_number),
+        );
+        self.mock_call(contract, KailuaGame::l1HeadCall {}, l1_head);
+        self.mock_call(contract, KailuaGame::resolvedAtCall {}, 0u64);
+        self.mock_call(contract, KailuaGame::statusCall {}, 0u8);
+        self.mock_call(contract, KailuaGame::validChildSignatureCall {}, B256::ZERO);
+
+        // publish intermediate outputs of game instances
+        let blob_hashes = if is_treasury {
+            vec![]
+        } else {
+            let sidecar = Proposal::create_sidecar(&proposal.io_field_elements)?;
+            self.add_sidecar(&sidecar)
+        };
+        if !is_treasury {
+            self.mock_call(
+                contract,
+                KailuaGame::parentGameIndexCall {},
+                proposal.parent,
+            );
+            self.mock_call(contract, KailuaGame::proposerCall {}, proposer);
+            self.mock_call(
+                contract,
+                KailuaGame::PROPOSAL_BLOBSCall {},
+                blob_hashes.len() as u64,
+            );
+            self.mock_call(
+                contract,
+                KailuaGame::PROPOSAL_OUTPUT_COUNTCall {},
+                deployment.proposal_output_count,
+            );
+            for (i, blob_hash) in blob_hashes.iter().enumerate() {
+                self.mock_call(
+                    contract,
+                    KailuaGame::proposalBlobHashesCall::new((U256::from(i),)),
+                    *blob_hash,
+                );
+            }
+        }
+        self.mock_call(
+            contract,
+            KailuaGame::signatureCall {},
+            blobs_hash(once(&proposal.output_root).chain(&blob_hashes)),
+        );
+
+        // register proposal in factory
+        self.mock_call(
+            deployment.factory,
+            IDisputeGameFactory::gameAtIndexCall::new((U256::from(proposal.index),)),
+            (KAILUA_GAME_TYPE, created_at, contract),
+        );
+        self.mock_call(
+            deployment.factory,
+            IDisputeGameFactory::gameCountCall {},
+            U256::from(proposal.index + 1),
+        );
+        self.mock_log(
+            deployment.factory,
+            LogData::new_unchecked(
+                vec![
+                    IDisputeGameFactory::DisputeGameCreated::SIGNATURE_HASH,
+                    contract.into_word(),
+                    B256::from(U256::from(KAILUA_GAME_TYPE)),
+                    proposal.output_root,
+                ],
+                Bytes::new(),
+            ),
+        );
+
+        // proposers start out uneliminated
+        self.state()
+            .calls
+            .entry((
+                deployment.treasury,
+                KailuaTreasury::eliminationRoundCall::new((proposer,))
+                    .abi_encode()
+                    .into(),
+            ))
+            .or_insert_with(|| U256::ZERO.abi_encode().into());
+
+        Ok(blob_hashes)
+    }
+
+    /// Resolves the given proposal in a new L1 block, marking it as the last resolved proposal of
+    /// its treasury if it was correct.
+    pub fn resolve_proposal(&self, deployment: &SyncDeployment, contract: Address, correct: bool) {
+        self.mine_l1_block();
+        let (_, _, resolved_at) = self.l1_head();
+        // IN_PROGRESS: 0, CHALLENGER_WINS: 1, DEFENDER_WINS: 2
+        let status = if correct { 2u8 } else { 1u8 };
+        self.mock_call(contract, KailuaGame::resolvedAtCall {}, resolved_at);
+        self.mock_call(contract, KailuaGame::statusCall {}, status);
+        if correct {
+            self.mock_call(
+                deployment.treasury,
+                KailuaTreasury::lastResolvedCall {},
+                contract,
+            );
+        }
+        self.mock_log(
+            contract,
+            LogData::new_unchecked(
+                vec![
+                    KailuaTournament::Resolved::SIGNATURE_HASH,
+                    B256::from(U256::from(status)),
+                ],
+                Bytes::new(),
+            ),
+        );
+    }
+}
+
+/// Description of a proposal to create on an [InMemoryChain]
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedProposal {
+    /// Address of the proposal contract
+    pub contract: Address,
+    /// Factory index of the proposal
+    pub index: u64,
+    /// Factory index of the parent proposal
+    pub parent: u64,
+    /// Address of the parent proposal contract (same as `contract` for treasury instances)
+    pub parent_contract: Address,
+    /// Address of the proposer (ignored for treasury instances)
+    pub proposer: Address,
+    /// Proposed output root
+    pub output_root: B256,
+    /// L2 block number of the proposed output root
+    pub output_block_number: u64,
+    /// Field elements of the proposed intermediate outputs (ignored for treasury instances)
+    pub io_field_elements: Vec<U256>,
+}
+
+#[async_trait]
+impl BlobSource for InMemoryChain {
+    async fn get_blob(&self, _timestamp: u64, blob_hash: B256) -> anyhow::Result<BlobData> {
+        self.state()
+            .blobs
+            .get(&blob_hash)
+            .cloned()
+            .ok_or_else(|| anyhow!("Blob {blob_hash} not found."))
+    }
+}
+
+#[async_trait]
+impl ChainReader for InMemoryChain {
+    async fn l1_header(&self, block: BlockNumberOrTag) -> anyhow::Result<Option<Header>> {
+        Ok(self
+            .state()
+            .l1_block(block)
+            .map(|block| block.header.clone()))
+    }
+
+    async fn l1_header_by_hash(&self, hash: B256) -> anyhow::Result<Option<Header>> {
+        Ok(self
+            .state()
+            .l1_blocks
+            .iter()
+            .find(|block| block.header.hash == hash)
+            .map(|block| block.header.clone()))
+    }
+
+    async fn l1_logs(&self, filter: &Filter) -> anyhow::Result<Vec<Log>> {
+        Ok(self
+            .state()
+            .logs
+            .iter()
+            .filter(|log| log_matches(filter, log))
+            .cloned()
+            .collect())
+    }
+
+    async fn l1_call(&self, to: Address, data: Bytes) -> anyhow::Result<Bytes> {
+        self.state().call(to, data).map_err(|err| anyhow!(err))
+    }
+
+    async fn sync_status(&self) -> anyhow::Result<Value> {
+        let state = self.state();
+        Ok(json!({
+            "safe_l2": { "number": state.safe_l2 },
+            "finalized_l2": { "number": state.finalized_l2.unwrap_or(state.safe_l2) },
+        }))
+    }
+
+    async fn safe_head_at_l1_block(&self, l1_block_number: u64) -> anyhow::Result<u64> {
+        self.state()
+            .safe_heads
+            .range(..=l1_block_number)
+            .next_back()
+            .map(|(_, safe_head)| *safe_head)
+            .ok_or_else(|| anyhow!("No safe head derived by L1 block {l1_block_number}"))
+    }
+
+    async fn op_output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256> {
+        let state = self.state();
+        if output_block_number > state.safe_l2 {
+            bail!("L2 block {output_block_number} not yet safe");
+        }
+        Ok(state.output_at(output_block_number))
+    }
+
+    async fn l2_output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256> {
+        if output_block_number > self.state().safe_l2 {
+            bail!("L2 block {output_block_number} not yet safe");
+        }
+        Ok(l2_output_root(output_block_number))
+    }
+}
+
+/// An alloy transport serving one RPC endpoint of an [InMemoryChain]
+#[derive(Clone, Debug)]
+pub struct InMemoryTransport {
+    chain: InMemoryChain,
+    layer: InMemoryLayer,
+}
+
+impl InMemoryTransport {
+    fn respond(&self, request: &SerializedRequest) -> Response {
+        let payload = match self
+            .chain
+            .state()
+            .handle(self.layer, request)
+            .and_then(|value| to_raw_value(&value))
+        {
+            Ok(result) => ResponsePayload::Success(result),
+            Err(message) => ResponsePayload::Failure(ErrorPayload {
+                code: -32000,
+                message: message.into(),
+                data: None,
+            }),
+        };
+        Response {
+            id: request.id().clone(),
+            payload,
+        }
+    }
+}
+
+fn to_raw_value<T: Serialize>(value: &T) -> Result<Box<RawValue>, String> {
+    serde_json::value::to_raw_value(value).map_err(|err| err.to_string())
+}
+
+impl tower::Service<RequestPacket> for InMemoryTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let response = match request {
+            RequestPacket::Single(request) => ResponsePacket::Single(self.respond(&request)),
+            RequestPacket::Batch(requests) => {
+                ResponsePacket::Batch(requests.iter().map(|r| self.respond(r)).collect())
+            }
+        };
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// A scripted Kailua deployment for offline tests of the sync agent and its users
+pub mod fixtures {
+    use super::*;
+    use crate::agent::SyncAgent;
+    use crate::provider::SyncProvider;
+    use crate::retry::RetryArgs;
+    use alloy::primitives::address;
+    use kailua_kona::blobs::hash_to_fe;
+    use kona_genesis::RollupConfig;
+    use std::path::Path;
+
+    pub const SYSTEM_CONFIG: Address = address!("0x0000000000000000000000000000000000000001");
+    pub const FACTORY: Address = address!("0x0000000000000000000000000000000000000002");
+    pub const TREASURY: Address = address!("0x0000000000000000000000000000000000000003");
+    pub const GAME: Address = address!("0x0000000000000000000000000000000000000004");
+    pub const ANCHOR: Address = address!("0x0000000000000000000000000000000000000010");
+    pub const HONEST_GAME: Address = address!("0x0000000000000000000000000000000000000011");
+    pub const FAULTY_GAME: Address = address!("0x0000000000000000000000000000000000000012");
+    pub const HONEST_PROPOSER: Address = address!("0x00000000000000000000000000000000000000a1");
+    pub const FAULTY_PROPOSER: Address = address!("0x00000000000000000000000000000000000000a2");
+
+    /// Returns a deployment of four single-block outputs per proposal.
+    pub fn deployment() -> SyncDeployment {
+        SyncDeployment {
+            treasury: TREASURY,
+            game: GAME,
+            verifier: Address::ZERO,
+            image_id: B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(
+                kailua_build::KAILUA_FPVM_KONA_ID,
+            )),
+            cfg_hash: B256::ZERO,
+            proposal_output_count: 4,
+            output_block_span: 1,
+            proposal_blobs: 1,
+            game_type: KAILUA_GAME_TYPE as u8,
+            factory: FACTORY,
+            timeout: 3600,
+            genesis_time: 0,
+            block_time: 2,
+        }
+    }
+
+    /// Returns the treasury instance anchoring the deployment at l2 block zero.
+    pub fn anchor_proposal(chain: &InMemoryChain) -> ScriptedProposal {
+        ScriptedProposal {
+            contract: ANCHOR,
+            index: 0,
+            parent: 0,
+            parent_contract: ANCHOR,
+            output_root: chain.output_at(0),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a chain on which the given deployment is anchored at a resolved treasury instance.
+    pub fn anchored_chain(deployment: &SyncDeployment) -> InMemoryChain {
+        let chain = InMemoryChain::new();
+        chain.script_deployment(SYSTEM_CONFIG, deployment);
+        chain.set_safe_l2(16);
+        chain
+            .script_proposal(deployment, &anchor_proposal(&chain))
+            .unwrap();
+        chain.resolve_proposal(deployment, ANCHOR, true);
+        chain
+    }
+
+    /// Returns a correct proposal extending the given parent proposal.
+    pub fn child_proposal(
+        chain: &InMemoryChain,
+        deployment: &SyncDeployment,
+        parent: &ScriptedProposal,
+        contract: Address,
+        index: u64,
+        proposer: Address,
+    ) -> ScriptedProposal {
+        let output_block_number = parent.output_block_number + deployment.blocks_per_proposal();
+        ScriptedProposal {
+            contract,
+            index,
+            parent: parent.index,
+            parent_contract: parent.contract,
+            proposer,
+            output_root: chain.output_at(output_block_number),
+            output_block_number,
+            io_field_elements: (1..deployment.proposal_output_count)
+                .map(|i| {
+                    hash_to_fe(
+                        chain.output_at(
+                            parent.output_block_number + i * deployment.output_block_span,
+                        ),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns a correct proposal extending the anchor.
+    pub fn game_proposal(
+        chain: &InMemoryChain,
+        deployment: &SyncDeployment,
+        contract: Address,
+        index: u64,
+        proposer: Address,
+    ) -> ScriptedProposal {
+        child_proposal(
+            chain,
+            deployment,
+            &anchor_proposal(chain),
+            contract,
+            index,
+            proposer,
+        )
+    }
+
+    /// Bounds the retries of all endpoints so that unscripted queries fail tests quickly.
+    pub fn install_retry_policy() {
+        RetryArgs {
+            retry_policy: Some("attempts=3,backoff=1,max-backoff=1,timeout=5".to_string()),
+            ..Default::default()
+        }
+        .install()
+        .expect("RetryArgs::install");
+    }
+
+    /// Creates an agent synchronizing with the given chain.
+    pub async fn sync_agent(
+        chain: &InMemoryChain,
+        data_dir: &Path,
+        log_sync_range: Option<u64>,
+    ) -> SyncAgent {
+        install_retry_policy();
+        let config = RollupConfig {
+            l1_system_config_address: SYSTEM_CONFIG,
+            ..Default::default()
+        };
+        SyncAgent::from_provider(
+            SyncProvider::in_memory(chain),
+            config,
+            data_dir.to_path_buf(),
+            None,
+            None,
+            log_sync_range,
+        )
+        .await
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::*;
+    use super::*;
+    use crate::provider::{OutputSource, RpcChainReader, SyncProvider};
+    use alloy::providers::Provider;
+
+    #[tokio::test]
+    async fn test_in_memory_provider() {
+        let chain = InMemoryChain::new();
+        let provider = SyncProvider::in_memory(&chain);
+        let hash = chain.mine_l1_block();
+        assert_eq!(provider.l1_provider.get_block_number().await.unwrap(), 1);
+        let block = provider
+            .l1_provider
+            .get_block_by_hash(hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.header.number, 1);
+
+        // reorged blocks are replaced
+        chain.reorg_l1(1);
+        assert!(provider
+            .l1_provider
+            .get_block_by_hash(hash)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(provider.l1_provider.get_block_number().await.unwrap(), 1);
+
+        // outputs are only available up to the safe head
+        chain.set_safe_l2(4);
+        assert_eq!(
+            provider.op_provider.output_at_block(4).await.unwrap(),
+            chain.output_at(4)
+        );
+        assert!(provider.op_provider.output_at_block(5).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_output_sources() {
+        let chain = InMemoryChain::new();
+        chain.set_safe_l2(4);
+        let mut provider = SyncProvider::in_memory(&chain);
+        let honest_output = chain.output_at(4);
+        chain.set_output(4, B256::repeat_byte(0xff));
+
+        // a faulty op-node is trusted by default
+        assert_eq!(
+            provider.output_at_block(4).await.unwrap(),
+            B256::repeat_byte(0xff)
+        );
+        // computed outputs ignore the op-node
+        provider.output_source = OutputSource::OpGeth;
+        assert_eq!(provider.output_at_block(4).await.unwrap(), honest_output);
+        assert!(provider.output_at_block(5).await.is_err());
+        // cross-checked outputs reject disagreements
+        provider.output_source = OutputSource::CrossCheck;
+        assert!(provider.output_at_block(4).await.is_err());
+        assert_eq!(
+            provider.output_at_block(3).await.unwrap(),
+            chain.output_at(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chain_readers() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        chain.set_safe_head_at_l1(1, 8);
+        chain.set_output(4, B256::repeat_byte(0xff));
+        let provider = SyncProvider::in_memory(&chain);
+        let rpc = RpcChainReader {
+            l1_provider: provider.l1_provider.clone(),
+            op_provider: provider.op_provider.clone(),
+            l2_provider: provider.l2_provider.clone(),
+        };
+
+        // the in-memory backend reads the same data as its rpc endpoints serve
+        let readers: [&dyn ChainReader; 2] = [&chain, &rpc];
+        let (l1_head, l1_hash, _) = chain.l1_head();
+        let logs_filter = Filter::new().address(deployment.factory).from_block(0);
+        let call_data = Bytes::from(KailuaTreasury::lastResolvedCall {}.abi_encode());
+        for reader in readers {
+            let header = reader
+                .l1_header(BlockNumberOrTag::Latest)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!((header.number, header.hash), (l1_head, l1_hash));
+            let header = reader.l1_header_by_hash(l1_hash).await.unwrap().unwrap();
+            assert_eq!(header.number, l1_head);
+            assert!(reader
+                .l1_header(BlockNumberOrTag::Number(l1_head + 1))
+                .await
+                .unwrap()
+                .is_none());
+            assert_eq!(reader.l1_logs(&logs_filter).await.unwrap().len(), 1);
+            let last_resolved = reader
+                .l1_call(deployment.treasury, call_data.clone())
+                .await
+                .unwrap();
+            assert_eq!(Address::abi_decode(&last_resolved).unwrap(), ANCHOR);
+            assert!(reader
+                .l1_call(deployment.factory, call_data.clone())
+                .await
+                .is_err());
+            let sync_status = reader.sync_status().await.unwrap();
+            assert_eq!(sync_status["safe_l2"]["number"].as_u64(), Some(16));
+            assert_eq!(reader.safe_head_at_l1_block(2).await.unwrap(), 8);
+            assert!(reader.safe_head_at_l1_block(0).await.is_err());
+            assert_eq!(
+                reader.op_output_at_block(4).await.unwrap(),
+                B256::repeat_byte(0xff)
+            );
+            assert_eq!(
+                reader.l2_output_at_block(4).await.unwrap(),
+                l2_output_root(4)
+            );
+            assert!(reader.op_output_at_block(17).await.is_err());
+            assert!(reader.l2_output_at_block(17).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_anchor() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        assert_eq!(agent.deployment.treasury, TREASURY);
+        assert_eq!(agent.deployment.blocks_per_proposal(), 4);
+
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0]);
+        assert_eq!(agent.cursor.canonical_proposal_tip, 0);
+        assert_eq!(agent.cursor.last_resolved_game, 0);
+        assert_eq!(agent.proposals[&0].contract, ANCHOR);
+    }
+
+    async fn test_sync_proposals(log_sync_range: Option<u64>) {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        let faulty_proposal = ScriptedProposal {
+            output_root: B256::repeat_byte(0xff),
+            ..game_proposal(&chain, &deployment, FAULTY_GAME, 2, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), log_sync_range).await;
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0, 1, 2]);
+
+        // the honest proposal extends the canonical chain
+        assert_eq!(agent.proposals[&1].is_correct(), Some(true));
+        assert_eq!(agent.proposals[&1].canonical, Some(true));
+        assert_eq!(agent.proposals[&0].successor, Some(1));
+        assert_eq!(agent.cursor.canonical_proposal_tip, 1);
+        // the faulty proposer is eliminated
+        assert_eq!(agent.proposals[&2].is_correct(), Some(false));
+        assert_eq!(agent.proposals[&2].canonical, Some(false));
+        assert_eq!(agent.eliminations.get(&FAULTY_PROPOSER), Some(&2));
+        assert!(!agent.eliminations.contains_key(&HONEST_PROPOSER));
+        assert_eq!(agent.cursor.last_resolved_game, 0);
+
+        // resolution of the honest proposal moves the cursor forward
+        chain.resolve_proposal(&deployment, HONEST_GAME, true);
+        assert!(agent.sync(0, None).await.unwrap().is_empty());
+        assert_eq!(agent.cursor.last_resolved_game, 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_proposals_from_factory() {
+        test_sync_proposals(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_sync_proposals_from_logs() {
+        test_sync_proposals(Some(2)).await;
+    }
+
+    #[tokio::test]
+    async fn test_load_logged_proposals() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        let faulty_proposal = ScriptedProposal {
+            output_root: B256::repeat_byte(0xff),
+            ..game_proposal(&chain, &deployment, FAULTY_GAME, 2, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), Some(2)).await;
+        assert_eq!(agent.sync(0, None).await.unwrap(), vec![0, 1, 2]);
+
+        // games are recorded with the data of their creation events
+        let logged_game = agent.cursor.log_games[&2];
+        assert_eq!(logged_game.contract, FAULTY_GAME);
+        assert!(!logged_game.is_treasury);
+        assert_eq!(logged_game.root_claim, B256::repeat_byte(0xff));
+        assert!(logged_game.created_at.is_some());
+        // proposals built from logs match the ones loaded from their contracts
+        for index in [1, 2] {
+            let logged = &agent.proposals[&index];
+            let loaded = Proposal::load(&agent.provider, logged.contract)
+                .await
+                .unwrap();
+            assert_eq!(logged.treasury, loaded.treasury);
+            assert_eq!(logged.index, loaded.index);
+            assert_eq!(logged.parent, loaded.parent);
+            assert_eq!(logged.proposer, loaded.proposer);
+            assert_eq!(logged.created_at, loaded.created_at);
+            assert_eq!(logged.io_field_elements, loaded.io_field_elements);
+            assert_eq!(logged.trail_field_elements, loaded.trail_field_elements);
+            assert_eq!(logged.output_root, loaded.output_root);
+            assert_eq!(logged.output_block_number, loaded.output_block_number);
+            assert_eq!(logged.l1_head, loaded.l1_head);
+            assert_eq!(logged.signature, loaded.signature);
+        }
+
+        // resolutions are timestamped by their logs
+        chain.resolve_proposal(&deployment, HONEST_GAME, true);
+        let (_, _, resolved_at) = chain.l1_head();
+        assert!(agent.sync(0, None).await.unwrap().is_empty());
+        assert_eq!(
+            agent.cursor.log_resolutions.get(&HONEST_GAME),
+            Some(&resolved_at)
+        );
+        assert_eq!(agent.proposals[&1].resolved_at, resolved_at);
+    }
+}
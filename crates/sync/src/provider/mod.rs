@@ -13,13 +13,33 @@
 // limitations under the License.
 
 use crate::await_tel;
+use crate::multicall::{is_multicall_deployed, MULTICALL3_ADDRESS};
 use crate::provider::beacon::BlobProvider;
-use crate::provider::optimism::OpNodeProvider;
-use alloy::providers::RootProvider;
-use anyhow::Context;
+use crate::provider::blobs::{BlobArchive, BlobSources, BlobStore};
+use crate::provider::failover::FailoverTransport;
+#[cfg(any(test, feature = "test-utils"))]
+use crate::provider::memory::{InMemoryChain, InMemoryLayer};
+use crate::provider::optimism::{compute_output_at_block, OpNodeProvider};
+use crate::retry::RetryArgs;
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::{Address, Bytes, B256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::types::{Filter, Header, Log, TransactionInput, TransactionRequest};
+use alloy_rpc_types_beacon::sidecar::BlobData;
+use anyhow::{bail, Context};
+use async_trait::async_trait;
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::warn;
 
 pub mod beacon;
+pub mod blobs;
+pub mod failover;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod memory;
 pub mod optimism;
 
 #[derive(clap::Args, Debug, Clone)]
@@ -39,18 +59,104 @@ pub struct ProviderArgs {
     /// Address of the L1 Beacon API endpoint to use.
     #[clap(long, env)]
     pub beacon_rpc_url: String,
+    /// Number of L1 blocks to scan per log query for discovering proposals (disabled if unset)
+    #[clap(long, env)]
+    pub log_sync_range: Option<u64>,
+    /// Addresses of additional OP-NODE endpoints to fail over to
+    #[clap(long, env, value_delimiter = ',')]
+    pub op_node_fallback_urls: Vec<String>,
+    /// Addresses of additional OP-GETH endpoints to fail over to
+    #[clap(long, env, value_delimiter = ',')]
+    pub op_geth_fallback_urls: Vec<String>,
+    /// Addresses of additional ethereum rpc endpoints to fail over to
+    #[clap(long, env, value_delimiter = ',')]
+    pub eth_rpc_fallback_urls: Vec<String>,
+    /// Addresses of additional L1 Beacon API endpoints to fail over to
+    #[clap(long, env, value_delimiter = ',')]
+    pub beacon_rpc_fallback_urls: Vec<String>,
+    /// Number of agreeing endpoints required to accept contract state and op-node outputs
+    #[clap(long, env)]
+    pub rpc_quorum: Option<usize>,
+    /// Addresses of blob archiver API endpoints to query for blobs pruned by the beacon nodes
+    #[clap(long, env, value_delimiter = ',')]
+    pub blob_archive_urls: Vec<String>,
+    /// Directory of the local cache of verified blobs (disabled if unset)
+    #[clap(long, env)]
+    pub blob_store_dir: Option<PathBuf>,
+    /// Source of the L2 output roots used to judge proposals
+    #[clap(long, env, value_enum, default_value_t = OutputSource::OpNode)]
+    pub output_source: OutputSource,
+    #[clap(flatten)]
+    pub retry: RetryArgs,
+}
+
+/// Source of the L2 output roots used to judge proposals
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputSource {
+    /// Query output roots from the op-node
+    #[default]
+    OpNode,
+    /// Compute output roots from op-geth block headers and state proofs
+    OpGeth,
+    /// Require op-node output roots to match the ones computed from op-geth
+    CrossCheck,
+}
+
+/// L1 methods whose results require a quorum (reads of the latest state are pinned to a block)
+pub const L1_QUORUM_METHODS: &[&str] = &["eth_call"];
+/// op-node methods whose results require a quorum
+pub const OP_NODE_QUORUM_METHODS: &[&str] = &["optimism_outputAtBlock", "optimism_syncStatus"];
+
+impl ProviderArgs {
+    pub fn op_node_urls(&self) -> Vec<String> {
+        endpoint_list(&self.op_node_url, &self.op_node_fallback_urls)
+    }
+
+    pub fn op_geth_urls(&self) -> Vec<String> {
+        endpoint_list(&self.op_geth_url, &self.op_geth_fallback_urls)
+    }
+
+    pub fn eth_rpc_urls(&self) -> Vec<String> {
+        endpoint_list(&self.eth_rpc_url, &self.eth_rpc_fallback_urls)
+    }
+
+    pub fn beacon_rpc_urls(&self) -> Vec<String> {
+        endpoint_list(&self.beacon_rpc_url, &self.beacon_rpc_fallback_urls)
+    }
+
+    /// Returns a client failing over between the ethereum rpc endpoints for sending transactions.
+    pub fn eth_rpc_client(&self) -> anyhow::Result<RpcClient> {
+        Ok(RpcClient::new(
+            FailoverTransport::new("eth-rpc", self.eth_rpc_urls(), None, &[])?,
+            false,
+        ))
+    }
+}
+
+fn endpoint_list(primary: &str, fallbacks: &[String]) -> Vec<String> {
+    [primary.to_string()]
+        .into_iter()
+        .chain(fallbacks.iter().cloned())
+        .collect()
 }
 
 /// A collection of RPC providers for L1 and L2 data
+#[derive(Clone)]
 pub struct SyncProvider {
     /// DA provider for blobs
-    pub da_provider: BlobProvider,
+    pub da_provider: Arc<dyn BlobSource>,
+    /// Reader of the L1, op-node and L2 chain data needed for synchronization
+    pub chain: Arc<dyn ChainReader>,
     /// Provider for L1 chain data
     pub l1_provider: RootProvider,
     /// Provider for op-node queries
     pub op_provider: OpNodeProvider,
     /// Provider for L2 chain data
     pub l2_provider: RootProvider,
+    /// Source of the L2 output roots
+    pub output_source: OutputSource,
+    /// Address of the Multicall3 contract for batching calls, if deployed
+    pub multicall: Option<Address>,
 }
 
 impl SyncProvider {
@@ -58,19 +164,204 @@ impl SyncProvider {
         let tracer = opentelemetry::global::tracer("kailua");
         let context = opentelemetry::Context::current_with_span(tracer.start("SyncProvider::new"));
 
-        let da_provider = await_tel!(context, BlobProvider::new(args.beacon_rpc_url.clone()))
+        args.retry.install().context("RetryArgs::install")?;
+
+        let beacon_provider = await_tel!(context, BlobProvider::new(args.beacon_rpc_urls()))
             .context("BlobProvider::new")?;
-        let l1_provider = RootProvider::new_http(args.eth_rpc_url.as_str().try_into()?);
-        let op_provider = OpNodeProvider(RootProvider::new_http(
-            args.op_node_url.as_str().try_into()?,
+        let blob_archive = (!args.blob_archive_urls.is_empty())
+            .then(|| BlobArchive::new(args.blob_archive_urls.clone(), &beacon_provider));
+        let mut blob_sources: Vec<(&'static str, Arc<dyn BlobSource>)> =
+            vec![("beacon", Arc::new(beacon_provider))];
+        if let Some(blob_archive) = blob_archive {
+            blob_sources.push(("blob-archive", Arc::new(blob_archive)));
+        }
+        let da_provider = BlobSources {
+            store: args
+                .blob_store_dir
+                .clone()
+                .map(BlobStore::open)
+                .transpose()
+                .context("BlobStore::open")?,
+            sources: blob_sources,
+        };
+        let l1_provider = RootProvider::new(RpcClient::new(
+            FailoverTransport::new(
+                "eth-rpc",
+                args.eth_rpc_urls(),
+                args.rpc_quorum,
+                L1_QUORUM_METHODS,
+            )?,
+            false,
+        ));
+        let multicall = await_tel!(context, is_multicall_deployed(&l1_provider))
+            .context("is_multicall_deployed")?
+            .then_some(MULTICALL3_ADDRESS);
+        if multicall.is_none() {
+            warn!("Multicall3 not deployed at {MULTICALL3_ADDRESS}. Contract calls will not be batched.");
+        }
+        let op_provider = OpNodeProvider(RootProvider::new(RpcClient::new(
+            FailoverTransport::new(
+                "op-node",
+                args.op_node_urls(),
+                args.rpc_quorum,
+                OP_NODE_QUORUM_METHODS,
+            )?,
+            false,
+        )));
+        let l2_provider = RootProvider::new(RpcClient::new(
+            FailoverTransport::new("op-geth", args.op_geth_urls(), None, &[])?,
+            false,
         ));
-        let l2_provider = RootProvider::new_http(args.op_geth_url.as_str().try_into()?);
 
         Ok(Self {
-            da_provider,
+            da_provider: Arc::new(da_provider),
+            chain: Arc::new(RpcChainReader {
+                l1_provider: l1_provider.clone(),
+                op_provider: op_provider.clone(),
+                l2_provider: l2_provider.clone(),
+            }),
             l1_provider,
             op_provider,
             l2_provider,
+            output_source: args.output_source,
+            multicall,
         })
     }
+
+    /// Creates providers that serve all queries from the given in-memory chain.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn in_memory(chain: &InMemoryChain) -> Self {
+        let provider = |layer| -> RootProvider {
+            RootProvider::new(RpcClient::new(chain.transport(layer), true))
+        };
+        Self {
+            da_provider: Arc::new(chain.clone()),
+            chain: Arc::new(chain.clone()),
+            l1_provider: provider(InMemoryLayer::L1),
+            op_provider: OpNodeProvider(provider(InMemoryLayer::OpNode)),
+            l2_provider: provider(InMemoryLayer::L2),
+            output_source: OutputSource::OpNode,
+            multicall: Some(MULTICALL3_ADDRESS),
+        }
+    }
+
+    /// Returns the output root at the given L2 block according to the configured output source.
+    pub async fn output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256> {
+        match self.output_source {
+            OutputSource::OpNode => self.chain.op_output_at_block(output_block_number).await,
+            OutputSource::OpGeth => self.chain.l2_output_at_block(output_block_number).await,
+            OutputSource::CrossCheck => {
+                let (reported, computed) = tokio::try_join!(
+                    self.chain.op_output_at_block(output_block_number),
+                    self.chain.l2_output_at_block(output_block_number)
+                )?;
+                if reported != computed {
+                    bail!(
+                        "op-node output {reported} at block {output_block_number} does not match computed output {computed}."
+                    );
+                }
+                Ok(computed)
+            }
+        }
+    }
+}
+
+/// A source of blob data published to the L1 chain
+#[async_trait]
+pub trait BlobSource: Send + Sync {
+    /// Fetches the blob with the given versioned hash published at the given L1 timestamp
+    async fn get_blob(&self, timestamp: u64, blob_hash: B256) -> anyhow::Result<BlobData>;
+}
+
+#[async_trait]
+impl BlobSource for BlobProvider {
+    async fn get_blob(&self, timestamp: u64, blob_hash: B256) -> anyhow::Result<BlobData> {
+        BlobProvider::get_blob(self, timestamp, blob_hash).await
+    }
+}
+
+/// A reader of the L1, op-node and L2 chain data needed for synchronization
+#[async_trait]
+pub trait ChainReader: Send + Sync {
+    /// Fetches the header of the given L1 block, if it exists
+    async fn l1_header(&self, block: BlockNumberOrTag) -> anyhow::Result<Option<Header>>;
+    /// Fetches the header of the L1 block with the given hash, if it exists
+    async fn l1_header_by_hash(&self, hash: B256) -> anyhow::Result<Option<Header>>;
+    /// Fetches the L1 logs matching the given filter
+    async fn l1_logs(&self, filter: &Filter) -> anyhow::Result<Vec<Log>>;
+    /// Executes a read-only call against the latest L1 state
+    async fn l1_call(&self, to: Address, data: Bytes) -> anyhow::Result<Bytes>;
+    /// Fetches the synchronization status of the op-node
+    async fn sync_status(&self) -> anyhow::Result<Value>;
+    /// Fetches the safe L2 block derived from L1 blocks up to the given one
+    async fn safe_head_at_l1_block(&self, l1_block_number: u64) -> anyhow::Result<u64>;
+    /// Fetches the output root reported by the op-node at the given L2 block
+    async fn op_output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256>;
+    /// Computes the output root at the given L2 block from the L2 execution data
+    async fn l2_output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256>;
+}
+
+/// A [ChainReader] querying the L1, op-node and L2 RPC endpoints
+#[derive(Clone)]
+pub struct RpcChainReader {
+    /// Provider for L1 chain data
+    pub l1_provider: RootProvider,
+    /// Provider for op-node queries
+    pub op_provider: OpNodeProvider,
+    /// Provider for L2 chain data
+    pub l2_provider: RootProvider,
+}
+
+#[async_trait]
+impl ChainReader for RpcChainReader {
+    async fn l1_header(&self, block: BlockNumberOrTag) -> anyhow::Result<Option<Header>> {
+        Ok(self
+            .l1_provider
+            .get_block_by_number(block)
+            .await
+            .context("get_block_by_number")?
+            .map(|block| block.header))
+    }
+
+    async fn l1_header_by_hash(&self, hash: B256) -> anyhow::Result<Option<Header>> {
+        Ok(self
+            .l1_provider
+            .get_block_by_hash(hash)
+            .await
+            .context("get_block_by_hash")?
+            .map(|block| block.header))
+    }
+
+    async fn l1_logs(&self, filter: &Filter) -> anyhow::Result<Vec<Log>> {
+        self.l1_provider.get_logs(filter).await.context("get_logs")
+    }
+
+    async fn l1_call(&self, to: Address, data: Bytes) -> anyhow::Result<Bytes> {
+        self.l1_provider
+            .call(
+                TransactionRequest::default()
+                    .to(to)
+                    .input(TransactionInput::new(data)),
+            )
+            .await
+            .context("eth_call")
+    }
+
+    async fn sync_status(&self) -> anyhow::Result<Value> {
+        self.op_provider.sync_status().await
+    }
+
+    async fn safe_head_at_l1_block(&self, l1_block_number: u64) -> anyhow::Result<u64> {
+        self.op_provider
+            .safe_head_at_l1_block(l1_block_number)
+            .await
+    }
+
+    async fn op_output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256> {
+        self.op_provider.output_at_block(output_block_number).await
+    }
+
+    async fn l2_output_at_block(&self, output_block_number: u64) -> anyhow::Result<B256> {
+        compute_output_at_block(&self.l2_provider, output_block_number).await
+    }
 }
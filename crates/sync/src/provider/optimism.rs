@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use crate::await_tel;
-use alloy::primitives::B256;
-use alloy::providers::{Provider, ProviderBuilder, RootProvider};
-use anyhow::Context;
+use alloy::primitives::{address, keccak256, Address, B256};
+use alloy::providers::{Provider, RootProvider};
+use anyhow::{anyhow, Context};
 use kona_genesis::RollupConfig;
 use kona_registry::Registry;
 use opentelemetry::global::tracer;
@@ -25,6 +25,9 @@ use std::str::FromStr;
 use tracing::log::warn;
 use tracing::{debug, info};
 
+/// Address of the L2ToL1MessagePasser predeploy
+pub const L2_TO_L1_MESSAGE_PASSER: Address = address!("0x4200000000000000000000000000000000000016");
+
 #[derive(Clone)]
 pub struct OpNodeProvider(pub RootProvider);
 
@@ -51,6 +54,31 @@ impl OpNodeProvider {
         )?)
     }
 
+    /// Returns the number of the safe L2 block derived from L1 blocks up to the given one.
+    ///
+    /// Requires the op-node to maintain its safe head database (`--safedb.path`).
+    pub async fn safe_head_at_l1_block(&self, l1_block_number: u64) -> anyhow::Result<u64> {
+        let tracer = tracer("kailua");
+        let context = opentelemetry::Context::current_with_span(
+            tracer.start("OpNodeProvider::safe_head_at_l1_block"),
+        );
+
+        let safe_head: Value = await_tel!(
+            context,
+            tracer,
+            "optimism_safeHeadAtL1Block",
+            self.0.client().request(
+                "optimism_safeHeadAtL1Block",
+                (format!("0x{l1_block_number:x}"),),
+            )
+        )
+        .context(format!("optimism_safeHeadAtL1Block {l1_block_number}"))?;
+
+        safe_head["safeHead"]["number"].as_u64().ok_or_else(|| {
+            anyhow!("failed to parse safe head derived by l1 block {l1_block_number}")
+        })
+    }
+
     pub async fn sync_status(&self) -> anyhow::Result<Value> {
         let tracer = tracer("kailua");
         let context =
@@ -79,11 +107,67 @@ impl OpNodeProvider {
     }
 }
 
+/// Computes the version 0 output root of the given L2 block using only execution layer data.
+pub async fn compute_output_at_block(
+    l2_provider: &RootProvider,
+    output_block_number: u64,
+) -> anyhow::Result<B256> {
+    let tracer = tracer("kailua");
+    let context =
+        opentelemetry::Context::current_with_span(tracer.start("compute_output_at_block"));
+
+    let block = await_tel!(
+        context,
+        tracer,
+        "get_block_by_number",
+        l2_provider.get_block_by_number(output_block_number.into())
+    )
+    .context(format!("get_block_by_number {output_block_number}"))?
+    .ok_or_else(|| anyhow!("L2 block {output_block_number} not found"))?;
+    let message_passer = await_tel!(
+        context,
+        tracer,
+        "get_proof",
+        l2_provider
+            .get_proof(L2_TO_L1_MESSAGE_PASSER, vec![])
+            .number(output_block_number)
+    )
+    .context(format!("get_proof {output_block_number}"))?;
+
+    Ok(keccak256(
+        [
+            B256::ZERO.as_slice(),
+            block.header.state_root.as_slice(),
+            message_passer.storage_hash.as_slice(),
+            block.header.hash.as_slice(),
+        ]
+        .concat(),
+    ))
+}
+
 pub async fn fetch_rollup_config(
     op_node_address: &str,
     l2_node_address: &str,
     chain_id: Option<u64>,
     bypass_chain_registry: bool,
+) -> anyhow::Result<RollupConfig> {
+    let op_node_provider = OpNodeProvider(RootProvider::new_http(op_node_address.try_into()?));
+    let l2_node_provider = RootProvider::new_http(l2_node_address.try_into()?);
+    fetch_rollup_config_from(
+        &op_node_provider,
+        &l2_node_provider,
+        chain_id,
+        bypass_chain_registry,
+    )
+    .await
+}
+
+/// Fetches the rollup configuration through the given op-node and l2 node providers.
+pub async fn fetch_rollup_config_from(
+    op_node_provider: &OpNodeProvider,
+    l2_node_provider: &RootProvider,
+    chain_id: Option<u64>,
+    bypass_chain_registry: bool,
 ) -> anyhow::Result<RollupConfig> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("fetch_rollup_config"));
@@ -105,9 +189,6 @@ pub async fn fetch_rollup_config(
         return Ok(chain_config);
     }
 
-    let op_node_provider = OpNodeProvider(RootProvider::new_http(op_node_address.try_into()?));
-    let l2_node_provider = ProviderBuilder::new().connect_http(l2_node_address.try_into()?);
-
     let mut rollup_config: Value = op_node_provider
         .rollup_config()
         .with_context(context.clone())
@@ -12,144 +12,654 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyhow::{anyhow, bail, Context};
+use opentelemetry::global::meter;
+use opentelemetry::KeyValue;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::RwLock;
+use std::time::Duration;
+use tokio_retry::strategy::{jitter, ExponentialBackoff};
+use tracing::{error, info};
+
+/// Retry policies installed for this process by endpoint class
+static RETRY_POLICIES: RwLock<BTreeMap<EndpointClass, RetryPolicy>> = RwLock::new(BTreeMap::new());
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct RetryArgs {
+    /// Retry policy for all endpoints, as comma-separated `key=value` pairs of `attempts`,
+    /// `backoff` (ms), `max-backoff` (ms), `timeout` (s), `jitter` and `give-up`
+    /// (`error`, `stall` or `exit`)
+    #[clap(long, env, value_parser = parse_retry_policy)]
+    pub retry_policy: Option<String>,
+    /// Retry policy overrides for ethereum rpc queries
+    #[clap(long, env, value_parser = parse_retry_policy)]
+    pub eth_rpc_retry_policy: Option<String>,
+    /// Retry policy overrides for OP-NODE queries
+    #[clap(long, env, value_parser = parse_retry_policy)]
+    pub op_node_retry_policy: Option<String>,
+    /// Retry policy overrides for OP-GETH queries
+    #[clap(long, env, value_parser = parse_retry_policy)]
+    pub op_geth_retry_policy: Option<String>,
+    /// Retry policy overrides for L1 Beacon API queries
+    #[clap(long, env, value_parser = parse_retry_policy)]
+    pub beacon_rpc_retry_policy: Option<String>,
+}
+
+fn parse_retry_policy(s: &str) -> Result<String, String> {
+    RetryPolicy::default()
+        .apply(s)
+        .map(|_| s.to_string())
+        .map_err(|err| format!("{err:?}"))
+}
+
+impl RetryArgs {
+    /// Installs the configured retry policies for all subsequent retried operations.
+    pub fn install(&self) -> anyhow::Result<()> {
+        let default = RetryPolicy::default().apply(self.retry_policy.as_deref().unwrap_or(""))?;
+        let mut policies = BTreeMap::new();
+        for (class, spec) in [
+            (EndpointClass::Default, None),
+            (EndpointClass::EthRpc, self.eth_rpc_retry_policy.as_deref()),
+            (EndpointClass::OpNode, self.op_node_retry_policy.as_deref()),
+            (EndpointClass::OpGeth, self.op_geth_retry_policy.as_deref()),
+            (
+                EndpointClass::BeaconRpc,
+                self.beacon_rpc_retry_policy.as_deref(),
+            ),
+        ] {
+            let policy = default.apply(spec.unwrap_or(""))?;
+            info!("Retry policy for {} endpoints: {policy:?}", class.name());
+            policies.insert(class, RetryPolicy { class, ..policy });
+        }
+        *RETRY_POLICIES.write().unwrap() = policies;
+        Ok(())
+    }
+}
+
+/// The class of endpoint queried by a retried operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EndpointClass {
+    Default,
+    EthRpc,
+    OpNode,
+    OpGeth,
+    BeaconRpc,
+}
+
+impl EndpointClass {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EndpointClass::Default => "default",
+            EndpointClass::EthRpc => "eth-rpc",
+            EndpointClass::OpNode => "op-node",
+            EndpointClass::OpGeth => "op-geth",
+            EndpointClass::BeaconRpc => "beacon-rpc",
+        }
+    }
+}
+
+/// What to do once a retried operation exhausts its attempts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GiveUp {
+    /// Fail the operation with its last error
+    #[default]
+    Error,
+    /// Keep retrying the operation at the maximum backoff
+    Stall,
+    /// Terminate the process
+    Exit,
+}
+
+/// Exit code of processes terminated by an exhausted retry policy
+pub const GIVE_UP_EXIT_CODE: i32 = 1;
+
+/// A policy for retrying failed operations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Class of the queried endpoint for reporting
+    pub class: EndpointClass,
+    /// Maximum number of attempts (unbounded if unset)
+    pub max_attempts: Option<u64>,
+    /// Delay before the first retry, grown exponentially for further retries
+    pub base_delay: Duration,
+    /// Maximum delay between two attempts
+    pub max_delay: Duration,
+    /// Maximum duration of each attempt of timed operations
+    pub timeout: Duration,
+    /// Whether to randomize delays between attempts
+    pub jitter: bool,
+    /// Behavior once all attempts are exhausted
+    pub give_up: GiveUp,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            class: EndpointClass::Default,
+            max_attempts: None,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_millis(1000),
+            timeout: Duration::from_secs(5),
+            jitter: false,
+            give_up: GiveUp::Error,
+        }
+    }
+}
+
+/// The reason a timed attempt failed
+#[derive(Debug)]
+pub enum AttemptError<E> {
+    Failed(E),
+    TimedOut(Duration),
+}
+
+impl RetryPolicy {
+    /// Returns the installed policy for the given endpoint class.
+    pub fn get(class: EndpointClass) -> Self {
+        let policies = RETRY_POLICIES.read().unwrap();
+        policies
+            .get(&class)
+            .or_else(|| policies.get(&EndpointClass::Default))
+            .copied()
+            .map(|policy| Self { class, ..policy })
+            .unwrap_or(Self {
+                class,
+                ..Default::default()
+            })
+    }
+
+    /// Returns a copy of this policy with the given settings overridden by the comma-separated
+    /// `key=value` pairs of the spec.
+    pub fn apply(mut self, spec: &str) -> anyhow::Result<Self> {
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((key, value)) = entry.split_once('=') else {
+                bail!("Expected key=value in retry policy entry {entry}.");
+            };
+            let value = value.trim();
+            match key.trim() {
+                "attempts" if value == "unbounded" => self.max_attempts = None,
+                "attempts" => {
+                    let attempts = value.parse::<u64>().context(entry.to_string())?;
+                    if attempts == 0 {
+                        bail!("Retry policy requires at least one attempt.");
+                    }
+                    self.max_attempts = Some(attempts);
+                }
+                "backoff" => {
+                    self.base_delay =
+                        Duration::from_millis(value.parse().context(entry.to_string())?)
+                }
+                "max-backoff" => {
+                    self.max_delay =
+                        Duration::from_millis(value.parse().context(entry.to_string())?)
+                }
+                "timeout" => {
+                    self.timeout = Duration::from_secs(value.parse().context(entry.to_string())?)
+                }
+                "jitter" => self.jitter = value.parse().context(entry.to_string())?,
+                "give-up" => {
+                    self.give_up = match value {
+                        "error" => GiveUp::Error,
+                        "stall" => GiveUp::Stall,
+                        "exit" => GiveUp::Exit,
+                        _ => bail!("Unknown give-up behavior {value}."),
+                    }
+                }
+                _ => bail!("Unknown retry policy setting {key}."),
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns a copy of this policy with the given backoff delays in milliseconds.
+    pub fn with_backoff(self, base_delay: u64, max_delay: u64) -> Self {
+        Self {
+            base_delay: Duration::from_millis(base_delay),
+            max_delay: Duration::from_millis(max_delay),
+            ..self
+        }
+    }
+
+    /// Returns a copy of this policy with the given attempt timeout in seconds.
+    pub fn with_timeout(self, timeout: u64) -> Self {
+        Self {
+            timeout: Duration::from_secs(timeout),
+            ..self
+        }
+    }
+
+    fn delays(&self) -> impl Iterator<Item = Duration> {
+        let jitter_enabled = self.jitter;
+        ExponentialBackoff::from_millis(self.base_delay.as_millis() as u64)
+            .max_delay(self.max_delay)
+            .map(move |delay| if jitter_enabled { jitter(delay) } else { delay })
+    }
+
+    /// Runs the operation until it succeeds or exhausts its attempts, after which it gives up as
+    /// configured by returning the last error, retrying indefinitely or exiting the process.
+    pub async fn retry<T, E, F, Fut>(&self, mut operation: F) -> Result<T, E>
+    where
+        E: Debug,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let attributes = [KeyValue::new("endpoint", self.class.name())];
+        let mut delays = self.delays();
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let err = match operation().await {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            meter("kailua")
+                .u64_counter("retry.failures")
+                .build()
+                .add(1, &attributes);
+            if self.max_attempts.is_some_and(|max| attempt >= max) {
+                meter("kailua")
+                    .u64_counter("retry.exhausted")
+                    .build()
+                    .add(1, &attributes);
+                match self.give_up {
+                    GiveUp::Error => {
+                        error!(
+                            "(Exhausted {attempt} attempts on {} endpoint) {err:?}",
+                            self.class.name()
+                        );
+                        return Err(err);
+                    }
+                    GiveUp::Stall => {
+                        error!(
+                            "(Stalling after {attempt} attempts on {} endpoint) {err:?}",
+                            self.class.name()
+                        );
+                        tokio::time::sleep(self.max_delay).await;
+                        continue;
+                    }
+                    GiveUp::Exit => {
+                        error!(
+                            "(Exiting after {attempt} attempts on {} endpoint) {err:?}",
+                            self.class.name()
+                        );
+                        std::process::exit(GIVE_UP_EXIT_CODE);
+                    }
+                }
+            }
+            error!("(Retrying) {err:?}");
+            tokio::time::sleep(delays.next().unwrap_or(self.max_delay)).await;
+        }
+    }
+
+    /// Runs the operation until it succeeds, giving up with an error describing the last failure
+    /// once it exhausts its attempts.
+    pub async fn retry_or_give_up<T, E, F, Fut>(&self, operation: F) -> anyhow::Result<T>
+    where
+        E: Debug,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        self.retry(operation).await.map_err(|err| self.give_up(err))
+    }
+
+    /// Runs the operation until it completes within the attempt timeout.
+    pub async fn retry_timeout<T, F, Fut>(&self, mut operation: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let timeout = self.timeout;
+        self.retry_or_give_up(|| tokio::time::timeout(timeout, operation()))
+            .await
+    }
+
+    /// Runs the operation until it succeeds within the attempt timeout.
+    pub async fn retry_res_timeout<T, E, F, Fut>(&self, mut operation: F) -> anyhow::Result<T>
+    where
+        E: Debug,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let timeout = self.timeout;
+        self.retry_or_give_up(|| {
+            let attempt = tokio::time::timeout(timeout, operation());
+            async move {
+                match attempt.await {
+                    Ok(result) => result.map_err(AttemptError::Failed),
+                    Err(_) => Err(AttemptError::TimedOut(timeout)),
+                }
+            }
+        })
+        .await
+    }
+
+    fn give_up<E: Debug>(&self, err: E) -> anyhow::Error {
+        anyhow!(
+            "Giving up on unresponsive {} endpoint after {} attempts: {err:?}",
+            self.class.name(),
+            self.max_attempts.unwrap_or_default()
+        )
+    }
+}
+
 #[macro_export]
 macro_rules! retry {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c).retry(|| async { $e })
+    };
+    ($c:ident => $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .retry(|| async { $e })
+    };
     ($e:expr) => {
-        $crate::retry!(250, 1000, $e)
+        $crate::retry!(Default => $e)
     };
     ($m:literal, $e:expr) => {
-        $crate::retry!(250, $m, $e)
+        $crate::retry!(Default => 250, $m, $e)
     };
     ($b:literal, $m:literal, $e:expr) => {
-        tokio_retry::Retry::spawn(
-            tokio_retry::strategy::ExponentialBackoff::from_millis($b)
-                .max_delay(std::time::Duration::from_millis($m)),
-            || async {
-                let res = $e;
-                if let Err(err) = &res {
-                    tracing::error!("(Retrying) {err:?}");
-                }
-                res
-            },
-        )
+        $crate::retry!(Default => $b, $m, $e)
     };
 }
 
 #[macro_export]
 macro_rules! retry_res {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .retry_or_give_up(|| async { $e })
+    };
+    ($c:ident => $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .retry_or_give_up(|| async { $e })
+    };
     ($e:expr) => {
-        $crate::retry_res!(250, 1000, $e)
+        $crate::retry_res!(Default => $e)
     };
     ($m:literal, $e:expr) => {
-        $crate::retry_res!(250, $m, $e)
+        $crate::retry_res!(Default => 250, $m, $e)
     };
     ($b:literal, $m:literal, $e:expr) => {
-        async { $crate::retry!($b, $m, $e).await.unwrap() }
+        $crate::retry_res!(Default => $b, $m, $e)
+    };
+}
+
+#[macro_export]
+macro_rules! retry_attempt_ctx {
+    ($e:expr) => {
+        opentelemetry::trace::FutureExt::with_context(
+            $e,
+            opentelemetry::Context::current_with_span(
+                opentelemetry::global::tracer("kailua")
+                    .start_with_context("retry_attempt", &opentelemetry::Context::current()),
+            ),
+        )
     };
 }
 
 #[macro_export]
 macro_rules! retry_ctx {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .retry(|| $crate::retry_attempt_ctx!($e))
+    };
+    ($c:ident => $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .retry(|| $crate::retry_attempt_ctx!($e))
+    };
     ($e:expr) => {
-        $crate::retry_ctx!(250, 1000, $e)
+        $crate::retry_ctx!(Default => $e)
     };
     ($m:literal, $e:expr) => {
-        $crate::retry_ctx!(250, $m, $e)
+        $crate::retry_ctx!(Default => 250, $m, $e)
     };
     ($b:literal, $m:literal, $e:expr) => {
-        $crate::retry!(
-            $b,
-            $m,
-            opentelemetry::trace::FutureExt::with_context(
-                $e,
-                opentelemetry::Context::current_with_span(
-                    opentelemetry::global::tracer("kailua")
-                        .start_with_context("retry_attempt", &opentelemetry::Context::current()),
-                )
-            )
-            .await
-        )
+        $crate::retry_ctx!(Default => $b, $m, $e)
     };
 }
 
 #[macro_export]
 macro_rules! retry_res_ctx {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .retry_or_give_up(|| $crate::retry_attempt_ctx!($e))
+    };
+    ($c:ident => $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .retry_or_give_up(|| $crate::retry_attempt_ctx!($e))
+    };
     ($e:expr) => {
-        $crate::retry_res_ctx!(250, 1000, $e)
+        $crate::retry_res_ctx!(Default => $e)
     };
     ($m:literal, $e:expr) => {
-        $crate::retry_res_ctx!(250, $m, $e)
+        $crate::retry_res_ctx!(Default => 250, $m, $e)
     };
     ($b:literal, $m:literal, $e:expr) => {
-        async { $crate::retry_ctx!($b, $m, $e).await.unwrap() }
+        $crate::retry_res_ctx!(Default => $b, $m, $e)
     };
 }
 
 #[macro_export]
 macro_rules! retry_timeout {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .retry_timeout(|| async { $e })
+    };
+    ($c:ident => $t:expr, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_timeout($t)
+            .retry_timeout(|| async { $e })
+    };
+    ($c:ident => $t:expr, $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .with_timeout($t)
+            .retry_timeout(|| async { $e })
+    };
     ($e:expr) => {
-        $crate::retry_timeout!(5, 250, 1000, $e)
+        $crate::retry_timeout!(Default => $e)
     };
     ($t:expr, $e:expr) => {
-        $crate::retry_timeout!($t, 250, 1000, $e)
+        $crate::retry_timeout!(Default => $t, $e)
     };
     ($t:expr, $m:literal, $e:expr) => {
-        $crate::retry_timeout!($t, 250, $m, $e)
+        $crate::retry_timeout!(Default => $t, 250, $m, $e)
     };
     ($t:expr, $b:literal, $m:literal, $e:expr) => {
-        $crate::retry_res!(
-            $b,
-            $m,
-            tokio::time::timeout(core::time::Duration::from_secs($t), async { $e }).await
-        )
+        $crate::retry_timeout!(Default => $t, $b, $m, $e)
     };
 }
 
 #[macro_export]
 macro_rules! retry_res_timeout {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .retry_res_timeout(|| async { $e })
+    };
+    ($c:ident => $t:expr, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_timeout($t)
+            .retry_res_timeout(|| async { $e })
+    };
+    ($c:ident => $t:expr, $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .with_timeout($t)
+            .retry_res_timeout(|| async { $e })
+    };
     ($e:expr) => {
-        $crate::retry_res_timeout!(5, 250, 1000, $e)
+        $crate::retry_res_timeout!(Default => $e)
     };
     ($t:expr, $e:expr) => {
-        $crate::retry_res_timeout!($t, 250, 1000, $e)
+        $crate::retry_res_timeout!(Default => $t, $e)
     };
     ($t:expr, $m:literal, $e:expr) => {
-        $crate::retry_res_timeout!($t, 250, $m, $e)
+        $crate::retry_res_timeout!(Default => $t, 250, $m, $e)
     };
     ($t:expr, $b:literal, $m:literal, $e:expr) => {
-        async {
-            $crate::retry_res!(
-                $crate::retry_res!(
-                    $b,
-                    $m,
-                    tokio::time::timeout(core::time::Duration::from_secs($t), async { $e }).await
-                )
-                .await
-            )
-            .await
-        }
+        $crate::retry_res_timeout!(Default => $t, $b, $m, $e)
     };
 }
 
 #[macro_export]
 macro_rules! retry_res_ctx_timeout {
+    ($c:ident => $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .retry_res_timeout(|| $crate::retry_attempt_ctx!(async { $e }))
+    };
+    ($c:ident => $t:expr, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_timeout($t)
+            .retry_res_timeout(|| $crate::retry_attempt_ctx!(async { $e }))
+    };
+    ($c:ident => $t:expr, $b:literal, $m:literal, $e:expr) => {
+        $crate::retry::RetryPolicy::get($crate::retry::EndpointClass::$c)
+            .with_backoff($b, $m)
+            .with_timeout($t)
+            .retry_res_timeout(|| $crate::retry_attempt_ctx!(async { $e }))
+    };
     ($e:expr) => {
-        $crate::retry_res_ctx_timeout!(5, 250, 1000, $e)
+        $crate::retry_res_ctx_timeout!(Default => $e)
     };
     ($t:expr, $e:expr) => {
-        $crate::retry_res_ctx_timeout!($t, 250, 1000, $e)
+        $crate::retry_res_ctx_timeout!(Default => $t, $e)
     };
     ($t:expr, $m:literal, $e:expr) => {
-        $crate::retry_res_ctx_timeout!($t, 250, $m, $e)
+        $crate::retry_res_ctx_timeout!(Default => $t, 250, $m, $e)
     };
     ($t:expr, $b:literal, $m:literal, $e:expr) => {
-        async {
-            $crate::retry_res_ctx!($crate::retry_res_ctx!(
-                $b,
-                $m,
-                tokio::time::timeout(core::time::Duration::from_secs($t), async { $e })
-            ))
+        $crate::retry_res_ctx_timeout!(Default => $t, $b, $m, $e)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_spec() {
+        let policy = RetryPolicy::default()
+            .apply("attempts=3, backoff=100,max-backoff=2000,timeout=10,jitter=true")
+            .unwrap();
+        assert_eq!(policy.max_attempts, Some(3));
+        assert_eq!(policy.base_delay, Duration::from_millis(100));
+        assert_eq!(policy.max_delay, Duration::from_millis(2000));
+        assert_eq!(policy.timeout, Duration::from_secs(10));
+        assert!(policy.jitter);
+        // unspecified settings are kept
+        assert_eq!(
+            policy.apply("attempts=unbounded").unwrap(),
+            RetryPolicy {
+                max_attempts: None,
+                ..policy
+            }
+        );
+        assert!(RetryPolicy::default().apply("attempts=0").is_err());
+        assert!(RetryPolicy::default().apply("retries=1").is_err());
+        assert!(RetryPolicy::default().apply("give-up=never").is_err());
+        for (spec, give_up) in [
+            ("give-up=error", GiveUp::Error),
+            ("give-up=stall", GiveUp::Stall),
+            ("give-up=exit", GiveUp::Exit),
+        ] {
+            assert_eq!(RetryPolicy::default().apply(spec).unwrap().give_up, give_up);
+        }
+        assert_eq!(RetryPolicy::default().give_up, GiveUp::Error);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhaustion() {
+        let policy = RetryPolicy::default()
+            .apply("attempts=3,backoff=1,max-backoff=1,timeout=1")
+            .unwrap();
+        let mut attempts = 0;
+        let result: Result<(), u64> = policy
+            .retry(|| {
+                attempts += 1;
+                let attempt = attempts;
+                async move { Err(attempt) }
+            })
+            .await;
+        assert_eq!(result, Err(3));
+
+        // timed out attempts are retried
+        let mut attempts = 0;
+        let value = policy
+            .retry_res_timeout(|| {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    if attempt < 3 {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    Ok::<_, ()>(attempt)
+                }
+            })
             .await
+            .unwrap();
+        assert_eq!(value, 3);
+
+        // exhausted attempts are reported as errors
+        let mut attempts = 0;
+        let result = policy
+            .retry_res_timeout(|| {
+                attempts += 1;
+                async { Err::<(), _>("unavailable") }
+            })
+            .await;
+        assert_eq!(attempts, 3);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("after 3 attempts: Failed(\"unavailable\")"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_give_up_stall() {
+        let policy = RetryPolicy::default()
+            .apply("attempts=2,backoff=1,max-backoff=1,give-up=stall")
+            .unwrap();
+        // stalled operations are retried beyond their attempts until they succeed
+        let mut attempts = 0;
+        let value = policy
+            .retry_or_give_up(|| {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    match attempt {
+                        5 => Ok(attempt),
+                        _ => Err("unavailable"),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 5);
+    }
+
+    #[tokio::test]
+    async fn test_retry_give_up_exit() {
+        const CHILD: &str = "KAILUA_TEST_RETRY_GIVE_UP_EXIT";
+        if std::env::var_os(CHILD).is_some() {
+            let policy = RetryPolicy::default()
+                .apply("attempts=2,backoff=1,max-backoff=1,give-up=exit")
+                .unwrap();
+            let _ = policy
+                .retry_or_give_up(|| async { Err::<(), _>("unavailable") })
+                .await;
+            unreachable!("Exhausted retry policy did not exit");
         }
-    };
+        // exhausting the attempts terminates the process
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "retry::tests::test_retry_give_up_exit"])
+            .env(CHILD, "1")
+            .status()
+            .unwrap();
+        assert_eq!(status.code(), Some(GIVE_UP_EXIT_CODE));
+    }
 }
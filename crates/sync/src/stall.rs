@@ -26,9 +26,9 @@ use std::marker::PhantomData;
 
 #[async_trait]
 pub trait Stall<R> {
-    async fn stall(&self, span: &'static str) -> R;
+    async fn stall(&self, span: &'static str) -> anyhow::Result<R>;
 
-    async fn stall_with_context(&self, context: Context, span: &'static str) -> R {
+    async fn stall_with_context(&self, context: Context, span: &'static str) -> anyhow::Result<R> {
         self.stall(span).with_context(context).await
     }
 }
@@ -40,7 +40,7 @@ where
     EthCall<'coder, PhantomData<C>, N>: IntoFuture,
     C::Return: Send,
 {
-    async fn stall(&self, span: &'static str) -> C::Return {
+    async fn stall(&self, span: &'static str) -> anyhow::Result<C::Return> {
         let tracer = tracer("kailua");
         let context = Context::current_with_span(tracer.start(span));
 
@@ -48,7 +48,7 @@ where
             context,
             tracer,
             "call_raw",
-            retry_res_ctx_timeout!(self
+            retry_res_ctx_timeout!(EthRpc => self
                 .call_raw()
                 .await
                 .and_then(|response| self.decode_output(response)))
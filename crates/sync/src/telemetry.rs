@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use opentelemetry::global::{meter, set_meter_provider, set_tracer_provider};
-use opentelemetry::metrics::{Gauge, Meter};
+use opentelemetry::metrics::{Counter, Gauge, Meter};
 use opentelemetry::KeyValue;
 use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
 use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider, Temporality};
@@ -101,6 +101,8 @@ pub struct SyncTelemetry {
     pub sync_canonical: Gauge<u64>,
     /// Gauge for reporting the next proposal index to query
     pub sync_next: Gauge<u64>,
+    /// Counter for reporting detected L1 reorgs
+    pub sync_reorgs: Counter<u64>,
 }
 
 impl Default for SyncTelemetry {
@@ -114,11 +116,13 @@ impl SyncTelemetry {
         let meter = meter("kailua");
         let sync_canonical = meter.u64_gauge("sync.canonical").build();
         let sync_next = meter.u64_gauge("sync.next").build();
+        let sync_reorgs = meter.u64_counter("sync.reorgs").build();
 
         Self {
             meter,
             sync_canonical,
             sync_next,
+            sync_reorgs,
         }
     }
 }
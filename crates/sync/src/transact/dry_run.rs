@@ -0,0 +1,259 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::contract::{CallBuilder, CallDecoder};
+use alloy::eips::eip4844::DATA_GAS_PER_BLOB;
+use alloy::eips::BlockId;
+use alloy::network::{Network, TransactionBuilder, TransactionBuilder4844};
+use alloy::primitives::{Address, Bytes, FixedBytes, TxKind, U256};
+use alloy::providers::Provider;
+use anyhow::Context;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, RwLock};
+use tracing::{info, warn};
+
+static DRY_RUN: LazyLock<DryRun> = LazyLock::new(DryRun::default);
+
+/// Returns the dry-run configuration shared by all transaction senders in this process.
+pub fn dry_run() -> &'static DryRun {
+    &DRY_RUN
+}
+
+/// The outcome of simulating a transaction instead of broadcasting it
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTransaction {
+    /// Name of the contract call (e.g. `KailuaTreasury::propose`)
+    pub call: String,
+    pub selector: Option<FixedBytes<4>>,
+    pub from: Option<Address>,
+    /// Recipient of the call, or `None` for contract deployments
+    pub to: Option<Address>,
+    pub value: U256,
+    pub calldata: Bytes,
+    pub blob_count: usize,
+    /// Latest block number at the time of the simulation
+    pub block_number: u64,
+    /// Output of `eth_call` if the call succeeded
+    pub output: Option<Bytes>,
+    /// Error of `eth_call` or `eth_estimateGas` if the call failed
+    pub error: Option<String>,
+    pub gas_estimate: Option<u64>,
+    pub max_fee_per_gas: u128,
+    pub blob_base_fee: Option<u128>,
+    /// Upper bound on the fees in wei under the current gas prices
+    pub estimated_cost: Option<u128>,
+}
+
+impl SimulatedTransaction {
+    pub fn estimate_cost(&self) -> Option<u128> {
+        let exec_cost = self.gas_estimate? as u128 * self.max_fee_per_gas;
+        let blob_cost = self.blob_count as u128
+            * DATA_GAS_PER_BLOB as u128
+            * self.blob_base_fee.unwrap_or_default();
+        Some(exec_cost + blob_cost)
+    }
+}
+
+/// Error returned instead of a receipt for transactions that were only simulated
+#[derive(Clone, Debug)]
+pub struct NotBroadcast(pub SimulatedTransaction);
+
+impl Display for NotBroadcast {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Dry run: {} was simulated but not broadcast.",
+            self.0.call
+        )
+    }
+}
+
+impl std::error::Error for NotBroadcast {}
+
+/// Returns whether the error stems from a transaction that was only simulated.
+pub fn is_dry_run(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<NotBroadcast>().is_some()
+}
+
+/// Replaces transaction publication with simulation when enabled
+#[derive(Debug, Default)]
+pub struct DryRun {
+    enabled: AtomicBool,
+    report: RwLock<Option<PathBuf>>,
+    simulations: Mutex<Vec<SimulatedTransaction>>,
+}
+
+impl DryRun {
+    pub fn configure(&self, enabled: bool, report: Option<PathBuf>) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        *self.report.write().unwrap() = report;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn simulations(&self) -> Vec<SimulatedTransaction> {
+        self.simulations.lock().unwrap().clone()
+    }
+
+    /// Simulates the call at the latest block using `eth_call` and `eth_estimateGas`.
+    pub async fn simulate<P: Provider<N>, D: CallDecoder, N: Network>(
+        &self,
+        call: &CallBuilder<P, D, N>,
+        name: &str,
+    ) -> anyhow::Result<SimulatedTransaction>
+    where
+        CallBuilder<P, D, N>: Clone,
+        N::TransactionRequest: TransactionBuilder4844,
+    {
+        let call = call.clone().block(BlockId::latest());
+        let block_number = call
+            .provider
+            .get_block_number()
+            .await
+            .context("get_block_number")?;
+        let request = call.clone().into_transaction_request();
+        let mut simulation = SimulatedTransaction {
+            call: name.to_string(),
+            selector: call.calldata().get(..4).map(FixedBytes::<4>::from_slice),
+            from: request.from(),
+            to: match request.kind() {
+                Some(TxKind::Call(to)) => Some(to),
+                _ => None,
+            },
+            value: request.value().unwrap_or_default(),
+            calldata: call.calldata().clone(),
+            blob_count: request
+                .blob_sidecar()
+                .map_or(0, |sidecar| sidecar.blobs.len()),
+            block_number,
+            ..Default::default()
+        };
+        match call.call_raw().await {
+            Ok(output) => simulation.output = Some(output),
+            Err(err) => simulation.error = Some(format!("eth_call: {err}")),
+        }
+        match call.estimate_gas().await {
+            Ok(gas) => simulation.gas_estimate = Some(gas),
+            Err(err) => {
+                simulation
+                    .error
+                    .get_or_insert(format!("eth_estimateGas: {err}"));
+            }
+        }
+        simulation.max_fee_per_gas = call
+            .provider
+            .estimate_eip1559_fees()
+            .await
+            .context("estimate_eip1559_fees")?
+            .max_fee_per_gas;
+        if simulation.blob_count > 0 {
+            simulation.blob_base_fee = Some(
+                call.provider
+                    .get_blob_base_fee()
+                    .await
+                    .context("get_blob_base_fee")?,
+            );
+        }
+        simulation.estimated_cost = simulation.estimate_cost();
+        Ok(simulation)
+    }
+
+    /// Logs the simulation and appends it to the report file unless it repeats an earlier one.
+    pub fn record(&self, simulation: SimulatedTransaction) -> anyhow::Result<()> {
+        let mut simulations = self.simulations.lock().unwrap();
+        // Agents retry the same transaction every iteration while it is not published
+        if simulations.iter().any(|earlier| {
+            earlier.call == simulation.call
+                && earlier.to == simulation.to
+                && earlier.value == simulation.value
+                && earlier.calldata == simulation.calldata
+        }) {
+            return Ok(());
+        }
+        match &simulation.error {
+            None => info!(
+                "Dry run: {} at block {} to {:?} with value {}, {} blobs and {} bytes of calldata would use {:?} gas costing up to {:?} wei.",
+                simulation.call,
+                simulation.block_number,
+                simulation.to,
+                simulation.value,
+                simulation.blob_count,
+                simulation.calldata.len(),
+                simulation.gas_estimate,
+                simulation.estimated_cost
+            ),
+            Some(err) => warn!(
+                "Dry run: {} at block {} would fail: {err}",
+                simulation.call, simulation.block_number
+            ),
+        }
+        simulations.push(simulation);
+        if let Some(report) = self.report.read().unwrap().as_ref() {
+            std::fs::write(report, serde_json::to_vec_pretty(&*simulations)?)
+                .with_context(|| format!("Failed to write {}", report.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dry_run_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("report.json");
+        let dry_run = DryRun::default();
+        dry_run.configure(true, Some(report.clone()));
+        assert!(dry_run.is_enabled());
+
+        let simulation = SimulatedTransaction {
+            call: "KailuaTreasury::propose".to_string(),
+            blob_count: 2,
+            gas_estimate: Some(100_000),
+            max_fee_per_gas: 10,
+            blob_base_fee: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            simulation.estimate_cost(),
+            Some(1_000_000 + 2 * DATA_GAS_PER_BLOB as u128 * 3)
+        );
+        dry_run.record(simulation.clone()).unwrap();
+        // Repeated simulations are only reported once
+        dry_run.record(simulation.clone()).unwrap();
+        dry_run
+            .record(SimulatedTransaction {
+                value: U256::from(1),
+                ..simulation
+            })
+            .unwrap();
+
+        let written: Vec<serde_json::Value> =
+            serde_json::from_slice(&std::fs::read(&report).unwrap()).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(written[0]["call"], "KailuaTreasury::propose");
+        assert_eq!(written[0]["blobCount"], 2);
+
+        let err = anyhow::Error::new(NotBroadcast(dry_run.simulations()[0].clone()));
+        assert!(is_dry_run(&err.context("transact")));
+    }
+}
@@ -12,17 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::transact::nonce::TrackedNonceManager;
+use crate::transact::policy::fee_policy;
 use alloy::eips::eip4844::BLOB_TX_MIN_BLOB_GASPRICE;
-use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::eips::BlockNumberOrTag;
 use alloy::network::{Network, TransactionBuilder, TransactionBuilder4844};
-use alloy::primitives::Address;
 use alloy::providers::fillers::{
     BlobGasFiller, ChainIdFiller, FillerControlFlow, GasFillable, GasFiller, JoinFill, NonceFiller,
-    NonceManager, TxFiller,
+    TxFiller,
 };
 use alloy::providers::{Provider, SendableTx};
 use alloy::transports::{RpcError, TransportResult};
-use async_trait::async_trait;
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct PremiumExecGasFiller {
@@ -69,15 +69,18 @@ impl<N: Network> TxFiller<N> for PremiumExecGasFiller {
         tx: SendableTx<N>,
     ) -> TransportResult<SendableTx<N>> {
         let mut tx = self.inner.fill(fillable, tx).await?;
+        let limits = fee_policy().limits();
         if let Some(builder) = tx.as_mut_builder() {
             if let Some(gas_price) = builder.gas_price() {
-                builder.set_gas_price(self.make_premium(gas_price));
+                builder.set_gas_price(limits.cap_fee_per_gas(self.make_premium(gas_price)));
             }
             if let Some(base_fee) = builder.max_fee_per_gas() {
-                builder.set_max_fee_per_gas(self.make_premium(base_fee));
+                builder.set_max_fee_per_gas(limits.cap_fee_per_gas(self.make_premium(base_fee)));
             }
             if let Some(priority_fee) = builder.max_priority_fee_per_gas() {
-                builder.set_max_priority_fee_per_gas(self.make_premium(priority_fee));
+                // The priority fee may not exceed the capped max fee
+                let max_fee = builder.max_fee_per_gas().unwrap_or(u128::MAX);
+                builder.set_max_priority_fee_per_gas(self.make_premium(priority_fee).min(max_fee));
             }
         }
         Ok(tx)
@@ -144,28 +147,14 @@ where
         fillable: Self::Fillable,
         tx: SendableTx<N>,
     ) -> TransportResult<SendableTx<N>> {
-        self.inner.fill(self.make_premium(fillable), tx).await
-    }
-}
-
-#[derive(Clone, Debug, Default)]
-pub struct LatestNonceManager;
-
-#[async_trait]
-impl NonceManager for LatestNonceManager {
-    async fn get_next_nonce<P, N>(&self, provider: &P, address: Address) -> TransportResult<u64>
-    where
-        P: Provider<N>,
-        N: Network,
-    {
-        provider
-            .get_transaction_count(address)
-            .block_id(BlockId::Number(BlockNumberOrTag::Latest))
-            .await
+        let fee = fee_policy()
+            .limits()
+            .cap_fee_per_blob_gas(self.make_premium(fillable));
+        self.inner.fill(fee, tx).await
     }
 }
 
 pub type PremiumFiller = JoinFill<
     PremiumExecGasFiller,
-    JoinFill<PremiumBlobGasFiller, JoinFill<NonceFiller<LatestNonceManager>, ChainIdFiller>>,
+    JoinFill<PremiumBlobGasFiller, JoinFill<NonceFiller<TrackedNonceManager>, ChainIdFiller>>,
 >;
@@ -0,0 +1,336 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::transact::policy::Urgency;
+use alloy::network::ReceiptResponse;
+use alloy::primitives::{keccak256, Address, Bytes, TxHash, B256, U256};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, warn};
+
+/// Name of the journal file inside the data directory
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+static JOURNAL: LazyLock<Journal> = LazyLock::new(Journal::default);
+
+/// Returns the transaction journal shared by all transaction senders in this process.
+pub fn journal() -> &'static Journal {
+    &JOURNAL
+}
+
+/// The final state of a journaled transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// Included and executed successfully
+    Confirmed,
+    /// Included but reverted
+    Reverted,
+    /// Not known to be included (e.g. rejected, or timed out)
+    Failed,
+}
+
+/// A record of a transaction submitted by this node
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    /// Unix timestamp in seconds of when the outcome was known
+    pub timestamp: u64,
+    /// Name of the contract call (e.g. `KailuaTreasury::propose`)
+    pub intent: String,
+    pub urgency: Urgency,
+    /// Index of the proposal the transaction was sent to, if any
+    pub proposal_index: Option<u64>,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub calldata_hash: B256,
+    pub outcome: Outcome,
+    pub error: Option<String>,
+    pub txn_hash: Option<TxHash>,
+    pub block_number: Option<u64>,
+    pub gas_used: Option<u64>,
+    pub effective_gas_price: Option<u128>,
+    pub blob_gas_used: Option<u64>,
+    pub blob_gas_price: Option<u128>,
+}
+
+impl JournalEntry {
+    /// Returns the fees paid in wei.
+    pub fn fee(&self) -> u128 {
+        let exec_fee = self.gas_used.unwrap_or_default() as u128
+            * self.effective_gas_price.unwrap_or_default();
+        let blob_fee = self.blob_gas_used.unwrap_or_default() as u128
+            * self.blob_gas_price.unwrap_or_default();
+        exec_fee + blob_fee
+    }
+}
+
+/// Append-only log of the transactions submitted by this process
+#[derive(Debug, Default)]
+pub struct Journal {
+    file: Mutex<Option<File>>,
+    proposals: RwLock<HashMap<Address, u64>>,
+}
+
+impl Journal {
+    /// Starts appending entries to the given journal file.
+    pub fn open(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("create_dir_all")?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        *self.file.lock().unwrap() = Some(file);
+        Ok(())
+    }
+
+    /// Associates the proposal contract with its index for later entries.
+    pub fn register_proposal(&self, contract: Address, index: u64) {
+        self.proposals.write().unwrap().insert(contract, index);
+    }
+
+    pub fn proposal_index(&self, contract: &Address) -> Option<u64> {
+        self.proposals.read().unwrap().get(contract).copied()
+    }
+
+    /// Durably appends the entry to the journal file, if one is open.
+    pub fn append(&self, entry: &JournalEntry) -> anyhow::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        let Some(file) = file.as_mut() else {
+            return Ok(());
+        };
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        file.write_all(&line).context("write_all")?;
+        file.sync_data().context("sync_data")
+    }
+
+    /// Records the outcome of submitting a transaction.
+    pub fn record<R: ReceiptResponse>(
+        &self,
+        intent: &str,
+        urgency: Urgency,
+        to: Option<Address>,
+        value: U256,
+        calldata: &Bytes,
+        result: &anyhow::Result<R>,
+    ) {
+        let mut entry = JournalEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            intent: intent.to_string(),
+            urgency,
+            proposal_index: to.and_then(|to| self.proposal_index(&to)),
+            from: None,
+            to,
+            value,
+            calldata_hash: keccak256(calldata),
+            outcome: Outcome::Failed,
+            error: None,
+            txn_hash: None,
+            block_number: None,
+            gas_used: None,
+            effective_gas_price: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+        };
+        match result {
+            Ok(receipt) => {
+                entry.outcome = if receipt.status() {
+                    Outcome::Confirmed
+                } else {
+                    Outcome::Reverted
+                };
+                entry.from = Some(receipt.from());
+                entry.txn_hash = Some(receipt.transaction_hash());
+                entry.block_number = receipt.block_number();
+                entry.gas_used = Some(receipt.gas_used());
+                entry.effective_gas_price = Some(receipt.effective_gas_price());
+                entry.blob_gas_used = receipt.blob_gas_used();
+                entry.blob_gas_price = receipt.blob_gas_price();
+            }
+            Err(err) => entry.error = Some(format!("{err:?}")),
+        }
+        if let Err(err) = self.append(&entry) {
+            error!("Failed to journal {intent} transaction: {err:?}");
+        }
+    }
+}
+
+/// Criteria for selecting journal entries
+#[derive(Clone, Debug, Default)]
+pub struct JournalFilter {
+    /// Substring of the intent (e.g. `resolve`)
+    pub intent: Option<String>,
+    pub proposal_index: Option<u64>,
+    pub outcome: Option<Outcome>,
+    /// Earliest unix timestamp in seconds (inclusive)
+    pub since: Option<u64>,
+    /// Latest unix timestamp in seconds (inclusive)
+    pub until: Option<u64>,
+}
+
+impl JournalFilter {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        self.intent
+            .as_ref()
+            .is_none_or(|intent| entry.intent.contains(intent))
+            && self
+                .proposal_index
+                .is_none_or(|index| entry.proposal_index == Some(index))
+            && self.outcome.is_none_or(|outcome| entry.outcome == outcome)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+    }
+}
+
+/// Reads all entries from the journal file, skipping any that are malformed.
+pub fn read_journal(path: &Path) -> anyhow::Result<Vec<JournalEntry>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut entries = vec![];
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.context("read_line")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => warn!("Skipping malformed journal line {}: {err:?}", number + 1),
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy::rpc::types::TransactionReceipt;
+
+    fn receipt(status: bool) -> TransactionReceipt {
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom::new(
+                Receipt {
+                    status: Eip658Value::Eip658(status),
+                    cumulative_gas_used: 50_000,
+                    logs: vec![],
+                },
+                Default::default(),
+            )),
+            transaction_hash: TxHash::repeat_byte(0x01),
+            transaction_index: Some(0),
+            block_hash: Some(B256::repeat_byte(0x02)),
+            block_number: Some(100),
+            gas_used: 50_000,
+            effective_gas_price: 10,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::repeat_byte(0x03),
+            to: Some(Address::repeat_byte(0x04)),
+            contract_address: None,
+        }
+    }
+
+    #[test]
+    fn test_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(JOURNAL_FILE);
+        let journal = Journal::default();
+        // Nothing is written before the journal is opened
+        journal.record(
+            "KailuaTreasury::propose",
+            Urgency::Normal,
+            None,
+            U256::ZERO,
+            &Bytes::new(),
+            &Ok(receipt(true)),
+        );
+        journal.open(&path).unwrap();
+
+        let game = Address::repeat_byte(0x04);
+        journal.register_proposal(game, 7);
+        journal.record(
+            "KailuaGame::resolve",
+            Urgency::Deferrable,
+            Some(game),
+            U256::ZERO,
+            &Bytes::from_static(&[0x01]),
+            &Ok(receipt(true)),
+        );
+        journal.record(
+            "KailuaGame::resolve",
+            Urgency::Deferrable,
+            Some(game),
+            U256::ZERO,
+            &Bytes::from_static(&[0x01]),
+            &Ok(receipt(false)),
+        );
+        journal.record::<TransactionReceipt>(
+            "KailuaTournament::proveValidity",
+            Urgency::Critical,
+            Some(Address::repeat_byte(0x05)),
+            U256::from(1),
+            &Bytes::new(),
+            &Err(anyhow::anyhow!("nonce too low")),
+        );
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"truncated\n")
+            .unwrap();
+
+        let entries = read_journal(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].proposal_index, Some(7));
+        assert_eq!(entries[0].outcome, Outcome::Confirmed);
+        assert_eq!(entries[0].calldata_hash, keccak256([0x01]));
+        assert_eq!(entries[0].fee(), 500_000);
+        assert_eq!(entries[1].outcome, Outcome::Reverted);
+        assert_eq!(entries[2].outcome, Outcome::Failed);
+        assert_eq!(entries[2].proposal_index, None);
+        assert_eq!(entries[2].fee(), 0);
+        assert!(entries[2].error.as_ref().unwrap().contains("nonce too low"));
+
+        let filter = JournalFilter {
+            intent: Some("resolve".to_string()),
+            proposal_index: Some(7),
+            ..Default::default()
+        };
+        assert_eq!(entries.iter().filter(|e| filter.matches(e)).count(), 2);
+        let filter = JournalFilter {
+            outcome: Some(Outcome::Failed),
+            since: Some(entries[2].timestamp),
+            ..Default::default()
+        };
+        assert_eq!(entries.iter().filter(|e| filter.matches(e)).count(), 1);
+        let filter = JournalFilter {
+            until: Some(entries[0].timestamp - 1),
+            ..Default::default()
+        };
+        assert_eq!(entries.iter().filter(|e| filter.matches(e)).count(), 0);
+    }
+}
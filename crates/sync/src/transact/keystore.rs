@@ -0,0 +1,155 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::primitives::{Address, B256};
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Reads the keystore password from the given file, or prompts for it if unspecified.
+pub fn read_password(keystore: &Path, password_file: &Option<PathBuf>) -> anyhow::Result<String> {
+    match password_file {
+        Some(password_file) => {
+            let password = std::fs::read_to_string(password_file)
+                .with_context(|| format!("Failed to read {}", password_file.display()))?;
+            Ok(password.trim_end_matches(['\r', '\n']).to_string())
+        }
+        None => rpassword::prompt_password(format!("Password for {}: ", keystore.display()))
+            .context("prompt_password"),
+    }
+}
+
+/// Reads a new keystore password from the given file, or prompts for it twice if unspecified.
+pub fn read_new_password(
+    keystore: &Path,
+    password_file: &Option<PathBuf>,
+) -> anyhow::Result<String> {
+    let password = read_password(keystore, password_file)?;
+    if password_file.is_none() {
+        let confirmation =
+            rpassword::prompt_password("Confirm password: ").context("prompt_password")?;
+        if confirmation != password {
+            bail!("Passwords do not match.");
+        }
+    }
+    if password.is_empty() {
+        bail!("Keystore password must not be empty.");
+    }
+    Ok(password)
+}
+
+/// Decrypts the keystore using the password from the given file, or from a prompt.
+pub fn decrypt_keystore(
+    keystore: &Path,
+    password_file: &Option<PathBuf>,
+) -> anyhow::Result<PrivateKeySigner> {
+    let password = read_password(keystore, password_file)?;
+    PrivateKeySigner::decrypt_keystore(keystore, password)
+        .with_context(|| format!("Failed to decrypt {}", keystore.display()))
+}
+
+/// Encrypts the given private key, or a new random one, into a keystore file at the given path.
+pub fn create_keystore(
+    keystore: &Path,
+    private_key: Option<&str>,
+    password: &str,
+) -> anyhow::Result<PrivateKeySigner> {
+    if keystore.exists() {
+        bail!("Keystore {} already exists.", keystore.display());
+    }
+    let dir = keystore
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = keystore
+        .file_name()
+        .and_then(|name| name.to_str())
+        .context("Invalid keystore file name")?;
+    std::fs::create_dir_all(dir).context("create_dir_all")?;
+    let private_key = match private_key {
+        Some(private_key) => B256::from_str(private_key).context("Invalid private key")?,
+        None => PrivateKeySigner::random().to_bytes(),
+    };
+    let (signer, _) = PrivateKeySigner::encrypt_keystore(
+        dir,
+        &mut rand_08::thread_rng(),
+        private_key,
+        password,
+        Some(name),
+    )
+    .context("encrypt_keystore")?;
+    Ok(signer)
+}
+
+/// The unencrypted contents of a keystore file
+#[derive(Clone, Debug, Deserialize)]
+pub struct KeystoreInfo {
+    pub address: Option<String>,
+    pub crypto: KeystoreCrypto,
+    pub version: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub kdf: String,
+}
+
+impl KeystoreInfo {
+    /// Parses the keystore file without decrypting it.
+    pub fn read(keystore: &Path) -> anyhow::Result<Self> {
+        let data = std::fs::read(keystore)
+            .with_context(|| format!("Failed to read {}", keystore.display()))?;
+        serde_json::from_slice(&data).context("Invalid keystore file")
+    }
+
+    /// Returns the address stored in plain text in the keystore, if any.
+    pub fn address(&self) -> Option<Address> {
+        self.address
+            .as_deref()
+            .and_then(|address| Address::from_str(address).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let keystore = dir.path().join("proposer.json");
+        let password_file = dir.path().join("password.txt");
+        std::fs::write(&password_file, "correct horse\n").unwrap();
+
+        let private_key = B256::repeat_byte(0x11);
+        let created =
+            create_keystore(&keystore, Some(&private_key.to_string()), "correct horse").unwrap();
+        assert_eq!(created.to_bytes(), private_key);
+        // Keystores are never overwritten
+        create_keystore(&keystore, None, "correct horse").unwrap_err();
+
+        let info = KeystoreInfo::read(&keystore).unwrap();
+        assert_eq!(info.version, 3);
+        assert_eq!(info.crypto.cipher, "aes-128-ctr");
+
+        let decrypted = decrypt_keystore(&keystore, &Some(password_file.clone())).unwrap();
+        assert_eq!(decrypted.address(), created.address());
+
+        std::fs::write(&password_file, "wrong horse").unwrap();
+        decrypt_keystore(&keystore, &Some(password_file)).unwrap_err();
+    }
+}
@@ -12,25 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod dry_run;
 pub mod fillers;
+pub mod journal;
+pub mod keystore;
+pub mod nonce;
+pub mod policy;
 pub mod provider;
+pub mod remote;
 pub mod rpc;
 pub mod safe;
 pub mod signer;
 
 use alloy::contract::{CallBuilder, CallDecoder, EthCall};
-use alloy::network::{Network, TransactionBuilder4844};
+use alloy::network::{Network, TransactionBuilder, TransactionBuilder4844};
 use alloy::providers::fillers::JoinFill;
-use alloy::providers::Provider;
 use alloy::providers::{Identity, ProviderBuilder};
-use anyhow::Context;
+use alloy::providers::{PendingTransactionError, Provider, WatchTxError};
+use anyhow::{bail, Context};
 use async_trait::async_trait;
+use dry_run::{dry_run, NotBroadcast};
 use fillers::{PremiumBlobGasFiller, PremiumExecGasFiller, PremiumFiller};
+use journal::journal;
+use nonce::nonce_tracker;
 use opentelemetry::global::tracer;
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use policy::{fee_policy, FeeLimits, Urgency};
 use std::future::IntoFuture;
+use std::path::PathBuf;
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(clap::Args, Debug, Clone)]
 pub struct TransactArgs {
@@ -43,9 +54,54 @@ pub struct TransactArgs {
     /// Blob Gas Fee Premium
     #[clap(long, env, required = false, default_value_t = 25)]
     pub blob_gas_premium: u128,
+    /// Number of times a transaction that timed out is replaced with bumped fees
+    #[clap(long, env, required = false, default_value_t = 3)]
+    pub txn_max_replacements: u64,
+    /// Whether to cancel pending transactions left behind by earlier runs on startup
+    #[clap(long, env, default_value_t = false)]
+    pub cancel_stale_txns: bool,
+    /// Maximum fee per execution gas in wei (unbounded if unset)
+    #[clap(long, env)]
+    pub max_fee_per_gas: Option<u128>,
+    /// Maximum fee per blob gas in wei (unbounded if unset)
+    #[clap(long, env)]
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Maximum amount of wei to spend on non-critical transaction fees per hour
+    #[clap(long, env)]
+    pub hourly_fee_budget: Option<u128>,
+    /// Maximum amount of wei to spend on non-critical transaction fees per day
+    #[clap(long, env)]
+    pub daily_fee_budget: Option<u128>,
+    /// Gas price in wei above which deferrable transactions (resolutions, prunes) are delayed
+    #[clap(long, env)]
+    pub defer_above_gas_price: Option<u128>,
+    /// Whether to simulate transactions at the latest block instead of publishing them
+    #[clap(long, env, default_value_t = false)]
+    pub dry_run: bool,
+    /// File to write a JSON report of all simulated transactions to
+    #[clap(long, env, requires = "dry_run")]
+    pub dry_run_report: Option<PathBuf>,
 }
 
 impl TransactArgs {
+    pub fn fee_limits(&self) -> FeeLimits {
+        FeeLimits {
+            max_fee_per_gas: self.max_fee_per_gas,
+            max_fee_per_blob_gas: self.max_fee_per_blob_gas,
+            hourly_budget: self.hourly_fee_budget,
+            daily_budget: self.daily_fee_budget,
+            defer_above_gas_price: self.defer_above_gas_price,
+        }
+    }
+
+    /// Installs the configured replacement, fee and dry run settings for all transactions sent by
+    /// this process. Must be called once on startup before any transaction is sent.
+    pub fn install(&self) {
+        nonce_tracker().set_max_replacements(self.txn_max_replacements);
+        fee_policy().configure(self.fee_limits());
+        dry_run().configure(self.dry_run, self.dry_run_report.clone());
+    }
+
     pub fn premium_provider<N: Network>(
         &self,
     ) -> ProviderBuilder<Identity, JoinFill<Identity, PremiumFiller>>
@@ -58,12 +114,22 @@ impl TransactArgs {
 
 #[async_trait]
 pub trait Transact<N: Network> {
-    async fn transact(
+    async fn transact_with_urgency(
         &self,
         span: &'static str,
         timeout: Option<Duration>,
+        urgency: Urgency,
     ) -> anyhow::Result<N::ReceiptResponse>;
 
+    async fn transact(
+        &self,
+        span: &'static str,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<N::ReceiptResponse> {
+        self.transact_with_urgency(span, timeout, Urgency::Normal)
+            .await
+    }
+
     async fn timed_transact_with_context(
         &self,
         context: opentelemetry::Context,
@@ -73,6 +139,18 @@ pub trait Transact<N: Network> {
         self.transact(span, timeout).with_context(context).await
     }
 
+    async fn timed_transact_with_urgency(
+        &self,
+        context: opentelemetry::Context,
+        span: &'static str,
+        timeout: Option<Duration>,
+        urgency: Urgency,
+    ) -> anyhow::Result<N::ReceiptResponse> {
+        self.transact_with_urgency(span, timeout, urgency)
+            .with_context(context)
+            .await
+    }
+
     async fn transact_with_context(
         &self,
         context: opentelemetry::Context,
@@ -88,30 +166,152 @@ impl<'coder, P: Provider<N>, D: CallDecoder + Send + Sync + 'static, N: Network>
 where
     CallBuilder<P, D, N>: Clone,
     EthCall<'coder, D, N>: IntoFuture,
+    N::TransactionRequest: TransactionBuilder4844,
 {
-    async fn transact(
+    async fn transact_with_urgency(
         &self,
         span: &'static str,
         timeout: Option<Duration>,
+        urgency: Urgency,
     ) -> anyhow::Result<N::ReceiptResponse> {
         let tracer = tracer("kailua");
         let context = opentelemetry::Context::current_with_span(tracer.start(span));
 
-        // Publish transaction
-        let pending_txn = self
-            .send()
-            .with_context(context.with_span(tracer.start_with_context("send", &context)))
-            .await
-            .context("send")?;
-        info!("Transaction published: {:?}", pending_txn.tx_hash());
-
-        // Wait for receipt with timeout
-        pending_txn
-            .with_timeout(timeout)
-            .get_receipt()
-            .with_context(context.with_span(tracer.start_with_context("get_receipt", &context)))
-            .await
-            .context("get_receipt")
+        // Simulate transactions instead of publishing them during dry runs
+        let dry_run = dry_run();
+        if dry_run.is_enabled() {
+            let simulation = dry_run
+                .simulate(self, span)
+                .with_context(context.with_span(tracer.start_with_context("simulate", &context)))
+                .await?;
+            dry_run.record(simulation.clone())?;
+            return Err(NotBroadcast(simulation).into());
+        }
+
+        // Delay transactions as the fee policy requires
+        let policy = fee_policy();
+        policy
+            .check::<_, N>(&self.provider, urgency)
+            .with_context(context.with_span(tracer.start_with_context("check", &context)))
+            .await?;
+
+        // Publish transaction and record the outcome in the journal
+        let result: anyhow::Result<N::ReceiptResponse> = async {
+            let nonces = nonce_tracker();
+
+            // Publish transaction
+            let publication = nonces.lock_publication().await;
+            let mut pending_txn = self
+                .send()
+                .with_context(context.with_span(tracer.start_with_context("send", &context)))
+                .await
+                .context("send")?;
+            info!("Transaction published: {:?}", pending_txn.tx_hash());
+            let tracked = nonces
+                .fetch::<_, N>(&self.provider, *pending_txn.tx_hash())
+                .with_context(context.with_span(tracer.start_with_context("fetch", &context)))
+                .await;
+            drop(publication);
+
+            let Some(mut tracked) = tracked else {
+                warn!("Failed to track transaction {:?}", pending_txn.tx_hash());
+                let receipt = pending_txn
+                    .with_timeout(timeout)
+                    .get_receipt()
+                    .with_context(
+                        context.with_span(tracer.start_with_context("get_receipt", &context)),
+                    )
+                    .await
+                    .context("get_receipt")?;
+                policy.record_receipt(&receipt);
+                return Ok(receipt);
+            };
+            nonces.track(tracked.clone());
+
+            // Wait for receipt with timeout, replacing stuck transactions with bumped fees
+            let mut replacements = 0;
+            loop {
+                match pending_txn
+                    .with_timeout(timeout)
+                    .get_receipt()
+                    .with_context(
+                        context.with_span(tracer.start_with_context("get_receipt", &context)),
+                    )
+                    .await
+                {
+                    Ok(receipt) => {
+                        nonces.forget(tracked.from, tracked.nonce);
+                        policy.record_receipt(&receipt);
+                        return Ok(receipt);
+                    }
+                    Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout))
+                        if replacements < nonces.max_replacements() => {}
+                    Err(err) => return Err(err).context("get_receipt"),
+                }
+                // An earlier version of the transaction may have been included meanwhile
+                if let Some(receipt) = nonces
+                    .find_receipt::<_, N>(&self.provider, &tracked)
+                    .await?
+                {
+                    policy.record_receipt(&receipt);
+                    return Ok(receipt);
+                }
+
+                replacements += 1;
+                let fees = tracked.bumped_fees();
+                if policy.limits().exceeded_by(Some(fees.0), fees.2) {
+                    bail!(
+                        "Replacing stuck transaction with nonce {} would exceed the fee caps.",
+                        tracked.nonce
+                    );
+                }
+                warn!(
+                    "Replacing stuck transaction with nonce {} ({replacements}/{}).",
+                    tracked.nonce,
+                    nonces.max_replacements()
+                );
+                let mut replacement = self
+                    .clone()
+                    .nonce(tracked.nonce)
+                    .gas(tracked.gas_limit)
+                    .max_fee_per_gas(fees.0)
+                    .max_priority_fee_per_gas(fees.1);
+                if let Some(max_fee_per_blob_gas) = fees.2 {
+                    replacement = replacement.max_fee_per_blob_gas(max_fee_per_blob_gas);
+                }
+                pending_txn = match replacement
+                    .send()
+                    .with_context(context.with_span(tracer.start_with_context("send", &context)))
+                    .await
+                {
+                    Ok(pending_txn) => pending_txn,
+                    Err(err) => {
+                        if let Some(receipt) = nonces
+                            .find_receipt::<_, N>(&self.provider, &tracked)
+                            .await?
+                        {
+                            policy.record_receipt(&receipt);
+                            return Ok(receipt);
+                        }
+                        return Err(err).context("send");
+                    }
+                };
+                info!("Transaction replaced: {:?}", pending_txn.tx_hash());
+                tracked = tracked.replaced(*pending_txn.tx_hash(), fees);
+                nonces.track(tracked.clone());
+            }
+        }
+        .await;
+        let request = self.clone().into_transaction_request();
+        journal().record(
+            span,
+            urgency,
+            request.to(),
+            request.value().unwrap_or_default(),
+            self.calldata(),
+            &result,
+        );
+        result
     }
 }
 
@@ -0,0 +1,446 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::provider::beacon::blob_sidecar;
+use crate::transact::dry_run::dry_run;
+use alloy::consensus::{Blob, Transaction};
+use alloy::eips::eip1559::Eip1559Estimation;
+use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::network::{Network, TransactionBuilder, TransactionBuilder4844, TransactionResponse};
+use alloy::primitives::{Address, TxHash, U256};
+use alloy::providers::fillers::NonceManager;
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::transports::TransportResult;
+use anyhow::Context;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::futures::Notified;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// Minimum fee increase in percent for nodes to accept a replacement transaction
+pub const EXEC_REPLACEMENT_BUMP: u128 = 10;
+/// Minimum fee increase in percent for nodes to accept a replacement blob transaction (EIP-4844)
+pub const BLOB_REPLACEMENT_BUMP: u128 = 100;
+/// Time after which a tracked transaction missing from the transaction pool is considered dropped
+pub const DROPPED_TXN_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+static NONCE_TRACKER: LazyLock<NonceTracker> = LazyLock::new(NonceTracker::default);
+
+/// Returns the nonce tracker shared by all transaction senders in this process.
+pub fn nonce_tracker() -> &'static NonceTracker {
+    &NONCE_TRACKER
+}
+
+/// A transaction published by this process that is not yet known to be confirmed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackedTransaction {
+    pub from: Address,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Hashes of all published versions of this transaction, latest last
+    pub hashes: Vec<TxHash>,
+    pub published_at: Instant,
+}
+
+impl TrackedTransaction {
+    pub fn from_response<T: TransactionResponse>(txn: &T) -> Self {
+        Self {
+            from: txn.from(),
+            nonce: txn.nonce(),
+            gas_limit: txn.gas_limit(),
+            max_fee_per_gas: txn.max_fee_per_gas(),
+            max_priority_fee_per_gas: txn
+                .max_priority_fee_per_gas()
+                .unwrap_or(txn.max_fee_per_gas()),
+            max_fee_per_blob_gas: txn.max_fee_per_blob_gas(),
+            hashes: vec![txn.tx_hash()],
+            published_at: Instant::now(),
+        }
+    }
+
+    /// Returns the minimum (exec, priority, blob) fees of a replacement for this transaction.
+    pub fn bumped_fees(&self) -> (u128, u128, Option<u128>) {
+        // Blob transactions must bump all fees by 100% to be replaced
+        let percent = match self.max_fee_per_blob_gas {
+            Some(_) => BLOB_REPLACEMENT_BUMP,
+            None => EXEC_REPLACEMENT_BUMP,
+        };
+        (
+            bump_fee(self.max_fee_per_gas, percent),
+            bump_fee(self.max_priority_fee_per_gas, percent),
+            self.max_fee_per_blob_gas
+                .map(|fee| bump_fee(fee, BLOB_REPLACEMENT_BUMP)),
+        )
+    }
+
+    /// Records the publication of a replacement with the given hash and (exec, priority, blob) fees.
+    pub fn replaced(mut self, hash: TxHash, fees: (u128, u128, Option<u128>)) -> Self {
+        (
+            self.max_fee_per_gas,
+            self.max_priority_fee_per_gas,
+            self.max_fee_per_blob_gas,
+        ) = fees;
+        self.hashes.push(hash);
+        self.published_at = Instant::now();
+        self
+    }
+}
+
+/// Increases the fee by the given percentage, rounding up.
+pub fn bump_fee(fee: u128, percent: u128) -> u128 {
+    fee.saturating_mul(100 + percent)
+        .div_ceil(100)
+        .max(fee.saturating_add(1))
+}
+
+/// Tracks the pending transactions of each sender to hand out gap-free nonces
+#[derive(Debug, Default)]
+pub struct NonceTracker {
+    /// Pending transactions by nonce for each sender
+    accounts: Mutex<HashMap<Address, BTreeMap<u64, TrackedTransaction>>>,
+    /// Serializes transaction publication so that nonces are tracked before being reused
+    publication: tokio::sync::Mutex<()>,
+    /// Number of times a stuck transaction is replaced before giving up
+    max_replacements: AtomicU64,
+    /// Notifies waiters whenever a transaction is tracked
+    tracked: Notify,
+}
+
+impl NonceTracker {
+    /// Acquires exclusive access to publish a transaction.
+    pub async fn lock_publication(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.publication.lock().await
+    }
+
+    pub fn max_replacements(&self) -> u64 {
+        self.max_replacements.load(Ordering::Relaxed)
+    }
+
+    pub fn set_max_replacements(&self, max_replacements: u64) {
+        self.max_replacements
+            .store(max_replacements, Ordering::Relaxed);
+    }
+
+    /// Returns the lowest nonce of the sender not held by any pending transaction.
+    pub async fn next_nonce<P: Provider<N>, N: Network>(
+        &self,
+        provider: &P,
+        address: Address,
+    ) -> TransportResult<u64> {
+        let latest = provider
+            .get_transaction_count(address)
+            .block_id(BlockId::Number(BlockNumberOrTag::Latest))
+            .await?;
+        let pending = provider
+            .get_transaction_count(address)
+            .block_id(BlockId::Number(BlockNumberOrTag::Pending))
+            .await?;
+        let mut accounts = self.accounts.lock().unwrap();
+        Ok(select_nonce(
+            accounts.entry(address).or_default(),
+            latest,
+            pending,
+        ))
+    }
+
+    /// Starts or updates the tracking of a published transaction.
+    pub fn track(&self, txn: TrackedTransaction) {
+        self.accounts
+            .lock()
+            .unwrap()
+            .entry(txn.from)
+            .or_default()
+            .insert(txn.nonce, txn);
+        self.tracked.notify_waiters();
+    }
+
+    /// Returns a future that completes once the next transaction is tracked.
+    pub fn tracked(&self) -> Notified<'_> {
+        self.tracked.notified()
+    }
+
+    /// Stops tracking the transaction of the sender with the given nonce.
+    pub fn forget(&self, address: Address, nonce: u64) {
+        if let Some(pending) = self.accounts.lock().unwrap().get_mut(&address) {
+            pending.remove(&nonce);
+        }
+    }
+
+    /// Returns whether the sender has a tracked transaction with the given nonce.
+    pub fn is_tracked(&self, address: Address, nonce: u64) -> bool {
+        self.accounts
+            .lock()
+            .unwrap()
+            .get(&address)
+            .is_some_and(|pending| pending.contains_key(&nonce))
+    }
+
+    /// Fetches the details of a freshly published transaction for tracking.
+    pub async fn fetch<P: Provider<N>, N: Network>(
+        &self,
+        provider: &P,
+        hash: TxHash,
+    ) -> Option<TrackedTransaction> {
+        // The node may take a moment to serve a transaction it has just accepted
+        for _ in 0..5 {
+            match provider.get_transaction_by_hash(hash).await {
+                Ok(Some(txn)) => return Some(TrackedTransaction::from_response(&txn)),
+                Ok(None) => {}
+                Err(err) => warn!("Failed to fetch transaction {hash}: {err:?}"),
+            }
+            sleep(Duration::from_millis(200)).await;
+        }
+        None
+    }
+
+    /// Returns the receipt of any published version of the tracked transaction.
+    pub async fn find_receipt<P: Provider<N>, N: Network>(
+        &self,
+        provider: &P,
+        txn: &TrackedTransaction,
+    ) -> anyhow::Result<Option<N::ReceiptResponse>> {
+        for hash in txn.hashes.iter().rev() {
+            if let Some(receipt) = provider
+                .get_transaction_receipt(*hash)
+                .await
+                .context("get_transaction_receipt")?
+            {
+                self.forget(txn.from, txn.nonce);
+                return Ok(Some(receipt));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Handles the transactions of the sender left pending by earlier runs, either by skipping
+    /// over their nonces or by replacing them with empty self-transfers, which carry a blob if
+    /// needed to replace a stuck blob transaction. Fails if a transaction cannot be replaced.
+    pub async fn reconcile<P: Provider>(
+        &self,
+        provider: &P,
+        address: Address,
+        cancel: bool,
+    ) -> anyhow::Result<()> {
+        let latest = provider
+            .get_transaction_count(address)
+            .block_id(BlockId::Number(BlockNumberOrTag::Latest))
+            .await
+            .context("get_transaction_count")?;
+        let pending = provider
+            .get_transaction_count(address)
+            .block_id(BlockId::Number(BlockNumberOrTag::Pending))
+            .await
+            .context("get_transaction_count")?;
+        let stale = (latest..pending)
+            .filter(|nonce| !self.is_tracked(address, *nonce))
+            .collect::<Vec<_>>();
+        if stale.is_empty() {
+            return Ok(());
+        }
+        if dry_run().is_enabled() {
+            warn!(
+                "Dry run: not handling {} pending transactions of {address} from earlier runs.",
+                stale.len()
+            );
+            return Ok(());
+        }
+        if !cancel {
+            warn!(
+                "Skipping over {} pending transactions of {address} from earlier runs.",
+                stale.len()
+            );
+            return Ok(());
+        }
+
+        let estimate = provider
+            .estimate_eip1559_fees()
+            .await
+            .context("estimate_eip1559_fees")?;
+        for nonce in stale {
+            if let Err(err) = self.cancel(provider, address, nonce, &estimate, None).await {
+                // Pending blob transactions can only be replaced by blob transactions
+                warn!("Failed to cancel stale transaction with nonce {nonce}: {err:?}");
+                let blob_base_fee = provider
+                    .get_blob_base_fee()
+                    .await
+                    .context("get_blob_base_fee")?;
+                self.cancel(provider, address, nonce, &estimate, Some(blob_base_fee))
+                    .await
+                    .with_context(|| {
+                        format!("Failed to cancel stale transaction with nonce {nonce}")
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the pending transaction of the sender with the given nonce by an empty
+    /// self-transfer, which carries a single empty blob if a blob base fee is given.
+    async fn cancel<P: Provider>(
+        &self,
+        provider: &P,
+        address: Address,
+        nonce: u64,
+        estimate: &Eip1559Estimation,
+        blob_base_fee: Option<u128>,
+    ) -> anyhow::Result<()> {
+        let sidecar = blob_base_fee
+            .map(|_| blob_sidecar(vec![Blob::default()]))
+            .transpose()?;
+        // The fees of stale transactions are unknown, so keep doubling until accepted
+        let mut factor = 2;
+        loop {
+            let max_fee_per_gas = estimate.max_fee_per_gas.max(1) * factor;
+            let max_priority_fee_per_gas = estimate.max_priority_fee_per_gas.max(1) * factor;
+            let max_fee_per_blob_gas = blob_base_fee.map(|fee| fee.max(1) * factor);
+            let mut cancellation = TransactionRequest::default()
+                .with_from(address)
+                .with_to(address)
+                .with_value(U256::ZERO)
+                .with_nonce(nonce)
+                .with_gas_limit(21_000)
+                .with_max_fee_per_gas(max_fee_per_gas)
+                .with_max_priority_fee_per_gas(max_priority_fee_per_gas);
+            if let (Some(sidecar), Some(max_fee_per_blob_gas)) = (&sidecar, max_fee_per_blob_gas) {
+                cancellation = cancellation
+                    .with_blob_sidecar(sidecar.clone())
+                    .with_max_fee_per_blob_gas(max_fee_per_blob_gas);
+            }
+            match provider.send_transaction(cancellation).await {
+                Ok(pending_txn) => {
+                    info!(
+                        "Cancelling stale transaction with nonce {nonce}: {:?}",
+                        pending_txn.tx_hash()
+                    );
+                    self.track(TrackedTransaction {
+                        from: address,
+                        nonce,
+                        gas_limit: 21_000,
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                        max_fee_per_blob_gas,
+                        hashes: vec![*pending_txn.tx_hash()],
+                        published_at: Instant::now(),
+                    });
+                    return Ok(());
+                }
+                Err(err) if err.to_string().contains("underpriced") && factor < 64 => {
+                    factor *= 2;
+                }
+                Err(err) => return Err(err).context("send_transaction"),
+            }
+        }
+    }
+}
+
+/// Forgets confirmed and dropped transactions and returns the lowest free nonce.
+fn select_nonce(tracked: &mut BTreeMap<u64, TrackedTransaction>, latest: u64, pending: u64) -> u64 {
+    // Forget confirmed transactions
+    tracked.retain(|nonce, _| *nonce >= latest);
+    // Forget transactions that have been missing from the pool for too long
+    tracked.retain(|nonce, txn| {
+        let dropped = *nonce >= pending && txn.published_at.elapsed() > DROPPED_TXN_GRACE_PERIOD;
+        if dropped {
+            warn!(
+                "Transaction with nonce {nonce} was dropped: {:?}",
+                txn.hashes
+            );
+        }
+        !dropped
+    });
+    // Skip over nonces held by transactions we or earlier runs published
+    let mut nonce = pending.max(latest);
+    while tracked.contains_key(&nonce) {
+        nonce += 1;
+    }
+    nonce
+}
+
+/// A [NonceManager] that hands out nonces through the process-wide [NonceTracker]
+#[derive(Clone, Debug, Default)]
+pub struct TrackedNonceManager;
+
+#[async_trait]
+impl NonceManager for TrackedNonceManager {
+    async fn get_next_nonce<P, N>(&self, provider: &P, address: Address) -> TransportResult<u64>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        nonce_tracker().next_nonce::<P, N>(provider, address).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracked(nonce: u64, published_at: Instant) -> TrackedTransaction {
+        TrackedTransaction {
+            from: Address::ZERO,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 10,
+            max_fee_per_blob_gas: None,
+            hashes: vec![TxHash::with_last_byte(nonce as u8)],
+            published_at,
+        }
+    }
+
+    #[test]
+    fn test_bumped_fees() {
+        let mut txn = tracked(0, Instant::now());
+        assert_eq!(txn.bumped_fees(), (110, 11, None));
+        txn.max_priority_fee_per_gas = 0;
+        assert_eq!(txn.bumped_fees().1, 1);
+        txn.max_fee_per_blob_gas = Some(7);
+        assert_eq!(txn.bumped_fees(), (200, 1, Some(14)));
+
+        let txn = txn.replaced(TxHash::ZERO, (200, 1, Some(14)));
+        assert_eq!(txn.hashes.len(), 2);
+        assert_eq!(txn.bumped_fees(), (400, 2, Some(28)));
+    }
+
+    #[test]
+    fn test_select_nonce() {
+        let now = Instant::now();
+        let stale = now - DROPPED_TXN_GRACE_PERIOD * 2;
+        let mut pending = BTreeMap::new();
+        // No tracked transactions skip over the pending ones of earlier runs
+        assert_eq!(select_nonce(&mut pending, 3, 5), 5);
+        // Tracked transactions are skipped over
+        pending.insert(5, tracked(5, now));
+        pending.insert(6, tracked(6, now));
+        assert_eq!(select_nonce(&mut pending, 3, 7), 7);
+        // Confirmed transactions are forgotten
+        assert_eq!(select_nonce(&mut pending, 6, 7), 7);
+        assert_eq!(pending.len(), 1);
+        // Recently published transactions are kept while missing from the pool
+        assert_eq!(select_nonce(&mut pending, 6, 6), 7);
+        // Dropped transactions are forgotten and their nonce reused
+        pending.insert(6, tracked(6, stale));
+        assert_eq!(select_nonce(&mut pending, 6, 6), 6);
+        assert!(pending.is_empty());
+    }
+}
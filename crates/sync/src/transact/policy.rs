@@ -0,0 +1,209 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::network::{Network, ReceiptResponse};
+use alloy::providers::Provider;
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Length of the window of the hourly fee budget
+pub const HOUR: Duration = Duration::from_secs(60 * 60);
+/// Length of the window of the daily fee budget
+pub const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+static FEE_POLICY: LazyLock<FeePolicy> = LazyLock::new(FeePolicy::default);
+
+/// Returns the fee policy shared by all transaction senders in this process.
+pub fn fee_policy() -> &'static FeePolicy {
+    &FEE_POLICY
+}
+
+/// How much a transaction may be delayed to save on fees
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Urgency {
+    /// Delayed while fees spike or any fee budget is exhausted (e.g. resolutions)
+    Deferrable,
+    /// Delayed while any fee budget is exhausted (e.g. proposals)
+    #[default]
+    Normal,
+    /// Never delayed because a deadline is at stake (e.g. challenges before timeout)
+    Critical,
+}
+
+/// Absolute limits on transaction fees
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeLimits {
+    /// Maximum fee per execution gas in wei
+    pub max_fee_per_gas: Option<u128>,
+    /// Maximum fee per blob gas in wei
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Maximum amount of wei to spend on fees in any hour
+    pub hourly_budget: Option<u128>,
+    /// Maximum amount of wei to spend on fees in any day
+    pub daily_budget: Option<u128>,
+    /// Gas price in wei above which deferrable transactions are delayed
+    pub defer_above_gas_price: Option<u128>,
+}
+
+impl FeeLimits {
+    /// Lowers the given fee per execution gas to the configured cap.
+    pub fn cap_fee_per_gas(&self, fee: u128) -> u128 {
+        self.max_fee_per_gas.map_or(fee, |cap| fee.min(cap))
+    }
+
+    /// Lowers the given fee per blob gas to the configured cap.
+    pub fn cap_fee_per_blob_gas(&self, fee: u128) -> u128 {
+        self.max_fee_per_blob_gas.map_or(fee, |cap| fee.min(cap))
+    }
+
+    /// Returns whether the given fees exceed any configured cap.
+    pub fn exceeded_by(&self, fee_per_gas: Option<u128>, fee_per_blob_gas: Option<u128>) -> bool {
+        let exceeds =
+            |fee: Option<u128>, cap: Option<u128>| fee.zip(cap).is_some_and(|(f, c)| f > c);
+        exceeds(fee_per_gas, self.max_fee_per_gas)
+            || exceeds(fee_per_blob_gas, self.max_fee_per_blob_gas)
+    }
+}
+
+/// Enforces the configured fee caps and budgets on published transactions
+#[derive(Debug, Default)]
+pub struct FeePolicy {
+    limits: RwLock<FeeLimits>,
+    /// Fees paid by confirmed transactions, oldest first
+    spending: Mutex<VecDeque<(Instant, u128)>>,
+}
+
+impl FeePolicy {
+    pub fn configure(&self, limits: FeeLimits) {
+        *self.limits.write().unwrap() = limits;
+    }
+
+    pub fn limits(&self) -> FeeLimits {
+        *self.limits.read().unwrap()
+    }
+
+    /// Records the fees paid by a confirmed transaction.
+    pub fn record_receipt<R: ReceiptResponse>(&self, receipt: &R) {
+        let exec_fee = receipt.gas_used() as u128 * receipt.effective_gas_price();
+        let blob_fee = receipt.blob_gas_used().unwrap_or_default() as u128
+            * receipt.blob_gas_price().unwrap_or_default();
+        self.record_spending(exec_fee + blob_fee);
+    }
+
+    pub fn record_spending(&self, amount: u128) {
+        let mut spending = self.spending.lock().unwrap();
+        spending.push_back((Instant::now(), amount));
+        // Forget spending older than the longest budget window
+        while spending
+            .front()
+            .is_some_and(|(time, _)| time.elapsed() > DAY)
+        {
+            spending.pop_front();
+        }
+    }
+
+    /// Returns the amount spent on fees within the given window.
+    pub fn spent_within(&self, window: Duration) -> u128 {
+        self.spending
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(time, _)| time.elapsed() <= window)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    /// Fails if the budgets do not allow publishing a transaction with the given urgency.
+    pub fn check_budget(&self, urgency: Urgency) -> anyhow::Result<()> {
+        if urgency == Urgency::Critical {
+            return Ok(());
+        }
+        let limits = self.limits();
+        for (budget, window, name) in [
+            (limits.hourly_budget, HOUR, "hourly"),
+            (limits.daily_budget, DAY, "daily"),
+        ] {
+            let Some(budget) = budget else {
+                continue;
+            };
+            let spent = self.spent_within(window);
+            if spent >= budget {
+                bail!("Delaying transaction after spending {spent} of {budget} wei {name} budget.");
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if a transaction with the given urgency should be delayed under the current fees.
+    pub async fn check<P: Provider<N>, N: Network>(
+        &self,
+        provider: &P,
+        urgency: Urgency,
+    ) -> anyhow::Result<()> {
+        self.check_budget(urgency)?;
+        if urgency != Urgency::Deferrable {
+            return Ok(());
+        }
+        let Some(threshold) = self.limits().defer_above_gas_price else {
+            return Ok(());
+        };
+        let gas_price = provider.get_gas_price().await.context("get_gas_price")?;
+        if gas_price > threshold {
+            bail!("Delaying transaction while gas price {gas_price} exceeds {threshold} wei.");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_limits() {
+        let limits = FeeLimits {
+            max_fee_per_gas: Some(100),
+            max_fee_per_blob_gas: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(limits.cap_fee_per_gas(150), 100);
+        assert_eq!(limits.cap_fee_per_gas(50), 50);
+        assert_eq!(limits.cap_fee_per_blob_gas(11), 10);
+        assert!(!limits.exceeded_by(Some(100), None));
+        assert!(limits.exceeded_by(Some(101), None));
+        assert!(limits.exceeded_by(Some(1), Some(11)));
+        assert!(!FeeLimits::default().exceeded_by(Some(u128::MAX), Some(u128::MAX)));
+    }
+
+    #[test]
+    fn test_fee_budget() {
+        let policy = FeePolicy::default();
+        policy.configure(FeeLimits {
+            hourly_budget: Some(100),
+            daily_budget: Some(150),
+            ..Default::default()
+        });
+        policy.check_budget(Urgency::Deferrable).unwrap();
+        policy.record_spending(60);
+        policy.check_budget(Urgency::Normal).unwrap();
+        policy.record_spending(40);
+        assert_eq!(policy.spent_within(HOUR), 100);
+        assert!(policy.check_budget(Urgency::Deferrable).is_err());
+        assert!(policy.check_budget(Urgency::Normal).is_err());
+        policy.check_budget(Urgency::Critical).unwrap();
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::await_tel;
+use crate::transact::policy::fee_policy;
 use crate::transact::rpc::get_block;
 use alloy::consensus::transaction::SignerRecoverable;
 use alloy::consensus::{BlockHeader, Transaction};
@@ -21,12 +22,15 @@ use alloy::network::{BlockResponse, Ethereum, Network, TransactionBuilder4844};
 use alloy::providers::fillers::{FillProvider, TxFiller};
 use alloy::providers::network::TransactionBuilder;
 use alloy::providers::{PendingTransactionBuilder, Provider, RootProvider};
-use alloy::transports::TransportResult;
+use alloy::transports::{TransportErrorKind, TransportResult};
 use async_trait::async_trait;
 use opentelemetry::global::tracer;
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use tracing::info;
 
+/// Number of times a transaction rejected as underpriced is broadcast again with higher fees
+pub const MAX_UNDERPRICED_ATTEMPTS: u32 = 10;
+
 #[derive(Debug, Clone)]
 pub struct SafeProvider<P> {
     /// Inner provider.
@@ -58,18 +62,21 @@ where
         tx: <Ethereum as Network>::TransactionRequest,
     ) -> TransportResult<PendingTransactionBuilder<Ethereum>> {
         let mut fee_factor = 1.0;
+        let mut attempts = 0;
         let tracer = tracer("kailua");
         let context = opentelemetry::Context::current_with_span(
-            tracer.start("Proposal::fetch_current_challenger_duration"),
+            tracer.start("SafeProvider::send_transaction"),
         );
 
         loop {
+            attempts += 1;
             let mut tx = tx.clone();
             // Get latest block
             let latest_block = await_tel!(
                 context,
                 get_block(self.provider(), BlockNumberOrTag::Latest)
             )
+            .map_err(|err| TransportErrorKind::custom_str(&format!("{err:?}")))?
             .header()
             .number();
             info!("Testing transaction viability under block {latest_block}");
@@ -84,18 +91,26 @@ where
                 .unwrap();
             let sender = envelope.recover_signer().unwrap();
 
-            // Ensure call success
+            // Ensure call success, on top of any own transactions that are still pending
+            let confirmed_nonce = self
+                .get_transaction_count(sender)
+                .block_id(BlockId::Number(BlockNumberOrTag::Number(latest_block)))
+                .await?;
+            let simulation_block = if envelope.nonce() > confirmed_nonce {
+                info!(
+                    "Testing transaction viability on top of {} pending transactions",
+                    envelope.nonce() - confirmed_nonce
+                );
+                BlockNumberOrTag::Pending
+            } else {
+                BlockNumberOrTag::Number(latest_block)
+            };
             self.call(tx.clone().with_from(sender))
-                .block(BlockId::Number(BlockNumberOrTag::Number(latest_block)))
+                .block(BlockId::Number(simulation_block))
                 .await?;
 
-            // Set nonce to that as of successful call block
-            tx.set_nonce(
-                self.inner
-                    .get_transaction_count(sender)
-                    .block_id(BlockId::Number(BlockNumberOrTag::Number(latest_block)))
-                    .await?,
-            );
+            // Keep the nonce assigned by the nonce manager across fee increases
+            tx.set_nonce(envelope.nonce());
 
             info!(
                 "Broadcasting transaction with nonce {} and fee factor {fee_factor}",
@@ -110,12 +125,22 @@ where
                 tx.set_max_fee_per_blob_gas((fee as f64 * fee_factor) as u128);
             }
             tx.set_max_fee_per_gas((envelope.max_fee_per_gas() as f64 * fee_factor) as u128);
+            if fee_policy()
+                .limits()
+                .exceeded_by(tx.max_fee_per_gas, tx.max_fee_per_blob_gas)
+            {
+                break Err(TransportErrorKind::custom_str(
+                    "Transaction fees would exceed the configured caps",
+                ));
+            }
 
             // attempt broadcast
             match self.inner.send_transaction(tx).await {
                 Ok(res) => break Ok(res),
                 Err(err) => {
-                    if !err.to_string().contains("underpriced") {
+                    if !err.to_string().contains("underpriced")
+                        || attempts >= MAX_UNDERPRICED_ATTEMPTS
+                    {
                         break Err(err);
                     }
                     // increase fees
@@ -0,0 +1,317 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::consensus::transaction::SignerRecoverable;
+use alloy::consensus::{SignableTransaction, Transaction, TxEnvelope};
+use alloy::eips::eip2718::Decodable2718;
+use alloy::network::TxSigner;
+use alloy::primitives::{Address, Bytes, ChainId, Signature};
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::types::{TransactionInput, TransactionRequest};
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use tracing::info;
+
+/// Message signed with `eth_sign` to verify that the remote signer controls its account
+pub const REMOTE_SIGNER_CHALLENGE: &[u8] = b"kailua remote signer check";
+
+/// A transaction signer delegating to a remote service over `eth_signTransaction` and `eth_sign`
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    client: RootProvider,
+    address: Address,
+    chain_id: Option<ChainId>,
+}
+
+impl RemoteSigner {
+    pub fn new(client: RootProvider, address: Address, chain_id: Option<ChainId>) -> Self {
+        Self {
+            client,
+            address,
+            chain_id,
+        }
+    }
+
+    /// Connects to the remote signer and verifies that it can sign for the given address, or
+    /// for its first account if unspecified.
+    pub async fn connect(
+        url: &str,
+        address: Option<Address>,
+        chain_id: Option<ChainId>,
+    ) -> anyhow::Result<Self> {
+        let client = RootProvider::new_http(url.try_into().context("Invalid remote signer url")?);
+        let address = match address {
+            Some(address) => address,
+            None => *client
+                .get_accounts()
+                .await
+                .context("eth_accounts")?
+                .first()
+                .context("Remote signer has no accounts")?,
+        };
+        let signer = Self::new(client, address, chain_id);
+        signer.verify().await?;
+        info!("Remote signer ready for {address}.");
+        Ok(signer)
+    }
+
+    /// Ensures that the remote signer produces valid signatures for the configured address.
+    pub async fn verify(&self) -> anyhow::Result<()> {
+        let signature = self.sign_message(REMOTE_SIGNER_CHALLENGE).await?;
+        let signer = signature
+            .recover_address_from_msg(REMOTE_SIGNER_CHALLENGE)
+            .context("recover_address_from_msg")?;
+        if signer != self.address {
+            bail!(
+                "Remote signer signed for {signer} instead of {}.",
+                self.address
+            );
+        }
+        Ok(())
+    }
+
+    /// Signs the given message using `eth_sign`.
+    pub async fn sign_message(&self, message: &[u8]) -> anyhow::Result<Signature> {
+        let signature: Bytes = self
+            .client
+            .raw_request(
+                "eth_sign".into(),
+                (self.address, Bytes::copy_from_slice(message)),
+            )
+            .await
+            .context("eth_sign")?;
+        Signature::try_from(signature.as_ref()).context("Signature::try_from")
+    }
+
+    async fn sign(&self, tx: &mut dyn SignableTransaction<Signature>) -> anyhow::Result<Signature> {
+        if let Some(chain_id) = self.chain_id {
+            if !tx.set_chain_id_checked(chain_id) {
+                bail!("Transaction chain id does not match signer chain id {chain_id}.");
+            }
+        }
+        let raw: Bytes = self
+            .client
+            .raw_request(
+                "eth_signTransaction".into(),
+                (signing_request(self.address, tx),),
+            )
+            .await
+            .context("eth_signTransaction")?;
+        let envelope = TxEnvelope::decode_2718(&mut raw.as_ref()).context("decode_2718")?;
+        // The remote signer must not have altered the transaction
+        if envelope.signature_hash() != tx.signature_hash() {
+            bail!("Remote signer returned a different transaction.");
+        }
+        let signer = envelope.recover_signer().context("recover_signer")?;
+        if signer != self.address {
+            bail!(
+                "Remote signer signed for {signer} instead of {}.",
+                self.address
+            );
+        }
+        Ok(*envelope.signature())
+    }
+}
+
+#[async_trait]
+impl TxSigner<Signature> for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        self.sign(tx)
+            .await
+            .map_err(|err| alloy::signers::Error::other(format!("{err:?}")))
+    }
+}
+
+/// Converts the unsigned transaction into a request for the remote signer.
+///
+/// Blob transactions are sent without their sidecar, which is not part of the signed payload.
+pub fn signing_request(
+    from: Address,
+    tx: &dyn SignableTransaction<Signature>,
+) -> TransactionRequest {
+    let mut request = TransactionRequest {
+        from: Some(from),
+        to: Some(tx.kind()),
+        value: Some(tx.value()),
+        input: TransactionInput::new(tx.input().clone()),
+        nonce: Some(tx.nonce()),
+        chain_id: tx.chain_id(),
+        gas: Some(tx.gas_limit()),
+        transaction_type: Some(tx.ty()),
+        access_list: tx.access_list().cloned(),
+        max_fee_per_blob_gas: tx.max_fee_per_blob_gas(),
+        blob_versioned_hashes: tx.blob_versioned_hashes().map(|hashes| hashes.to_vec()),
+        authorization_list: tx.authorization_list().map(|list| list.to_vec()),
+        ..Default::default()
+    };
+    match tx.gas_price() {
+        Some(gas_price) => request.gas_price = Some(gas_price),
+        None => {
+            request.max_fee_per_gas = Some(tx.max_fee_per_gas());
+            request.max_priority_fee_per_gas = tx.max_priority_fee_per_gas();
+        }
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::{TxEip1559, TxEip4844, TxLegacy, TypedTransaction};
+    use alloy::eips::eip2718::Encodable2718;
+    use alloy::network::TxSignerSync;
+    use alloy::primitives::{TxKind, B256, U256};
+    use alloy::rpc::client::RpcClient;
+    use alloy::rpc::json_rpc::{
+        ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest,
+    };
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+    use alloy::transports::{TransportError, TransportFut};
+    use serde_json::{json, Value};
+    use std::task::{Context, Poll};
+
+    /// A local stand-in for a remote signing service
+    #[derive(Clone)]
+    struct StandInSigner(PrivateKeySigner);
+
+    impl StandInSigner {
+        fn handle(&self, request: &SerializedRequest) -> anyhow::Result<Value> {
+            let params = request
+                .params()
+                .map(|params| serde_json::from_str::<Vec<Value>>(params.get()))
+                .transpose()?
+                .unwrap_or_default();
+            match request.method() {
+                "eth_accounts" => Ok(json!([self.0.address()])),
+                "eth_sign" => {
+                    let message: Bytes = serde_json::from_value(params[1].clone())?;
+                    let signature = self.0.sign_message_sync(&message)?;
+                    Ok(json!(Bytes::from(signature.as_bytes())))
+                }
+                "eth_signTransaction" => {
+                    let request: TransactionRequest = serde_json::from_value(params[0].clone())?;
+                    let mut tx = request
+                        .build_typed_tx()
+                        .map_err(|_| anyhow::anyhow!("Incomplete transaction"))?;
+                    let signature = self.0.sign_transaction_sync(&mut tx)?;
+                    Ok(json!(Bytes::from(
+                        tx.into_envelope(signature).encoded_2718()
+                    )))
+                }
+                method => bail!("Unsupported method {method}"),
+            }
+        }
+
+        fn respond(&self, request: &SerializedRequest) -> Response {
+            let payload = match self.handle(request) {
+                Ok(result) => {
+                    ResponsePayload::Success(serde_json::value::to_raw_value(&result).unwrap())
+                }
+                Err(err) => ResponsePayload::Failure(ErrorPayload {
+                    code: -32000,
+                    message: err.to_string().into(),
+                    data: None,
+                }),
+            };
+            Response {
+                id: request.id().clone(),
+                payload,
+            }
+        }
+    }
+
+    impl tower::Service<RequestPacket> for StandInSigner {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: RequestPacket) -> Self::Future {
+            let response = match request {
+                RequestPacket::Single(request) => ResponsePacket::Single(self.respond(&request)),
+                RequestPacket::Batch(requests) => {
+                    ResponsePacket::Batch(requests.iter().map(|r| self.respond(r)).collect())
+                }
+            };
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let key = PrivateKeySigner::random();
+        let address = key.address();
+        let client = RootProvider::new(RpcClient::new(StandInSigner(key), true));
+        let signer = RemoteSigner::new(client.clone(), address, Some(1));
+        signer.verify().await.unwrap();
+        // A signer for the wrong account is rejected
+        RemoteSigner::new(client, Address::repeat_byte(0x01), Some(1))
+            .verify()
+            .await
+            .unwrap_err();
+
+        let to = Address::repeat_byte(0x02);
+        let transactions: [TypedTransaction; 3] = [
+            TxLegacy {
+                nonce: 1,
+                gas_price: 10,
+                gas_limit: 21_000,
+                to: TxKind::Call(to),
+                value: U256::from(1),
+                ..Default::default()
+            }
+            .into(),
+            TxEip1559 {
+                nonce: 2,
+                gas_limit: 100_000,
+                max_fee_per_gas: 20,
+                max_priority_fee_per_gas: 2,
+                to: TxKind::Call(to),
+                input: Bytes::from_static(&[0xde, 0xad]),
+                ..Default::default()
+            }
+            .into(),
+            TxEip4844 {
+                nonce: 3,
+                gas_limit: 100_000,
+                max_fee_per_gas: 20,
+                max_priority_fee_per_gas: 2,
+                to,
+                blob_versioned_hashes: vec![B256::repeat_byte(0x01)],
+                max_fee_per_blob_gas: 5,
+                ..Default::default()
+            }
+            .into(),
+        ];
+        for mut tx in transactions {
+            let signature = signer.sign_transaction(&mut tx).await.unwrap();
+            assert_eq!(tx.chain_id(), Some(1));
+            let recovered = signature
+                .recover_address_from_prehash(&tx.signature_hash())
+                .unwrap();
+            assert_eq!(recovered, address);
+        }
+    }
+}
@@ -18,6 +18,7 @@ use alloy::eips::{BlockId, BlockNumberOrTag};
 use alloy::network::{BlockResponse, Network};
 use alloy::primitives::{BlockNumber, B256};
 use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
 use anyhow::{anyhow, Context};
 use opentelemetry::global::tracer;
 use opentelemetry::trace::FutureExt;
@@ -34,12 +35,12 @@ pub async fn get_next_block<P: Provider<N>, N: Network>(
         context,
         tracer,
         "Provider::get_block_by_hash",
-        retry_res_ctx_timeout!(provider
+        retry_res_ctx_timeout!(EthRpc => provider
             .get_block_by_hash(parent_hash)
             .await
             .context("get_block_by_hash")?
             .ok_or_else(|| anyhow!("Failed to fetch parent block")))
-    );
+    )?;
     let parent_number = block_parent.header().number();
     let block = await_tel!(context, get_block_by_number(&provider, parent_number + 1))?;
 
@@ -62,7 +63,7 @@ pub async fn get_block_by_number<P: Provider<N>, N: Network>(
             .await
             .context("get_block_by_number")?
             .ok_or_else(|| anyhow!("Failed to fetch block")))
-    );
+    )?;
 
     Ok(block)
 }
@@ -70,7 +71,7 @@ pub async fn get_block_by_number<P: Provider<N>, N: Network>(
 pub async fn get_block<P: Provider<N>, N: Network>(
     provider: P,
     block_id: BlockNumberOrTag,
-) -> N::BlockResponse {
+) -> anyhow::Result<N::BlockResponse> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("get_block"));
 
@@ -78,10 +79,25 @@ pub async fn get_block<P: Provider<N>, N: Network>(
         context,
         tracer,
         "Provider::get_block",
-        retry_res_ctx_timeout!(provider
+        retry_res_ctx_timeout!(EthRpc => provider
             .get_block(BlockId::Number(block_id))
             .await
             .context("get_block")?
             .ok_or_else(|| anyhow!("Failed to fetch block")))
     )
 }
+
+pub async fn get_logs<P: Provider<N>, N: Network>(
+    provider: P,
+    filter: &Filter,
+) -> anyhow::Result<Vec<Log>> {
+    let tracer = tracer("kailua");
+    let context = opentelemetry::Context::current_with_span(tracer.start("get_logs"));
+
+    await_tel!(
+        context,
+        tracer,
+        "Provider::get_logs",
+        retry_res_ctx_timeout!(EthRpc => provider.get_logs(filter).await.context("get_logs"))
+    )
+}
@@ -12,40 +12,583 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::stall::Stall;
+use crate::transact::signer::SignerBackend;
 use crate::transact::Transact;
 use alloy::contract::SolCallBuilder;
 use alloy::network::{Network, TransactionBuilder};
-use alloy::primitives::{Address, Uint, U256};
+use alloy::primitives::{Address, Bytes, ChainId, Signature, B256, U256};
 use alloy::providers::Provider;
-use anyhow::Context;
+use alloy::signers::Signer;
+use alloy::sol;
+use alloy::sol_types::{eip712_domain, SolStruct};
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
+/// How often to look for new owner signatures while the Safe threshold is not met
+pub const SAFE_SIGNATURE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+sol! {
+    /// The EIP-712 structure signed by Safe owners
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+}
+
+/// A Safe transaction awaiting owner signatures
+///
+/// Refunds are never used, so the base gas, gas price, gas token and refund receiver are zero.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransaction {
+    pub safe: Address,
+    pub chain_id: ChainId,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub operation: u8,
+    pub safe_tx_gas: U256,
+    pub nonce: U256,
+    pub hash: B256,
+}
+
+impl SafeTransaction {
+    pub fn new(
+        safe: Address,
+        chain_id: ChainId,
+        to: Address,
+        value: U256,
+        data: Bytes,
+        safe_tx_gas: U256,
+        nonce: U256,
+    ) -> Self {
+        let mut txn = Self {
+            safe,
+            chain_id,
+            to,
+            value,
+            data,
+            operation: 0,
+            safe_tx_gas,
+            nonce,
+            hash: B256::ZERO,
+        };
+        txn.hash = txn.signing_hash();
+        txn
+    }
+
+    pub fn safe_tx(&self) -> SafeTx {
+        SafeTx {
+            to: self.to,
+            value: self.value,
+            data: self.data.clone(),
+            operation: self.operation,
+            safeTxGas: self.safe_tx_gas,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: Address::ZERO,
+            refundReceiver: Address::ZERO,
+            nonce: self.nonce,
+        }
+    }
+
+    /// Computes the EIP-712 hash that owners sign, as `Safe::getTransactionHash` would.
+    pub fn signing_hash(&self) -> B256 {
+        let domain = eip712_domain! {
+            chain_id: self.chain_id,
+            verifying_contract: self.safe,
+        };
+        self.safe_tx().eip712_signing_hash(&domain)
+    }
+
+    /// Fails if the stated hash does not commit to the transaction contents.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let hash = self.signing_hash();
+        if hash != self.hash {
+            bail!("Safe transaction hash {} should be {hash}.", self.hash);
+        }
+        Ok(())
+    }
+
+    /// Signs the transaction hash using the given owner signer.
+    ///
+    /// Remote signers only support `eth_sign`, which Safe accepts with an adjusted `v` value.
+    pub async fn sign(&self, signer: &SignerBackend) -> anyhow::Result<OwnerSignature> {
+        self.verify()?;
+        let signature = match signer {
+            SignerBackend::Local(signer) => {
+                SafeSignature::Typed(signer.sign_hash(&self.hash).await?)
+            }
+            SignerBackend::Aws(signer) => SafeSignature::Typed(signer.sign_hash(&self.hash).await?),
+            SignerBackend::Gcp(signer) => SafeSignature::Typed(signer.sign_hash(&self.hash).await?),
+            SignerBackend::Remote(signer) => {
+                SafeSignature::EthSign(signer.sign_message(self.hash.as_slice()).await?)
+            }
+        };
+        OwnerSignature::recover(self.hash, signature)
+    }
+
+    /// Path of the transaction payload inside the signatures directory
+    pub fn payload_path(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("{}.json", self.hash))
+    }
+
+    /// Path of an owner's signature inside the signatures directory
+    pub fn signature_path(&self, dir: &Path, owner: Address) -> PathBuf {
+        dir.join(format!("{}-{owner}.json", self.hash))
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let data =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let txn: Self = serde_json::from_slice(&data).context("Invalid Safe transaction")?;
+        txn.verify()?;
+        Ok(txn)
+    }
+
+    /// Writes the transaction payload into the signatures directory.
+    pub fn write(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(dir).context("create_dir_all")?;
+        let path = self.payload_path(dir);
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Reads all valid signatures for this transaction from the signatures directory.
+    pub fn read_signatures(&self, dir: &Path) -> anyhow::Result<Vec<OwnerSignature>> {
+        let prefix = format!("{}-", self.hash);
+        let mut signatures = vec![];
+        if !dir.exists() {
+            return Ok(signatures);
+        }
+        for entry in std::fs::read_dir(dir).context("read_dir")? {
+            let path = entry.context("read_dir")?.path();
+            if !path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix))
+            {
+                continue;
+            }
+            match OwnerSignature::read(&path) {
+                Ok(signature) if signature.hash == self.hash => signatures.push(signature),
+                Ok(_) => warn!("Ignoring signature {} for another hash.", path.display()),
+                Err(err) => warn!("Ignoring signature {}: {err:?}", path.display()),
+            }
+        }
+        Ok(signatures)
+    }
+}
+
+/// The forms of owner approval accepted by Safe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "signature")]
+pub enum SafeSignature {
+    /// Signature over the EIP-712 hash
+    Typed(Signature),
+    /// Signature over the EIP-191 prefixed hash
+    EthSign(Signature),
+    /// Approval by the executor or through `Safe::approveHash`
+    Approved,
+}
+
+/// An owner's approval of a Safe transaction hash
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnerSignature {
+    pub hash: B256,
+    pub owner: Address,
+    pub signature: SafeSignature,
+}
+
+impl OwnerSignature {
+    pub fn approved(hash: B256, owner: Address) -> Self {
+        Self {
+            hash,
+            owner,
+            signature: SafeSignature::Approved,
+        }
+    }
+
+    /// Recovers the owner that produced the given signature.
+    pub fn recover(hash: B256, signature: SafeSignature) -> anyhow::Result<Self> {
+        let owner = match signature {
+            SafeSignature::Typed(signature) => signature
+                .recover_address_from_prehash(&hash)
+                .context("recover_address_from_prehash")?,
+            SafeSignature::EthSign(signature) => signature
+                .recover_address_from_msg(hash.as_slice())
+                .context("recover_address_from_msg")?,
+            SafeSignature::Approved => bail!("Approvals do not carry a signature."),
+        };
+        Ok(Self {
+            hash,
+            owner,
+            signature,
+        })
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let data =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let claimed: Self = serde_json::from_slice(&data).context("Invalid owner signature")?;
+        let recovered = Self::recover(claimed.hash, claimed.signature)?;
+        if recovered.owner != claimed.owner {
+            bail!(
+                "Signature is by {} instead of {}.",
+                recovered.owner,
+                claimed.owner
+            );
+        }
+        Ok(recovered)
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Encodes the signature in the 65-byte format expected by `Safe::execTransaction`.
+    pub fn encode(&self) -> [u8; 65] {
+        match self.signature {
+            SafeSignature::Typed(signature) => signature.as_bytes(),
+            SafeSignature::EthSign(signature) => {
+                let mut bytes = signature.as_bytes();
+                bytes[64] += 4;
+                bytes
+            }
+            SafeSignature::Approved => {
+                let mut bytes = [0u8; 65];
+                bytes[12..32].copy_from_slice(self.owner.as_slice());
+                bytes[64] = 1;
+                bytes
+            }
+        }
+    }
+}
+
+/// Concatenates the signatures of distinct owners in the ascending order required by Safe.
+pub fn encode_signatures(signatures: &[OwnerSignature], threshold: usize) -> Bytes {
+    let by_owner = signatures
+        .iter()
+        .map(|signature| (signature.owner, signature))
+        .collect::<BTreeMap<_, _>>();
+    by_owner
+        .values()
+        .take(threshold)
+        .flat_map(|signature| signature.encode())
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Executes the call through the Safe once enough of its owners approve it.
+///
+/// The executor's own approval is implicit if it is an owner. Other approvals are collected from
+/// `Safe::approveHash` and from signature files in the signatures directory, where the payload
+/// of every transaction is written for offline signing.
+///
+/// Waiting for signatures fails once `signatures_timeout` elapses, or once the Safe nonce moves
+/// past that of the payload, which can then no longer be executed.
 pub async fn exec_safe_txn<P1: Provider<N>, P2: Provider<N>, C, N: Network>(
     txn: SolCallBuilder<P1, C, N>,
     safe: &kailua_contracts::Safe::SafeInstance<P2, N>,
     from: Address,
+    signatures_dir: &Option<PathBuf>,
+    signatures_timeout: Option<Duration>,
 ) -> anyhow::Result<()> {
     let req = txn.into_transaction_request();
-    safe.execTransaction(
+    let chain_id = safe
+        .provider()
+        .get_chain_id()
+        .await
+        .context("get_chain_id")?;
+    let nonce = safe.nonce().stall("Safe::nonce").await?;
+    let safe_txn = SafeTransaction::new(
+        *safe.address(),
+        chain_id,
         req.to().unwrap(),
         req.value().unwrap_or_default(),
         req.input().cloned().unwrap_or_default(),
-        0,
-        Uint::from(req.gas_limit().unwrap_or_default()),
-        U256::ZERO,
-        U256::ZERO,
-        Address::ZERO,
-        Address::ZERO,
-        [
-            [0u8; 12].as_slice(),
-            from.as_slice(),
-            [0u8; 32].as_slice(),
-            [1u8].as_slice(),
-        ]
-        .concat()
-        .into(),
+        U256::from(req.gas_limit().unwrap_or_default()),
+        nonce,
+    );
+    let safe_tx = safe_txn.safe_tx();
+    let expected_hash = safe
+        .getTransactionHash(
+            safe_tx.to,
+            safe_tx.value,
+            safe_tx.data.clone(),
+            safe_tx.operation,
+            safe_tx.safeTxGas,
+            safe_tx.baseGas,
+            safe_tx.gasPrice,
+            safe_tx.gasToken,
+            safe_tx.refundReceiver,
+            safe_tx.nonce,
+        )
+        .stall("Safe::getTransactionHash")
+        .await?;
+    if expected_hash != safe_txn.hash {
+        bail!(
+            "Safe transaction hash {} does not match Safe::getTransactionHash {expected_hash}.",
+            safe_txn.hash
+        );
+    }
+    if let Some(dir) = signatures_dir {
+        let path = safe_txn.write(dir)?;
+        info!(
+            "Safe transaction {} written to {}.",
+            safe_txn.hash,
+            path.display()
+        );
+    }
+
+    let threshold = safe
+        .getThreshold()
+        .stall("Safe::getThreshold")
+        .await?
+        .to::<usize>();
+    let owners = safe.getOwners().stall("Safe::getOwners").await?;
+    let started_at = Instant::now();
+    let signatures = loop {
+        let current_nonce = safe.nonce().stall("Safe::nonce").await?;
+        if current_nonce != nonce {
+            bail!(
+                "Safe nonce moved from {nonce} to {current_nonce} before Safe transaction {} was executed.",
+                safe_txn.hash
+            );
+        }
+        let mut signatures = vec![];
+        for owner in &owners {
+            if owner == &from {
+                signatures.push(OwnerSignature::approved(safe_txn.hash, *owner));
+            } else if !safe
+                .approvedHashes(*owner, safe_txn.hash)
+                .stall("Safe::approvedHashes")
+                .await?
+                .is_zero()
+            {
+                signatures.push(OwnerSignature::approved(safe_txn.hash, *owner));
+            }
+        }
+        if let Some(dir) = signatures_dir {
+            signatures.extend(
+                safe_txn
+                    .read_signatures(dir)?
+                    .into_iter()
+                    .filter(|signature| owners.contains(&signature.owner)),
+            );
+        }
+        signatures.sort_by_key(|signature| signature.owner);
+        signatures.dedup_by_key(|signature| signature.owner);
+        if signatures.len() >= threshold {
+            break signatures;
+        }
+        let Some(dir) = signatures_dir else {
+            bail!(
+                "Safe transaction {} has {} of {threshold} required approvals and no signatures directory was configured.",
+                safe_txn.hash,
+                signatures.len()
+            );
+        };
+        if signatures_timeout.is_some_and(|timeout| started_at.elapsed() >= timeout) {
+            bail!(
+                "Timed out waiting for Safe transaction {} to be signed ({}/{threshold}).",
+                safe_txn.hash,
+                signatures.len()
+            );
+        }
+        info!(
+            "Waiting for Safe transaction {} to be signed ({}/{threshold}) into {}.",
+            safe_txn.hash,
+            signatures.len(),
+            dir.display()
+        );
+        tokio::time::sleep(SAFE_SIGNATURE_POLL_INTERVAL).await;
+    };
+
+    safe.execTransaction(
+        safe_tx.to,
+        safe_tx.value,
+        safe_tx.data,
+        safe_tx.operation,
+        safe_tx.safeTxGas,
+        safe_tx.baseGas,
+        safe_tx.gasPrice,
+        safe_tx.gasToken,
+        safe_tx.refundReceiver,
+        encode_signatures(&signatures, threshold),
     )
     .transact("Safe::execTransaction", None)
     .await
     .context("Safe::execTransaction")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::memory::fixtures::install_retry_policy;
+    use crate::provider::memory::InMemoryChain;
+    use crate::provider::SyncProvider;
+    use alloy::primitives::b256;
+    use alloy::signers::local::PrivateKeySigner;
+    use kailua_contracts::Safe;
+
+    fn safe_txn() -> SafeTransaction {
+        SafeTransaction::new(
+            Address::repeat_byte(0x5a),
+            1,
+            Address::repeat_byte(0x01),
+            U256::ZERO,
+            Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+            U256::ZERO,
+            U256::from(7),
+        )
+    }
+
+    #[test]
+    fn test_safe_tx_hash() {
+        // SAFE_TX_TYPEHASH of Safe v1.4.0
+        assert_eq!(
+            SafeTx::eip712_type_hash(&safe_txn().safe_tx()),
+            b256!("0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8")
+        );
+
+        let mut txn = safe_txn();
+        txn.verify().unwrap();
+        txn.nonce += U256::from(1);
+        txn.verify().unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_safe_signatures() {
+        let dir = tempfile::tempdir().unwrap();
+        let txn = safe_txn();
+        let payload = txn.write(dir.path()).unwrap();
+        assert_eq!(SafeTransaction::read(&payload).unwrap(), txn);
+
+        let mut keys = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        keys.sort_by_key(|key| key.address());
+        for key in &keys {
+            let signature = txn.sign(&SignerBackend::Local(key.clone())).await.unwrap();
+            assert_eq!(signature.owner, key.address());
+            assert_eq!(signature.encode()[64], 27 + signature_parity(&signature));
+            signature
+                .write(&txn.signature_path(dir.path(), key.address()))
+                .unwrap();
+        }
+        // Tampered signatures are ignored
+        let mut forged =
+            OwnerSignature::read(&txn.signature_path(dir.path(), keys[0].address())).unwrap();
+        forged.owner = Address::repeat_byte(0x02);
+        forged
+            .write(&txn.signature_path(dir.path(), forged.owner))
+            .unwrap();
+
+        let signatures = txn.read_signatures(dir.path()).unwrap();
+        assert_eq!(signatures.len(), 2);
+
+        // Signatures are ordered by owner, with approvals encoding the owner
+        let approval = OwnerSignature::approved(txn.hash, Address::ZERO);
+        let mut all = signatures.clone();
+        all.push(approval.clone());
+        let encoded = encode_signatures(&all, 3);
+        assert_eq!(encoded.len(), 3 * 65);
+        assert_eq!(encoded[..65], approval.encode());
+        assert_eq!(encoded[64], 1);
+        assert_eq!(encode_signatures(&all, 2).len(), 2 * 65);
+
+        // eth_sign signatures are shifted by four
+        let key = &keys[0];
+        let eth_sign = OwnerSignature::recover(
+            txn.hash,
+            SafeSignature::EthSign(key.sign_message(txn.hash.as_slice()).await.unwrap()),
+        )
+        .unwrap();
+        assert_eq!(eth_sign.owner, key.address());
+        assert!(eth_sign.encode()[64] > 30);
+    }
+
+    #[tokio::test]
+    async fn test_exec_safe_txn_timeout() {
+        install_retry_policy();
+        let chain = InMemoryChain::new();
+        let provider = SyncProvider::in_memory(&chain);
+        let safe_address = Address::repeat_byte(0x5a);
+        let (owner, co_owner) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xa2));
+        let safe = Safe::new(safe_address, &provider.l1_provider);
+        let call = safe.approveHash(B256::ZERO);
+        let req = call.clone().into_transaction_request();
+        let safe_txn = SafeTransaction::new(
+            safe_address,
+            1,
+            safe_address,
+            U256::ZERO,
+            req.input().cloned().unwrap_or_default(),
+            U256::ZERO,
+            U256::from(7),
+        );
+        let safe_tx = safe_txn.safe_tx();
+        chain.mock_call(safe_address, Safe::nonceCall {}, U256::from(7));
+        chain.mock_call(
+            safe_address,
+            Safe::getTransactionHashCall::new((
+                safe_tx.to,
+                safe_tx.value,
+                safe_tx.data.clone(),
+                safe_tx.operation,
+                safe_tx.safeTxGas,
+                safe_tx.baseGas,
+                safe_tx.gasPrice,
+                safe_tx.gasToken,
+                safe_tx.refundReceiver,
+                safe_tx.nonce,
+            )),
+            safe_txn.hash,
+        );
+        chain.mock_call(safe_address, Safe::getThresholdCall {}, U256::from(2));
+        chain.mock_call(safe_address, Safe::getOwnersCall {}, vec![owner, co_owner]);
+        chain.mock_call(
+            safe_address,
+            Safe::approvedHashesCall::new((co_owner, safe_txn.hash)),
+            U256::ZERO,
+        );
+
+        // Missing signatures are awaited for at most the timeout
+        let dir = tempfile::tempdir().unwrap();
+        let err = exec_safe_txn(
+            call,
+            &safe,
+            owner,
+            &Some(dir.path().to_path_buf()),
+            Some(Duration::ZERO),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Timed out"));
+        assert!(safe_txn.payload_path(dir.path()).exists());
+    }
+
+    fn signature_parity(signature: &OwnerSignature) -> u8 {
+        match signature.signature {
+            SafeSignature::Typed(signature) | SafeSignature::EthSign(signature) => {
+                signature.v() as u8
+            }
+            SafeSignature::Approved => 0,
+        }
+    }
+}
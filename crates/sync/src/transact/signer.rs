@@ -12,18 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use alloy::network::EthereumWallet;
-use alloy::primitives::ChainId;
+use crate::transact::keystore::decrypt_keystore;
+use crate::transact::remote::RemoteSigner;
+use alloy::network::{EthereumWallet, TxSigner};
+use alloy::primitives::{Address, ChainId};
 use alloy::signers::{
     aws::AwsSigner,
     gcp::{GcpKeyRingRef, GcpSigner, KeySpecifier},
-    local::LocalSigner,
+    local::{LocalSigner, PrivateKeySigner},
+    Signer,
 };
 use anyhow::Context;
 use aws_config::BehaviorVersion;
 use gcloud_sdk::{
     google::cloud::kms::v1::key_management_service_client::KeyManagementServiceClient, GoogleApi,
 };
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[macro_export]
@@ -33,16 +37,16 @@ macro_rules! define_signer_args {
             #[derive(clap::Args, Debug, Clone, Default)]
             $vis struct $struct_name {
                 /// Wallet private key
-                #[clap(long, env, required_unless_present_any = [stringify!([<$prefix aws_key_id>]), stringify!([<$prefix google_keyring>])])]
+                #[clap(long, env, required_unless_present_any = [stringify!([<$prefix aws_key_id>]), stringify!([<$prefix google_keyring>]), stringify!([<$prefix remote_signer_url>]), stringify!([<$prefix keystore>])])]
                 pub [<$prefix key>]: Option<String>,
 
                 /// AWS KMS Key ID
-                #[clap(long, env, required_unless_present_any = [stringify!([<$prefix key>]), stringify!([<$prefix google_keyring>])])]
+                #[clap(long, env, required_unless_present_any = [stringify!([<$prefix key>]), stringify!([<$prefix google_keyring>]), stringify!([<$prefix remote_signer_url>]), stringify!([<$prefix keystore>])])]
                 pub [<$prefix aws_key_id>]: Option<String>,
 
                 /// GCP KMS Project ID
                 #[clap(long, env, requires = stringify!([<$prefix google_location>]))]
-                #[clap(required_unless_present_any = [stringify!([<$prefix key>]), stringify!([<$prefix aws_key_id>])])]
+                #[clap(required_unless_present_any = [stringify!([<$prefix key>]), stringify!([<$prefix aws_key_id>]), stringify!([<$prefix remote_signer_url>]), stringify!([<$prefix keystore>])])]
                 pub [<$prefix google_project_id>]: Option<String>,
                 /// GCP KMS Location
                 #[clap(long, env, requires = stringify!([<$prefix google_keyring>]))]
@@ -53,17 +57,39 @@ macro_rules! define_signer_args {
                 /// GCP KMS Key name
                 #[clap(long, env, requires = stringify!([<$prefix google_project_id>]))]
                 pub [<$prefix google_key_name>]: Option<String>,
+
+                /// Remote Signer JSON-RPC URL
+                #[clap(long, env, required_unless_present_any = [stringify!([<$prefix key>]), stringify!([<$prefix aws_key_id>]), stringify!([<$prefix google_keyring>]), stringify!([<$prefix keystore>])])]
+                pub [<$prefix remote_signer_url>]: Option<String>,
+                /// Remote Signer Account (defaults to the first account)
+                #[clap(long, env, requires = stringify!([<$prefix remote_signer_url>]))]
+                pub [<$prefix remote_signer_address>]: Option<Address>,
+
+                /// Encrypted Keystore File
+                #[clap(long, env, required_unless_present_any = [stringify!([<$prefix key>]), stringify!([<$prefix aws_key_id>]), stringify!([<$prefix google_keyring>]), stringify!([<$prefix remote_signer_url>])])]
+                pub [<$prefix keystore>]: Option<PathBuf>,
+                /// Keystore Password File (prompts for the password if unspecified)
+                #[clap(long, env, requires = stringify!([<$prefix keystore>]))]
+                pub [<$prefix keystore_password_file>]: Option<PathBuf>,
             }
 
             impl $struct_name {
                 pub async fn wallet(&self, chain_id: Option<ChainId>) -> anyhow::Result<EthereumWallet> {
-                    args_to_wallet(
+                    Ok(self.signer(chain_id).await?.into_wallet())
+                }
+
+                pub async fn signer(&self, chain_id: Option<ChainId>) -> anyhow::Result<SignerBackend> {
+                    args_to_signer(
                         &self.[<$prefix key>],
                         &self.[<$prefix aws_key_id>],
                         &self.[<$prefix google_project_id>],
                         &self.[<$prefix google_location>],
                         &self.[<$prefix google_keyring>],
                         &self.[<$prefix google_key_name>],
+                        &self.[<$prefix remote_signer_url>],
+                        &self.[<$prefix remote_signer_address>],
+                        &self.[<$prefix keystore>],
+                        &self.[<$prefix keystore_password_file>],
                         chain_id
                     ).await
                 }
@@ -87,25 +113,67 @@ define_signer_args!(pub GuardianSignerArgs, guardian_);
 define_signer_args!(pub ProposerSignerArgs, proposer_);
 define_signer_args!(pub ValidatorSignerArgs, validator_);
 
-pub async fn args_to_wallet(
+/// A signer instantiated from any of the supported backends
+#[derive(Clone, Debug)]
+pub enum SignerBackend {
+    Local(PrivateKeySigner),
+    Remote(RemoteSigner),
+    Aws(AwsSigner),
+    Gcp(GcpSigner),
+}
+
+impl SignerBackend {
+    pub fn address(&self) -> Address {
+        match self {
+            SignerBackend::Local(signer) => signer.address(),
+            SignerBackend::Remote(signer) => TxSigner::address(signer),
+            SignerBackend::Aws(signer) => Signer::address(signer),
+            SignerBackend::Gcp(signer) => Signer::address(signer),
+        }
+    }
+
+    pub fn into_wallet(self) -> EthereumWallet {
+        match self {
+            SignerBackend::Local(signer) => EthereumWallet::from(signer),
+            SignerBackend::Remote(signer) => EthereumWallet::from(signer),
+            SignerBackend::Aws(signer) => EthereumWallet::from(signer),
+            SignerBackend::Gcp(signer) => EthereumWallet::from(signer),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn args_to_signer(
     key: &Option<String>,
     aws_key_id: &Option<String>,
     google_project_id: &Option<String>,
     google_location: &Option<String>,
     google_keyring: &Option<String>,
     google_key_name: &Option<String>,
+    remote_signer_url: &Option<String>,
+    remote_signer_address: &Option<Address>,
+    keystore: &Option<PathBuf>,
+    keystore_password_file: &Option<PathBuf>,
     chain_id: Option<ChainId>,
-) -> anyhow::Result<EthereumWallet> {
+) -> anyhow::Result<SignerBackend> {
     if let Some(key) = key {
         let local_signer = LocalSigner::from_str(key)?;
-        return Ok(EthereumWallet::from(local_signer));
+        return Ok(SignerBackend::Local(local_signer));
+    } else if let Some(url) = remote_signer_url {
+        let signer = RemoteSigner::connect(url, *remote_signer_address, chain_id)
+            .await
+            .context("RemoteSigner::connect")?;
+        return Ok(SignerBackend::Remote(signer));
+    } else if let Some(keystore) = keystore {
+        let local_signer = decrypt_keystore(keystore, keystore_password_file)?;
+        return Ok(SignerBackend::Local(local_signer));
     } else if let Some(key_id) = aws_key_id {
         let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
         let client = aws_sdk_kms::Client::new(&config);
         let signer = AwsSigner::new(client, key_id.clone(), chain_id)
             .await
             .context("AwsSigner::new")?;
-        return Ok(EthereumWallet::from(signer));
+        return Ok(SignerBackend::Aws(signer));
     }
 
     let project_id = google_project_id.clone().unwrap();
@@ -126,5 +194,5 @@ pub async fn args_to_wallet(
         .await
         .context("GcpSigner::new")?;
 
-    Ok(EthereumWallet::from(signer))
+    Ok(SignerBackend::Gcp(signer))
 }
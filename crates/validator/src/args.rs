@@ -47,6 +47,9 @@ pub struct ValidateArgs {
     /// The maximum amount of seconds to wait before starting to compute a validity proof. (Default 0)
     #[clap(long, env, default_value_t = 0)]
     pub max_validity_proving_delay: u64,
+    /// The number of seconds between checks for claimable elimination bonds, or 0 to disable collection. (Default 600)
+    #[clap(long, env, default_value_t = 600)]
+    pub bond_collection_interval: u64,
 
     /// The number of l1 heads to jump back when initially proving
     #[cfg(feature = "devnet")]
@@ -0,0 +1,306 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::args::ValidateArgs;
+use crate::proposals::dispatch::current_time;
+use alloy::network::ReceiptResponse;
+use alloy::primitives::{Address, U256};
+use alloy::providers::Provider;
+use anyhow::Context;
+use kailua_contracts::*;
+use kailua_sync::agent::SyncAgent;
+use kailua_sync::await_tel;
+use kailua_sync::provider::failover::is_endpoint_error;
+use kailua_sync::stall::Stall;
+use kailua_sync::transact::dry_run::is_dry_run;
+use kailua_sync::transact::policy::Urgency;
+use kailua_sync::transact::Transact;
+use opentelemetry::global::tracer;
+use opentelemetry::metrics::{Counter, Gauge};
+use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
+use opentelemetry::KeyValue;
+use std::collections::BTreeMap;
+use std::future::IntoFuture;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// The maximum number of elimination bonds to claim in a single transaction
+pub const BOND_CLAIMS_LIMIT: usize = 16;
+
+/// Tracks the elimination bonds owed by the treasuries to the validator's payout addresses
+#[derive(Clone, Debug, Default)]
+pub struct BondCollector {
+    /// The proposers eliminated by each payee in each treasury, in the order they are paid out
+    pub eliminations: BTreeMap<(Address, Address), Vec<Address>>,
+    /// Unix timestamp in seconds of the next check for claimable bonds
+    pub next_check: u64,
+}
+
+impl BondCollector {
+    /// Reads any new eliminations credited to the payee from the treasury.
+    pub async fn sync<P: Provider>(
+        &mut self,
+        treasury: &KailuaTreasury::KailuaTreasuryInstance<P>,
+        payee: Address,
+    ) -> anyhow::Result<&[Address]> {
+        let tracer = tracer("kailua");
+        let context =
+            opentelemetry::Context::current_with_span(tracer.start("BondCollector::sync"));
+
+        let eliminations = self
+            .eliminations
+            .entry((*treasury.address(), payee))
+            .or_default();
+        // The treasury does not expose the length of the list, so read until the call reverts
+        loop {
+            match await_tel!(
+                context,
+                tracer,
+                "KailuaTreasury::eliminations",
+                treasury
+                    .eliminations(payee, U256::from(eliminations.len()))
+                    .call()
+                    .into_future()
+            ) {
+                Ok(eliminated) => eliminations.push(eliminated),
+                Err(err) if is_reverted(&err) => break,
+                Err(err) => return Err(err).context("KailuaTreasury::eliminations"),
+            }
+        }
+        Ok(eliminations)
+    }
+}
+
+/// Returns whether the call failed because it reverted rather than because of the endpoint.
+pub fn is_reverted(err: &alloy::contract::Error) -> bool {
+    match err {
+        alloy::contract::Error::TransportError(err) => err
+            .as_error_resp()
+            .is_some_and(|payload| !is_endpoint_error(payload)),
+        _ => false,
+    }
+}
+
+/// Claims the elimination bonds the validator has won, and reports those won by its payout recipient.
+#[allow(clippy::too_many_arguments)]
+pub async fn collect_elimination_bonds<P: Provider>(
+    args: &ValidateArgs,
+    agent: &SyncAgent,
+    bond_collector: &mut BondCollector,
+    meter_bonds_claimable: &Gauge<u64>,
+    meter_bonds_collected: &Counter<u64>,
+    meter_bonds_fail: &Counter<u64>,
+    validator_address: Address,
+    payout_recipient: Address,
+    validator_provider: &P,
+) -> anyhow::Result<()> {
+    let tracer = tracer("kailua");
+    let context =
+        opentelemetry::Context::current_with_span(tracer.start("collect_elimination_bonds"));
+
+    if args.bond_collection_interval == 0 {
+        return Ok(());
+    }
+    let current_timestamp = current_time();
+    if current_timestamp < bond_collector.next_check {
+        return Ok(());
+    }
+    bond_collector.next_check = current_timestamp + args.bond_collection_interval;
+
+    let mut payees = vec![validator_address];
+    if payout_recipient != validator_address {
+        payees.push(payout_recipient);
+    }
+    // Bonds owed by the treasuries of earlier deployments remain claimable after a handover
+    for treasury_address in agent.known_treasuries() {
+        let treasury = KailuaTreasury::new(treasury_address, validator_provider);
+        for &payee in &payees {
+            let eliminations = match bond_collector.sync(&treasury, payee).await {
+                Ok(eliminations) => eliminations.to_vec(),
+                Err(err) => {
+                    warn!("Failed to read eliminations credited to {payee}: {err:?}");
+                    // Retry on the next iteration
+                    bond_collector.next_check = current_timestamp;
+                    continue;
+                }
+            };
+            let paid: usize = treasury
+                .eliminationsPaid(payee)
+                .stall_with_context(context.clone(), "KailuaTreasury::eliminationsPaid")
+                .await?
+                .to();
+            let mut unpaid = vec![];
+            for eliminated in eliminations.into_iter().skip(paid) {
+                let bond = treasury
+                    .paidBonds(eliminated)
+                    .stall_with_context(context.clone(), "KailuaTreasury::paidBonds")
+                    .await?;
+                unpaid.push((eliminated, bond));
+            }
+            let claimable = unpaid
+                .iter()
+                .fold(U256::ZERO, |total, (_, bond)| total + bond);
+            meter_bonds_claimable.record(
+                to_gwei(claimable),
+                &[
+                    KeyValue::new("treasury", treasury_address.to_string()),
+                    KeyValue::new("payee", payee.to_string()),
+                ],
+            );
+            if claimable.is_zero() {
+                continue;
+            }
+            info!(
+                "{claimable} wei in {} elimination bonds are claimable by {payee}.",
+                unpaid.len()
+            );
+            // Only the payee itself can claim its bonds
+            if payee != validator_address {
+                warn!("Elimination bonds of payout recipient {payee} must be claimed by its owner using claimEliminationBonds.");
+                continue;
+            }
+
+            let claims = unpaid.len().min(BOND_CLAIMS_LIMIT);
+            let claimed = unpaid[..claims]
+                .iter()
+                .fold(U256::ZERO, |total, (_, bond)| total + bond);
+            if claimed.is_zero() {
+                // Skip over the eliminations of proposers that never paid in a bond
+                info!("Skipping {claims} empty elimination bonds.");
+            } else {
+                info!("Claiming {claimed} wei in {claims} elimination bonds.");
+            }
+            match treasury
+                .claimEliminationBonds(U256::from(claims))
+                .timed_transact_with_urgency(
+                    context.clone(),
+                    "KailuaTreasury::claimEliminationBonds",
+                    Some(Duration::from_secs(args.txn_args.txn_timeout)),
+                    Urgency::Deferrable,
+                )
+                .await
+                .context("KailuaTreasury::claimEliminationBonds")
+            {
+                Ok(receipt) if ReceiptResponse::status(&receipt) => {
+                    info!("Elimination bonds claimed: {:?}", receipt.transaction_hash);
+                    meter_bonds_collected.add(
+                        to_gwei(claimed),
+                        &[
+                            KeyValue::new("treasury", treasury_address.to_string()),
+                            KeyValue::new("txn_hash", receipt.transaction_hash.to_string()),
+                            KeyValue::new("claims", claims.to_string()),
+                        ],
+                    );
+                    // Claim any remaining bonds on the next iteration
+                    if claims < unpaid.len() {
+                        bond_collector.next_check = current_timestamp;
+                    }
+                }
+                Ok(receipt) => {
+                    error!(
+                        "KailuaTreasury::claimEliminationBonds reverted: {:?}",
+                        receipt.transaction_hash
+                    );
+                    meter_bonds_fail.add(1, &[KeyValue::new("msg", "reverted")]);
+                }
+                Err(err) if is_dry_run(&err) => {}
+                Err(err) => {
+                    error!("Failed to claim elimination bonds: {err:?}");
+                    meter_bonds_fail.add(1, &[KeyValue::new("msg", err.to_string())]);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts the amount of wei to gwei for reporting through u64 metrics.
+pub fn to_gwei(wei: U256) -> u64 {
+    (wei / U256::from(1_000_000_000u64)).saturating_to()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+    use alloy::rpc::json_rpc::ErrorPayload;
+    use alloy::transports::{RpcError, TransportErrorKind};
+    use kailua_sync::provider::memory::fixtures::*;
+    use kailua_sync::provider::memory::InMemoryChain;
+    use kailua_sync::provider::SyncProvider;
+
+    const OLD_TREASURY: Address = address!("0x0000000000000000000000000000000000000005");
+    const PAYEE: Address = address!("0x00000000000000000000000000000000000000b1");
+
+    fn script_elimination(chain: &InMemoryChain, treasury: Address, i: u64, eliminated: Address) {
+        chain.mock_call(
+            treasury,
+            KailuaTreasury::eliminationsCall::new((PAYEE, U256::from(i))),
+            eliminated,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bond_collector_sync() {
+        install_retry_policy();
+        let chain = InMemoryChain::new();
+        let provider = SyncProvider::in_memory(&chain);
+        let treasury = KailuaTreasury::new(TREASURY, &provider.l1_provider);
+        let old_treasury = KailuaTreasury::new(OLD_TREASURY, &provider.l1_provider);
+        script_elimination(&chain, TREASURY, 0, FAULTY_PROPOSER);
+        script_elimination(&chain, OLD_TREASURY, 0, HONEST_PROPOSER);
+        let mut collector = BondCollector::default();
+
+        // eliminations are read until the call reverts
+        assert_eq!(
+            collector.sync(&treasury, PAYEE).await.unwrap(),
+            &[FAULTY_PROPOSER]
+        );
+        // eliminations owed by other treasuries are tracked separately
+        assert_eq!(
+            collector.sync(&old_treasury, PAYEE).await.unwrap(),
+            &[HONEST_PROPOSER]
+        );
+        script_elimination(&chain, TREASURY, 1, HONEST_PROPOSER);
+        assert_eq!(
+            collector.sync(&treasury, PAYEE).await.unwrap(),
+            &[FAULTY_PROPOSER, HONEST_PROPOSER]
+        );
+        assert_eq!(
+            collector.eliminations[&(OLD_TREASURY, PAYEE)],
+            vec![HONEST_PROPOSER]
+        );
+    }
+
+    #[test]
+    fn test_is_reverted() {
+        let error = |code: i64, message: &'static str| {
+            alloy::contract::Error::TransportError(RpcError::ErrorResp(ErrorPayload {
+                code,
+                message: message.into(),
+                data: None,
+            }))
+        };
+        assert!(is_reverted(&error(3, "execution reverted")));
+        assert!(is_reverted(&error(
+            -32000,
+            "execution reverted: custom error"
+        )));
+        // endpoint failures do not mark the end of the list
+        assert!(!is_reverted(&error(-32603, "internal error")));
+        assert!(!is_reverted(&alloy::contract::Error::TransportError(
+            TransportErrorKind::custom_str("connection refused")
+        )));
+    }
+}
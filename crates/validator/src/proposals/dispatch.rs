@@ -37,7 +37,7 @@ pub async fn dispatch_proof_requests(
     last_proof_l1_head: &mut BTreeMap<u64, u64>,
     channel: &mut DuplexChannel<Message>,
     is_fault: bool,
-) {
+) -> anyhow::Result<()> {
     let tracer = tracer("kailua");
     let context =
         opentelemetry::Context::current_with_span(tracer.start("dispatch_proof_requests"));
@@ -90,7 +90,7 @@ pub async fn dispatch_proof_requests(
         let proof_status = parent_contract
             .proofStatus(proposal.signature)
             .stall_with_context(context.clone(), "KailuaTournament::proofStatus")
-            .await;
+            .await?;
         if proof_status != 0 {
             info!(
                 "Proposal {} signature {} already proven {proof_status}",
@@ -130,6 +130,7 @@ pub async fn dispatch_proof_requests(
             );
         }
     }
+    Ok(())
 }
 
 pub fn current_time() -> u64 {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod bonds;
 #[cfg(feature = "devnet")]
 pub mod devnet;
 pub mod dispatch;
@@ -27,6 +28,7 @@ use alloy::primitives::B256;
 use anyhow::{bail, Context};
 use kailua_sync::agent::{SyncAgent, FINAL_L2_BLOCK_RESOLVED};
 use kailua_sync::proposal::Proposal;
+use kailua_sync::transact::nonce::nonce_tracker;
 use kailua_sync::transact::provider::SafeProvider;
 use kailua_sync::{await_tel, await_tel_res};
 use opentelemetry::global::{meter, tracer};
@@ -58,6 +60,9 @@ pub async fn handle_proposals(
     let meter_proofs_published = meter.u64_counter("validator.proofs.published").build();
     let meter_proofs_fail = meter.u64_counter("validator.proofs.errs").build();
     let meter_proofs_discarded = meter.u64_counter("validator.proofs.discarded").build();
+    let meter_bonds_claimable = meter.u64_gauge("validator.bonds.claimable").build();
+    let meter_bonds_collected = meter.u64_counter("validator.bonds.collected").build();
+    let meter_bonds_fail = meter.u64_counter("validator.bonds.errs").build();
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("handle_proposals"));
 
@@ -85,9 +90,23 @@ pub async fn handle_proposals(
         args.txn_args
             .premium_provider::<Ethereum>()
             .wallet(validator_wallet)
-            .connect_http(args.sync.provider.eth_rpc_url.as_str().try_into()?),
+            .connect_client(args.sync.provider.eth_rpc_client()?),
     );
     info!("Validator address: {validator_address}");
+    let payout_recipient = args
+        .proving
+        .payout_recipient_address
+        .unwrap_or(validator_address);
+    await_tel_res!(
+        context,
+        tracer,
+        "NonceTracker::reconcile",
+        nonce_tracker().reconcile(
+            &validator_provider,
+            validator_address,
+            args.txn_args.cancel_stale_txns
+        )
+    )?;
 
     // Run the validator loop
     info!(
@@ -100,6 +119,7 @@ pub async fn handle_proposals(
     let mut trail_fault_buffer = BinaryHeap::new();
     let mut proposal_validity_buffer = BinaryHeap::new();
     let mut last_proof_l1_head = BTreeMap::new();
+    let mut bond_collector = bonds::BondCollector::default();
     loop {
         // Wait for new data on every iteration
         sleep(Duration::from_secs(1)).await;
@@ -140,7 +160,8 @@ pub async fn handle_proposals(
             &mut output_fault_buffer,
             &mut trail_fault_buffer,
         )
-        .await;
+        .await
+        .context("process_proposals")?;
 
         // dispatch buffered output fault proof requests
         dispatch::dispatch_proof_requests(
@@ -153,7 +174,8 @@ pub async fn handle_proposals(
             &mut channel,
             true,
         )
-        .await;
+        .await
+        .context("dispatch_proof_requests")?;
 
         // dispatch buffered validity proof requests
         dispatch::dispatch_proof_requests(
@@ -166,7 +188,8 @@ pub async fn handle_proposals(
             &mut channel,
             false,
         )
-        .await;
+        .await
+        .context("dispatch_proof_requests")?;
 
         // publish proofs with receipts on chain
         receipts::publish_receipt_proofs(
@@ -182,7 +205,8 @@ pub async fn handle_proposals(
             &mut channel,
             &validator_provider,
         )
-        .await;
+        .await
+        .context("publish_receipt_proofs")?;
 
         // publish trail fault proofs
         trails::publish_trail_proofs(
@@ -195,7 +219,23 @@ pub async fn handle_proposals(
             validator_address,
             &validator_provider,
         )
-        .await;
+        .await
+        .context("publish_trail_proofs")?;
+
+        // collect the bonds of eliminated proposers
+        bonds::collect_elimination_bonds(
+            &args,
+            &agent,
+            &mut bond_collector,
+            &meter_bonds_claimable,
+            &meter_bonds_collected,
+            &meter_bonds_fail,
+            validator_address,
+            payout_recipient,
+            &validator_provider,
+        )
+        .await
+        .context("collect_elimination_bonds")?;
     }
 }
 
@@ -42,7 +42,7 @@ pub async fn process_proposals(
     proposal_validity_buffer: &mut BinaryHeap<(Reverse<u64>, u64)>,
     output_fault_buffer: &mut BinaryHeap<(Reverse<u64>, u64)>,
     trail_fault_buffer: &mut BinaryHeap<(Reverse<u64>, u64)>,
-) {
+) -> anyhow::Result<()> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("process_proposals"));
 
@@ -117,7 +117,7 @@ pub async fn process_proposals(
         let is_validity_proven = await_tel!(
             context,
             parent.fetch_is_successor_validity_proven(&agent.provider.l1_provider)
-        );
+        )?;
         if is_validity_proven {
             info!(
                 "Validity proof settling all disputes in tournament {} already submitted",
@@ -271,7 +271,7 @@ pub async fn process_proposals(
         let proof_status = parent_contract
             .proofStatus(proposal.signature)
             .stall_with_context(context.clone(), "KailuaTournament::proofStatus")
-            .await;
+            .await?;
         if proof_status != 0 {
             info!(
                 "Proposal {} signature {} already proven {proof_status}",
@@ -312,6 +312,7 @@ pub async fn process_proposals(
             trail_fault_buffer.push((random_wait, *proposal_index));
         }
     }
+    Ok(())
 }
 
 pub fn random_processing_time(max_seconds: u64) -> Reverse<u64> {
@@ -322,3 +323,124 @@ pub fn random_processing_time(max_seconds: u64) -> Reverse<u64> {
         .as_secs();
     Reverse(current_time + random_wait)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::B256;
+    use clap::Parser;
+    use kailua_sync::provider::memory::fixtures::*;
+    use kailua_sync::provider::memory::ScriptedProposal;
+    use opentelemetry::global::meter;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[clap(flatten)]
+        args: ValidateArgs,
+    }
+
+    fn validate_args(fast_forward_target: u64) -> ValidateArgs {
+        let mut args = Cli::parse_from([
+            "kailua-validator",
+            "--op-node-url=http://localhost:7545",
+            "--op-geth-url=http://localhost:8545",
+            "--eth-rpc-url=http://localhost:9545",
+            "--beacon-rpc-url=http://localhost:5052",
+            "--validator-key=0x92db14e403b83dfe3df233f83dfa3a0d7096f21ca9b0d6d6b8d88b2b4ec1564e",
+        ])
+        .args;
+        args.fast_forward_target = fast_forward_target;
+        args
+    }
+
+    async fn process(
+        args: &ValidateArgs,
+        agent: &mut SyncAgent,
+        loaded_proposals: &[u64],
+    ) -> (Vec<u64>, Vec<u64>, Vec<u64>) {
+        let meter = meter("kailua");
+        let counter = meter.u64_counter("test.count").build();
+        let gauge = meter.u64_gauge("test.latest").build();
+        let mut proposal_validity_buffer = BinaryHeap::new();
+        let mut output_fault_buffer = BinaryHeap::new();
+        let mut trail_fault_buffer = BinaryHeap::new();
+        process_proposals(
+            args,
+            agent,
+            loaded_proposals,
+            &counter,
+            &gauge,
+            &counter,
+            &gauge,
+            &counter,
+            &gauge,
+            &mut proposal_validity_buffer,
+            &mut output_fault_buffer,
+            &mut trail_fault_buffer,
+        )
+        .await
+        .unwrap();
+        let indices = |buffer: BinaryHeap<(Reverse<u64>, u64)>| {
+            let mut indices = buffer.into_iter().map(|(_, i)| i).collect::<Vec<_>>();
+            indices.sort();
+            indices
+        };
+        (
+            indices(proposal_validity_buffer),
+            indices(output_fault_buffer),
+            indices(trail_fault_buffer),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_process_proposals() {
+        let deployment = deployment();
+        let chain = anchored_chain(&deployment);
+        let honest_proposal = game_proposal(&chain, &deployment, HONEST_GAME, 1, HONEST_PROPOSER);
+        chain
+            .script_proposal(&deployment, &honest_proposal)
+            .unwrap();
+        let faulty_proposal = ScriptedProposal {
+            output_root: B256::repeat_byte(0xff),
+            ..game_proposal(&chain, &deployment, FAULTY_GAME, 2, FAULTY_PROPOSER)
+        };
+        chain
+            .script_proposal(&deployment, &faulty_proposal)
+            .unwrap();
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut agent = sync_agent(&chain, data_dir.path(), None).await;
+        let loaded_proposals = agent.sync(0, None).await.unwrap();
+        chain.mock_call(
+            ANCHOR,
+            KailuaTournament::proofStatusCall::new((agent.proposals[&2].signature,)),
+            0u8,
+        );
+
+        // only the faulty proposal is challenged, at its diverging output
+        let (validity, output_faults, trail_faults) =
+            process(&validate_args(0), &mut agent, &loaded_proposals).await;
+        assert!(validity.is_empty());
+        assert_eq!(output_faults, vec![2]);
+        assert!(trail_faults.is_empty());
+
+        // fast-forwarding proves the validity of the canonical proposal instead
+        let (validity, output_faults, trail_faults) =
+            process(&validate_args(4), &mut agent, &loaded_proposals).await;
+        assert_eq!(validity, vec![1]);
+        assert!(output_faults.is_empty());
+        assert!(trail_faults.is_empty());
+
+        // proven faults are not challenged again
+        chain.mock_call(
+            ANCHOR,
+            KailuaTournament::proofStatusCall::new((agent.proposals[&2].signature,)),
+            1u8,
+        );
+        let (validity, output_faults, trail_faults) =
+            process(&validate_args(0), &mut agent, &loaded_proposals).await;
+        assert!(validity.is_empty());
+        assert!(output_faults.is_empty());
+        assert!(trail_faults.is_empty());
+    }
+}
@@ -26,6 +26,8 @@ use kailua_kona::journal::ProofJournal;
 use kailua_kona::precondition::validity_precondition_hash;
 use kailua_sync::agent::SyncAgent;
 use kailua_sync::stall::Stall;
+use kailua_sync::transact::dry_run::is_dry_run;
+use kailua_sync::transact::policy::Urgency;
 use kailua_sync::transact::Transact;
 use kailua_sync::{await_tel, retry_res_ctx_timeout};
 use opentelemetry::global::tracer;
@@ -51,7 +53,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
     meter_proofs_fail: &Counter<u64>,
     channel: &mut DuplexChannel<Message>,
     validator_provider: &P,
-) {
+) -> anyhow::Result<()> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("publish_receipt_proofs"));
 
@@ -101,7 +103,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
         if await_tel!(
             context,
             parent.fetch_is_successor_validity_proven(&agent.provider.l1_provider)
-        ) {
+        )? {
             info!(
                 "Skipping proof submission in tournament {} with validity proof.",
                 parent.index
@@ -119,7 +121,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
         let expected_fpvm_image_id = parent_contract
             .FPVM_IMAGE_ID()
             .stall_with_context(context.clone(), "KailuaTournament::FPVM_IMAGE_ID")
-            .await
+            .await?
             .0;
 
         // advance l1 head if insufficient data
@@ -190,7 +192,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
                 let contract_blobs_hash = proposal_contract
                     .blobsHash()
                     .stall_with_context(context.clone(), "KailuaGame::blobsHash")
-                    .await;
+                    .await?;
                 if proposal.blobs_hash() != contract_blobs_hash {
                     warn!(
                         "Local proposal blobs hash {} doesn't match contract blobs hash {}",
@@ -217,7 +219,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
                 let config_hash = proposal_contract
                     .ROLLUP_CONFIG_HASH()
                     .stall_with_context(context.clone(), "KailuaGame::ROLLUP_CONFIG_HASH")
-                    .await;
+                    .await?;
                 if proof_journal.config_hash != config_hash {
                     warn!(
                         "Proof config hash {} does not match contract hash {config_hash}",
@@ -254,10 +256,11 @@ pub async fn publish_receipt_proofs<P: Provider>(
                     child_index,
                     encoded_seal.clone(),
                 )
-                .timed_transact_with_context(
+                .timed_transact_with_urgency(
                     context.clone(),
                     "KailuaTournament::proveValidity",
                     Some(Duration::from_secs(args.txn_args.txn_timeout)),
+                    Urgency::Critical,
                 )
                 .await
                 .context("KailuaTournament::proveValidity")
@@ -267,7 +270,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
                     let proof_status = parent_contract
                         .provenAt(proposal.signature)
                         .stall_with_context(context.clone(), "KailuaTournament::provenAt")
-                        .await;
+                        .await?;
                     info!("Validity proof timestamp: {proof_status}");
                     info!("KailuaTournament::proveValidity: {} gas", receipt.gas_used);
 
@@ -293,6 +296,8 @@ pub async fn publish_receipt_proofs<P: Provider>(
                         ],
                     );
                 }
+                // Simulated proofs are not resubmitted
+                Err(e) if is_dry_run(&e) => {}
                 Err(e) => {
                     error!("Failed to confirm validity proof txn: {e:?}");
                     meter_proofs_fail.add(
@@ -345,13 +350,12 @@ pub async fn publish_receipt_proofs<P: Provider>(
                 tracer,
                 "op_node_output",
                 retry_res_ctx_timeout!(
-                    agent
+                    OpNode => agent
                         .provider
-                        .op_provider
                         .output_at_block(proof_journal.claimed_l2_block_number)
                         .await
                 )
-            );
+            )?;
             if proof_journal.claimed_l2_output_root != op_node_output {
                 error!(
                     "Local op node output {op_node_output} doesn't match proof {}",
@@ -380,7 +384,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
         let fault_proof_status = parent_contract
             .proofStatus(proposal.signature)
             .stall_with_context(context.clone(), "KailuaTournament::proofStatus")
-            .await;
+            .await?;
         if fault_proof_status != 0 {
             warn!("Skipping proof submission for already proven game at local index {proposal_index}.");
             meter_proofs_discarded.add(
@@ -446,7 +450,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
                         proofs.last().unwrap().clone(),
                     )
                     .stall_with_context(context.clone(), "KailuaGame::verifyIntermediateOutput")
-                    .await;
+                    .await?;
                 if !proposal_has_output {
                     warn!("Could not verify proposed output");
                 } else {
@@ -479,7 +483,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
                         proofs.first().unwrap().clone(),
                     )
                     .stall_with_context(context.clone(), "KailuaGame::verifyIntermediateOutput")
-                    .await;
+                    .await?;
                 if !proposal_has_output {
                     warn!("Could not verify last common output for proposal");
                 } else {
@@ -513,7 +517,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
             let config_hash = parent_contract
                 .ROLLUP_CONFIG_HASH()
                 .stall_with_context(context.clone(), "KailuaTournament::ROLLUP_CONFIG_HASH")
-                .await;
+                .await?;
             if proof_journal.config_hash != config_hash {
                 warn!(
                     "Config hash mismatch. Found {}, expected {config_hash}.",
@@ -543,10 +547,11 @@ pub async fn publish_receipt_proofs<P: Provider>(
                 output_fe,
                 [commitments, proofs],
             )
-            .timed_transact_with_context(
+            .timed_transact_with_urgency(
                 context.clone(),
                 "KailuaTournament::proveOutputFault",
                 Some(Duration::from_secs(args.txn_args.txn_timeout)),
+                Urgency::Critical,
             )
             .await
             .context("KailuaTournament::proveOutputFault");
@@ -557,7 +562,7 @@ pub async fn publish_receipt_proofs<P: Provider>(
                 let proof_status = parent_contract
                     .proofStatus(proposal.signature)
                     .stall_with_context(context.clone(), "KailuaTournament::proofStatus")
-                    .await;
+                    .await?;
                 info!("Proposal {} proven: {proof_status}", proposal.index);
                 info!(
                     "KailuaTournament::proveOutputFault: {} gas",
@@ -586,6 +591,8 @@ pub async fn publish_receipt_proofs<P: Provider>(
                     ],
                 );
             }
+            // Simulated proofs are not resubmitted
+            Err(e) if is_dry_run(&e) => {}
             Err(e) => {
                 error!("Failed to confirm fault proof txn: {e:?}");
                 meter_proofs_fail.add(
@@ -601,4 +608,5 @@ pub async fn publish_receipt_proofs<P: Provider>(
             }
         }
     }
+    Ok(())
 }
@@ -20,6 +20,8 @@ use anyhow::Context;
 use kailua_contracts::*;
 use kailua_sync::agent::SyncAgent;
 use kailua_sync::stall::Stall;
+use kailua_sync::transact::dry_run::is_dry_run;
+use kailua_sync::transact::policy::Urgency;
 use kailua_sync::transact::Transact;
 use opentelemetry::global::tracer;
 use opentelemetry::metrics::Counter;
@@ -40,7 +42,7 @@ pub async fn publish_trail_proofs<P: Provider>(
     meter_proofs_fail: &Counter<u64>,
     validator_address: Address,
     validator_provider: &P,
-) {
+) -> anyhow::Result<()> {
     let tracer = tracer("kailua");
     let context = opentelemetry::Context::current_with_span(tracer.start("publish_trail_proofs"));
 
@@ -116,7 +118,7 @@ pub async fn publish_trail_proofs<P: Provider>(
         let fault_proof_status = parent_contract
             .proofStatus(proposal.signature)
             .stall_with_context(context.clone(), "KailuaTournament::proofStatus")
-            .await;
+            .await?;
         if fault_proof_status != 0 {
             warn!("Skipping proof submission for already proven game at local index {proposal_index}.");
             meter_proofs_discarded.add(
@@ -151,7 +153,7 @@ pub async fn publish_trail_proofs<P: Provider>(
                     kzg_proof.clone(),
                 )
                 .stall_with_context(context.clone(), "KailuaGame::verifyIntermediateOutput")
-                .await
+                .await?
             {
                 warn!("Could not verify divergent trail output for proposal");
             } else {
@@ -177,10 +179,11 @@ pub async fn publish_trail_proofs<P: Provider>(
                 blob_commitment,
                 kzg_proof,
             )
-            .timed_transact_with_context(
+            .timed_transact_with_urgency(
                 context.clone(),
                 "KailuaTournament::proveTrailFault",
                 Some(Duration::from_secs(args.txn_args.txn_timeout)),
+                Urgency::Critical,
             )
             .await
             .context("KailuaTournament::proveTrailFault");
@@ -191,7 +194,7 @@ pub async fn publish_trail_proofs<P: Provider>(
                 let proof_status = parent_contract
                     .proofStatus(proposal.signature)
                     .stall_with_context(context.clone(), "KailuaTournament::proofStatus")
-                    .await;
+                    .await?;
                 info!("Proposal {} proven: {proof_status}", proposal.index);
                 info!(
                     "KailuaTournament::proveTrailFault: {} gas",
@@ -220,6 +223,8 @@ pub async fn publish_trail_proofs<P: Provider>(
                     ],
                 );
             }
+            // Simulated proofs are not resubmitted
+            Err(e) if is_dry_run(&e) => {}
             Err(e) => {
                 error!("Failed to confirm fault proof txn: {e:?}");
                 meter_proofs_fail.add(
@@ -235,4 +240,5 @@ pub async fn publish_trail_proofs<P: Provider>(
             }
         }
     }
+    Ok(())
 }
@@ -64,17 +64,18 @@ pub async fn handle_proof_requests(
     let raw_image_id = args.proving.image_id();
     let fpvm_image_id = B256::from(bytemuck::cast::<[u32; 8], [u8; 32]>(raw_image_id));
     // Set payout recipient
-    let validator_wallet = await_tel_res!(
-        context,
-        tracer,
-        "ValidatorSigner::wallet",
-        args.validator_signer
-            .wallet(Some(rollup_config.l1_chain_id))
-    )?;
-    let payout_recipient = args
-        .proving
-        .payout_recipient_address
-        .unwrap_or_else(|| validator_wallet.default_signer().address());
+    let payout_recipient = match args.proving.payout_recipient_address {
+        Some(payout_recipient) => payout_recipient,
+        None => await_tel_res!(
+            context,
+            tracer,
+            "ValidatorSigner::wallet",
+            args.validator_signer
+                .wallet(Some(rollup_config.l1_chain_id))
+        )?
+        .default_signer()
+        .address(),
+    };
     info!("Proof payout recipient: {payout_recipient}");
 
     let task_channel: AsyncChannel<Task> = async_channel::unbounded();
@@ -16,6 +16,7 @@ use crate::args::ValidateArgs;
 use crate::channel::DuplexChannel;
 use crate::{proposals, requests};
 use anyhow::Context;
+use kailua_sync::transact::journal::{journal, JOURNAL_FILE};
 use opentelemetry::global::tracer;
 use opentelemetry::trace::{FutureExt, TraceContextExt, Tracer};
 use std::path::PathBuf;
@@ -40,6 +41,11 @@ pub async fn validate(
         args.proving.skip_derivation_proof = false;
     }
 
+    // Journal all submitted transactions
+    journal()
+        .open(&data_dir.join(JOURNAL_FILE))
+        .context("Journal::open")?;
+
     // We run two concurrent tasks, one for the chain, and one for the prover.
     // Both tasks communicate using the duplex channel
     let channel_pair = DuplexChannel::new_pair(4096);